@@ -4,10 +4,15 @@
 //! Use [`AttributeId`] with [`Attributes::stat`] for typed lookup.
 //! Use the HP/mana/stamina accessors on [`Attributes`] for game-visible units.
 
+use std::collections::BTreeSet;
 use std::fmt;
+use std::ops::Range;
 
 use serde::{Deserialize, Serialize};
 
+use crate::data::GameData;
+use crate::format::find_skills_offset;
+use crate::items::RawBits;
 use crate::utils::read_bits;
 use crate::utils::write_bits;
 use crate::utils::BytePosition;
@@ -177,6 +182,37 @@ impl fmt::Display for Stat {
     }
 }
 
+/// Which stats [`Attributes::to_bytes_with`] writes to the stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum AttributeOmission {
+    /// Write the stats that were present when parsed, plus any that became non-zero.
+    ///
+    /// Attributes built in code have no recorded set and write every stat.
+    #[default]
+    Mirror,
+    /// Follow the game and omit every zero-valued stat.
+    OmitZeros,
+}
+
+/// Stat id found in an attributes stream without a known bit width.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub(crate) struct UnknownStat {
+    pub stat_id: u32,
+    /// Position of the entry in the stream.
+    pub index: usize,
+}
+
+impl From<UnknownStat> for ParseHardError {
+    fn from(unknown_stat: UnknownStat) -> Self {
+        ParseHardError {
+            message: format!(
+                "Unknown attributes stat id {} at index {}; its bit width is not in the stat table.",
+                unknown_stat.stat_id, unknown_stat.index
+            ),
+        }
+    }
+}
+
 /// Character attributes as stored in the attributes bitstream.
 ///
 /// Stat names follow the save format's canonical stat keys.
@@ -198,6 +234,16 @@ pub struct Attributes {
     pub experience: Stat,
     pub gold: Stat,
     pub goldbank: Stat,
+    /// Stats outside [`AttributeId`], in stream order, with the id and width they were read with.
+    #[serde(default)]
+    pub extra: Vec<Stat>,
+    /// Stream bits from the first stat id with no known width through the trailer, kept by
+    /// lax parsing and written back after the other stats.
+    #[serde(default)]
+    pub unread: Option<RawBits>,
+    /// Stat ids read from the stream, or `None` when not parsed from bytes.
+    #[serde(default)]
+    present_stats: Option<BTreeSet<u32>>,
 }
 
 impl Attributes {
@@ -304,6 +350,14 @@ impl Attributes {
         self.maxstamina.value / 256
     }
 
+    /// Stat ids that were present in the parsed stream.
+    ///
+    /// The game omits zero-valued stats, so a fresh character usually lacks gold or experience.
+    /// Returns `None` for attributes that were not parsed from bytes.
+    pub fn present_stats(&self) -> Option<&BTreeSet<u32>> {
+        self.present_stats.as_ref()
+    }
+
     /// Character level mirrored by [`crate::character::Character::level`].
     pub const fn level(&self) -> u8 {
         self.level.value as u8
@@ -346,11 +400,42 @@ impl Attributes {
     /// Parse attributes from a packed bitstream and advance `byte_position`.
     ///
     /// Each entry is encoded as `9-bit header + value bits`.
-    /// Not every stat must be present in the stream.
+    /// Not every stat must be present in the stream. Stat ids outside [`AttributeId`]
+    /// are an error; use [`Attributes::parse_with`] to read them into [`Attributes::extra`].
     pub fn parse(
         byte_slice: &[u8],
         byte_position: &mut BytePosition,
     ) -> Result<Attributes, ParseHardError> {
+        let (attributes, unknown_stat) =
+            Attributes::parse_until_unknown(byte_slice, byte_position, None)?;
+        unknown_stat.map_or(Ok(attributes), |unknown_stat| Err(unknown_stat.into()))
+    }
+
+    /// Parse attributes using the stat bit widths (`CSvBits`) from `game_data`.
+    ///
+    /// Stats missing from `game_data` keep their default D2R width. Stat ids outside
+    /// [`AttributeId`] that `game_data` knows are kept in [`Attributes::extra`]; other ids
+    /// are an error. The widths are kept on each [`Stat`], so [`Attributes::to_bytes`]
+    /// writes them back unchanged.
+    pub fn parse_with(
+        byte_slice: &[u8],
+        byte_position: &mut BytePosition,
+        game_data: &GameData,
+    ) -> Result<Attributes, ParseHardError> {
+        let (attributes, unknown_stat) =
+            Attributes::parse_until_unknown(byte_slice, byte_position, Some(game_data))?;
+        unknown_stat.map_or(Ok(attributes), |unknown_stat| Err(unknown_stat.into()))
+    }
+
+    /// Parse attributes, stopping before the first stat id with no known bit width.
+    ///
+    /// `byte_position` is left on the unknown stat's header, since the rest of the
+    /// stream cannot be read without its width.
+    pub(crate) fn parse_until_unknown(
+        byte_slice: &[u8],
+        byte_position: &mut BytePosition,
+        game_data: Option<&GameData>,
+    ) -> Result<(Attributes, Option<UnknownStat>), ParseHardError> {
         if byte_slice.len() < 2 {
             return Err(ParseHardError {
                 message: format!(
@@ -370,51 +455,159 @@ impl Attributes {
         byte_position.current_byte = 2;
 
         let mut attributes = Attributes::default();
+        let mut present_stats = BTreeSet::new();
 
-        // Parse one extra header to allow a fully populated stream plus trailer.
-        for _i in 0..(STAT_NUMBER + 1) {
+        for index in 0.. {
+            let header_position = *byte_position;
             let header: u32 =
                 read_bits(byte_slice, byte_position, STAT_HEADER_LENGTH).map_err(|error| {
                     ParseHardError {
-                        message: format!("Error while parsing attributes header {_i}: {error}"),
+                        message: format!("Error while parsing attributes header {index}: {error}"),
                     }
                 })?;
             if header == SECTION_TRAILER {
                 break;
             }
 
-            let stat_id = AttributeId::from_index(header as usize).ok_or_else(|| ParseHardError {
-                message: format!(
-                    "Invalid attributes header value {header} at index {_i}; expected < {} or trailer.",
-                    STAT_NUMBER
-                ),
+            let table_stat =
+                game_data.and_then(|game_data| game_data.character_stat(header as u16));
+            let stat_id = AttributeId::from_index(header as usize);
+            let bit_length = match (stat_id, table_stat) {
+                (_, Some(table_stat)) => table_stat.bit_length,
+                (Some(stat_id), None) => stat_id.bit_length(),
+                (None, None) => {
+                    *byte_position = header_position;
+                    attributes.present_stats = Some(present_stats);
+                    return Ok((attributes, Some(UnknownStat { stat_id: header, index })));
+                }
+            };
+            let value = read_bits(byte_slice, byte_position, bit_length).map_err(|error| {
+                ParseHardError {
+                    message: format!(
+                        "Error while parsing attributes value {index} (header {header}): {error}"
+                    ),
+                }
             })?;
-            let value =
-                read_bits(byte_slice, byte_position, stat_id.bit_length()).map_err(|error| {
-                    ParseHardError {
-                        message: format!(
-                            "Error while parsing attributes value {_i} (header {header}): {error}"
-                        ),
-                    }
-                })?;
-            attributes.stat_mut(stat_id).value = value;
+            present_stats.insert(header);
+
+            match (stat_id, table_stat) {
+                (Some(stat_id), _) => {
+                    let stat = attributes.stat_mut(stat_id);
+                    stat.bit_length = bit_length;
+                    stat.value = value;
+                }
+                (None, table_stat) => attributes.extra.push(Stat {
+                    id: header,
+                    name: table_stat.map_or_else(String::new, |stat| stat.name.clone()),
+                    bit_length,
+                    value,
+                }),
+            }
         }
-        Ok(attributes)
+        attributes.present_stats = Some(present_stats);
+        Ok((attributes, None))
+    }
+
+    /// Keep the bits from `byte_position` through the trailer in [`Attributes::unread`].
+    ///
+    /// `byte_slice` ends where the skills section starts. The trailer is all ones and the
+    /// padding after it is zero, so the kept bits end at the last set bit.
+    pub(crate) fn keep_unread(
+        &mut self,
+        byte_slice: &[u8],
+        mut byte_position: BytePosition,
+    ) -> Result<(), ParseHardError> {
+        let bit_length = unread_bit_length(byte_slice, &byte_position);
+        self.unread = Some(RawBits::read(byte_slice, &mut byte_position, bit_length)?);
+        Ok(())
     }
 
-    /// Encode attributes into a byte-aligned bitstream.
+    /// Bit spans of every entry in a packed attributes stream, relative to its start.
+    ///
+    /// Each stat yields a `<name>.id` span for its 9-bit header and a `<name>` span for its value.
+    /// Widths come from `game_data` when given. Entries from the first stat id with no known
+    /// width through the trailer form one `unread` span, as kept by lax parsing.
+    pub(crate) fn bit_spans(
+        byte_slice: &[u8],
+        game_data: Option<&GameData>,
+    ) -> Result<Vec<(Range<usize>, String)>, ParseHardError> {
+        let mut unknown_position = BytePosition::default();
+        let (attributes, unknown_stat) =
+            Attributes::parse_until_unknown(byte_slice, &mut unknown_position, game_data)?;
+
+        let bit_offset = |position: &BytePosition| position.current_byte * 8 + position.current_bit;
+        let mut spans = vec![(0..SECTION_HEADER.len() * 8, "header".to_string())];
+        let mut byte_position = BytePosition { current_byte: SECTION_HEADER.len(), current_bit: 0 };
+        let mut extra_stats = attributes.extra.iter();
+        loop {
+            let header_start = bit_offset(&byte_position);
+            if unknown_stat.is_some() && header_start == bit_offset(&unknown_position) {
+                let skills_offset = find_skills_offset(byte_slice, byte_position.current_byte)
+                    .ok_or_else(|| ParseHardError {
+                        message: "Cannot find the skills section after an unknown attributes stat."
+                            .to_string(),
+                    })?;
+                let bit_length = unread_bit_length(&byte_slice[..skills_offset], &byte_position);
+                spans.push((header_start..header_start + bit_length, "unread".to_string()));
+                break;
+            }
+            let header = read_bits(byte_slice, &mut byte_position, STAT_HEADER_LENGTH)?;
+            let value_start = bit_offset(&byte_position);
+            if header == SECTION_TRAILER {
+                spans.push((header_start..value_start, "trailer".to_string()));
+                break;
+            }
+
+            // Headers before the unknown stat were validated by the parse above.
+            let (name, bit_length) = match AttributeId::from_index(header as usize) {
+                Some(stat_id) => (stat_id.name(), attributes.stat(stat_id).bit_length),
+                None => {
+                    let Some(stat) = extra_stats.next() else { break };
+                    (stat.name.as_str(), stat.bit_length)
+                }
+            };
+            let name = if name.is_empty() { format!("stat{header}") } else { name.to_string() };
+            read_bits(byte_slice, &mut byte_position, bit_length)?;
+            spans.push((header_start..value_start, format!("{name}.id")));
+            spans.push((value_start..bit_offset(&byte_position), name));
+        }
+        Ok(spans)
+    }
+
+    /// Encode attributes into a byte-aligned bitstream, mirroring the parsed stat set.
     pub fn to_bytes(&self) -> Result<Vec<u8>, ParseHardError> {
+        self.to_bytes_with(AttributeOmission::Mirror)
+    }
+
+    /// Encode attributes, choosing which stats to write with `omission`.
+    pub fn to_bytes_with(&self, omission: AttributeOmission) -> Result<Vec<u8>, ParseHardError> {
         let mut result: Vec<u8> = Vec::<u8>::new();
         let mut byte_position: BytePosition = BytePosition::default();
         result.extend_from_slice(&SECTION_HEADER);
         byte_position.current_byte += 2;
 
-        for stat_id in AttributeId::ALL {
-            let stat = self.stat(stat_id);
+        let known_stats = AttributeId::ALL.map(|stat_id| self.stat(stat_id));
+        for stat in known_stats.into_iter().chain(&self.extra) {
+            let written = stat.value != 0
+                || match omission {
+                    AttributeOmission::Mirror => self
+                        .present_stats
+                        .as_ref()
+                        .is_none_or(|present_stats| present_stats.contains(&stat.id)),
+                    AttributeOmission::OmitZeros => false,
+                };
+            if !written {
+                continue;
+            }
             write_bits(&mut result, &mut byte_position, stat.id, STAT_HEADER_LENGTH)?;
             write_bits(&mut result, &mut byte_position, stat.value, stat.bit_length)?;
         }
-        write_bits(&mut result, &mut byte_position, SECTION_TRAILER, STAT_HEADER_LENGTH)?;
+        match &self.unread {
+            Some(unread) => unread.write(&mut result, &mut byte_position)?,
+            None => {
+                write_bits(&mut result, &mut byte_position, SECTION_TRAILER, STAT_HEADER_LENGTH)?
+            }
+        }
         Ok(result)
     }
 
@@ -441,6 +634,16 @@ impl Attributes {
     }
 }
 
+/// Bits from `byte_position` through the last set bit of `byte_slice`.
+fn unread_bit_length(byte_slice: &[u8], byte_position: &BytePosition) -> usize {
+    let start = byte_position.current_byte * 8 + byte_position.current_bit;
+    let end = byte_slice
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |index| index * 8 + 8 - byte_slice[index].leading_zeros() as usize);
+    end.saturating_sub(start)
+}
+
 impl Default for Attributes {
     fn default() -> Self {
         let mut attributes = Attributes {
@@ -460,6 +663,9 @@ impl Default for Attributes {
             experience: Stat::default(),
             gold: Stat::default(),
             goldbank: Stat::default(),
+            extra: Vec::new(),
+            unread: None,
+            present_stats: None,
         };
         // Initialize all fields using static stat metadata.
         for stat_id in AttributeId::ALL {
@@ -97,6 +97,9 @@ fn test_write_and_read_attributes() {
             value: 45964,
             bit_length: 25,
         },
+        extra: Vec::new(),
+        unread: None,
+        present_stats: Some((0..16).collect()),
     };
     let result: Vec<u8> = expected_attributes.to_bytes().expect("attributes should serialize");
 
@@ -172,3 +175,32 @@ fn test_typed_stat_access() {
     assert_eq!(strength.name, crate::attributes::AttributeId::Strength.name());
     assert_eq!(strength.bit_length, crate::attributes::AttributeId::Strength.bit_length());
 }
+
+#[test]
+fn test_to_bytes_mirrors_parsed_stat_set() {
+    use crate::attributes::{AttributeOmission, Attributes};
+
+    let mut attributes = Attributes::new_save_defaults();
+    attributes.statpts.value = 5;
+    // Zero-valued stats are left out, as the game writes them.
+    let bytes = attributes.to_bytes_with(AttributeOmission::OmitZeros).unwrap();
+    assert!(bytes.len() < attributes.to_bytes().unwrap().len());
+
+    let mut parsed = Attributes::parse(&bytes, &mut crate::utils::BytePosition::default()).unwrap();
+    assert_eq!(parsed.present_stats().unwrap().len(), 12);
+    assert!(!parsed.present_stats().unwrap().contains(&13));
+    assert_eq!(parsed.to_bytes().unwrap(), bytes);
+
+    // Mirroring keeps a stat that dropped to zero and adds one that became non-zero.
+    parsed.statpts.value = 0;
+    parsed.gold.value = 100;
+    let mirrored =
+        Attributes::parse(&parsed.to_bytes().unwrap(), &mut crate::utils::BytePosition::default())
+            .unwrap();
+    assert_eq!(mirrored.present_stats().unwrap().len(), 13);
+    assert_eq!((mirrored.statpts.value, mirrored.gold.value), (0, 100));
+
+    let omitted = parsed.to_bytes_with(AttributeOmission::OmitZeros).unwrap();
+    let omitted = Attributes::parse(&omitted, &mut crate::utils::BytePosition::default()).unwrap();
+    assert!(!omitted.present_stats().unwrap().contains(&4));
+}
@@ -0,0 +1,282 @@
+//! `halbu` command-line tool for inspecting, validating and editing `.d2s` saves.
+//!
+//! Exit codes:
+//! - `0`: success
+//! - `1`: I/O, parse or encode failure
+//! - `2`: invalid command line
+//! - `3`: blocking validation or compatibility issues
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use halbu::attributes::AttributeId;
+use halbu::format::FormatId;
+use halbu::{CompatibilityChecks, CompatibilityIssue, IssueSeverity, ParseIssue, Save, Strictness};
+
+const EXIT_FAILURE: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+const EXIT_BLOCKING: u8 = 3;
+
+#[derive(Parser)]
+#[command(
+    name = "halbu",
+    version,
+    about = "Inspect and edit Diablo II and Diablo II: Resurrected saves."
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print header and character summary.
+    Info { path: PathBuf },
+    /// Report parse issues, validation issues and compatibility blockers.
+    Validate { path: PathBuf },
+    /// Re-encode a save to another format.
+    Convert {
+        path: PathBuf,
+        /// Target format (`v96`, `v99`, `v105`).
+        #[arg(long, value_parser = parse_format)]
+        to: FormatId,
+        /// Output path. Defaults to overwriting the input.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Encode even when compatibility checks report blocking issues.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Set character fields, e.g. `level=75 strength=220`.
+    ///
+    /// Keys: `name`, `level`, and attribute names (`strength`, `gold`, ...).
+    /// `level` also moves experience to that level's threshold; set `experience` after it
+    /// to override.
+    /// `hitpoints`, `maxhp`, `mana`, `maxmana`, `stamina` and `maxstamina` take in-game values.
+    Set {
+        path: PathBuf,
+        #[arg(required = true, value_name = "KEY=VALUE")]
+        assignments: Vec<String>,
+        /// Output path. Defaults to overwriting the input.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Write even when validation or compatibility checks report blocking issues.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the parsed save model.
+    Dump {
+        path: PathBuf,
+        /// Print the versioned JSON document instead of Rust debug output.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Info { path } => info(&path),
+        Command::Validate { path } => validate(&path),
+        Command::Convert { path, to, output, force } => convert(&path, to, output, force),
+        Command::Set { path, assignments, output, force } => {
+            set(&path, &assignments, output, force)
+        }
+        Command::Dump { path, json } => dump(&path, json),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::from(EXIT_FAILURE)
+        }
+    }
+}
+
+type CommandResult = Result<ExitCode, Box<dyn std::error::Error>>;
+
+fn parse_format(value: &str) -> Result<FormatId, String> {
+    let digits = value.strip_prefix(['v', 'V']).unwrap_or(value);
+    digits
+        .parse::<u32>()
+        .ok()
+        .and_then(FormatId::from_version)
+        .filter(|format| FormatId::encodable_formats().contains(format))
+        .ok_or_else(|| format!("unsupported format '{value}', expected v96, v99 or v105"))
+}
+
+fn read_save(path: &Path, strictness: Strictness) -> Result<Save, Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(path)?;
+    Ok(Save::parse(&bytes, strictness)?.save)
+}
+
+fn print_parse_issue(issue: &ParseIssue) {
+    let section = issue.section.as_deref().unwrap_or("-");
+    println!("parse {:?}/{:?} [{section}]: {}", issue.severity, issue.kind, issue.message);
+}
+
+fn print_compatibility_issues(issues: &[CompatibilityIssue]) -> bool {
+    for issue in issues {
+        let level = if issue.blocking { "blocking" } else { "warning" };
+        println!("compatibility {level} {:?}: {}", issue.code, issue.message);
+    }
+    issues.iter().any(|issue| issue.blocking)
+}
+
+fn info(path: &Path) -> CommandResult {
+    let bytes = std::fs::read(path)?;
+    let summary = Save::summarize(&bytes, Strictness::Lax)?;
+    let unknown = || "?".to_string();
+
+    println!("name:      {}", summary.name.unwrap_or_else(unknown));
+    println!("class:     {}", summary.class.map_or_else(unknown, |class| class.to_string()));
+    println!("level:     {}", summary.level.map_or_else(unknown, |level| level.to_string()));
+    println!("title:     {}", summary.title.unwrap_or_else(|| "-".to_string()));
+    println!("version:   {}", summary.version.map_or_else(unknown, |version| version.to_string()));
+    println!("edition:   {}", summary.edition.map_or("?", |edition| edition.label()));
+    println!(
+        "expansion: {}",
+        summary.expansion_type.map_or("?", |expansion_type| expansion_type.label())
+    );
+    for issue in &summary.issues {
+        print_parse_issue(issue);
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn validate(path: &Path) -> CommandResult {
+    let bytes = std::fs::read(path)?;
+    let parsed = Save::parse(&bytes, Strictness::Lax)?;
+
+    for issue in &parsed.issues {
+        print_parse_issue(issue);
+    }
+    let report = parsed.save.validate();
+    for issue in &report.issues {
+        let level = if issue.blocking { "blocking" } else { "warning" };
+        println!("validation {level} {:?}: {}", issue.code, issue.message);
+    }
+    let compatibility_blocked =
+        print_compatibility_issues(&parsed.save.check_compatibility(parsed.save.format()));
+
+    let parse_blocked =
+        parsed.issues.iter().any(|issue| matches!(issue.severity, IssueSeverity::Error));
+    if parse_blocked || report.has_blocking_issues() || compatibility_blocked {
+        return Ok(ExitCode::from(EXIT_BLOCKING));
+    }
+    println!("OK");
+    Ok(ExitCode::SUCCESS)
+}
+
+fn write_save(
+    save: &Save,
+    target: FormatId,
+    output: &Path,
+    force: bool,
+) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    if print_compatibility_issues(&save.check_compatibility(target)) && !force {
+        eprintln!("refusing to write: blocking compatibility issues (use --force to override)");
+        return Ok(ExitCode::from(EXIT_BLOCKING));
+    }
+
+    let checks = if force { CompatibilityChecks::Ignore } else { CompatibilityChecks::Enforce };
+    let bytes = save.encode_for(target, checks)?;
+    std::fs::write(output, bytes)?;
+    println!("wrote {}", output.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+fn convert(path: &Path, target: FormatId, output: Option<PathBuf>, force: bool) -> CommandResult {
+    let save = read_save(path, Strictness::Strict)?;
+    write_save(&save, target, output.as_deref().unwrap_or(path), force)
+}
+
+fn apply_assignment(save: &mut Save, assignment: &str) -> Result<(), String> {
+    let (key, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, found '{assignment}'"))?;
+
+    if key == "name" {
+        save.character.name = value.to_string();
+        return Ok(());
+    }
+
+    let number: u32 = value
+        .parse()
+        .map_err(|_| format!("'{key}' expects an unsigned integer, found '{value}'"))?;
+    if key == "level" {
+        let level = u8::try_from(number).map_err(|_| format!("level {number} is out of range"))?;
+        save.set_level_with_experience(level);
+        return Ok(());
+    }
+
+    let attribute = AttributeId::ALL
+        .into_iter()
+        .find(|attribute| attribute.name() == key)
+        .ok_or_else(|| format!("unknown key '{key}'"))?;
+    let attributes = &mut save.attributes;
+    match attribute {
+        AttributeId::Hitpoints => attributes.set_hp(number),
+        AttributeId::MaxHp => attributes.set_max_hp(number),
+        AttributeId::Mana => attributes.set_mana(number),
+        AttributeId::MaxMana => attributes.set_max_mana(number),
+        AttributeId::Stamina => attributes.set_stamina(number),
+        AttributeId::MaxStamina => attributes.set_max_stamina(number),
+        _ => {
+            let stat = match attribute {
+                AttributeId::Strength => &mut attributes.strength,
+                AttributeId::Energy => &mut attributes.energy,
+                AttributeId::Dexterity => &mut attributes.dexterity,
+                AttributeId::Vitality => &mut attributes.vitality,
+                AttributeId::StatPoints => &mut attributes.statpts,
+                AttributeId::NewSkills => &mut attributes.newskills,
+                AttributeId::Experience => &mut attributes.experience,
+                AttributeId::Gold => &mut attributes.gold,
+                AttributeId::GoldBank => &mut attributes.goldbank,
+                _ => return Err(format!("'{key}' cannot be set directly")),
+            };
+            if number > stat.max() {
+                return Err(format!("{key}={number} exceeds the maximum of {}", stat.max()));
+            }
+            stat.value = number;
+        }
+    }
+    Ok(())
+}
+
+fn set(path: &Path, assignments: &[String], output: Option<PathBuf>, force: bool) -> CommandResult {
+    let mut save = read_save(path, Strictness::Strict)?;
+    for assignment in assignments {
+        // Unknown keys and malformed values are invalid command lines, like clap's own errors.
+        if let Err(error) = apply_assignment(&mut save, assignment) {
+            eprintln!("error: {error}");
+            return Ok(ExitCode::from(EXIT_USAGE));
+        }
+    }
+
+    let report = save.validate();
+    for issue in &report.issues {
+        let level = if issue.blocking { "blocking" } else { "warning" };
+        println!("validation {level} {:?}: {}", issue.code, issue.message);
+    }
+    if report.has_blocking_issues() && !force {
+        eprintln!("refusing to write: blocking validation issues (use --force to override)");
+        return Ok(ExitCode::from(EXIT_BLOCKING));
+    }
+
+    write_save(&save, save.format(), output.as_deref().unwrap_or(path), force)
+}
+
+fn dump(path: &Path, json: bool) -> CommandResult {
+    let bytes = std::fs::read(path)?;
+    let parsed = Save::parse(&bytes, Strictness::Lax)?;
+    if json {
+        println!("{}", parsed.save.to_json_document()?);
+    } else {
+        println!("{parsed:#?}");
+    }
+    Ok(ExitCode::SUCCESS)
+}
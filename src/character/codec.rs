@@ -1,4 +1,7 @@
+use std::ops::Range;
+
 use crate::character::v105::CharacterCodecV105;
+use crate::character::v96::CharacterCodecV96;
 use crate::character::v99::CharacterCodecV99;
 use crate::character::Character;
 use crate::format::FormatId;
@@ -16,6 +19,9 @@ pub fn decode_for_format(
     character_section_bytes: &[u8],
 ) -> Result<Character, ParseHardError> {
     match format_id {
+        FormatId::V71 | FormatId::V87 | FormatId::V89 | FormatId::V92 | FormatId::V96 => {
+            CharacterCodecV96::decode(character_section_bytes)
+        }
         FormatId::V99 => CharacterCodecV99::decode(character_section_bytes),
         FormatId::V105 | FormatId::Unknown(_) => {
             CharacterCodecV105::decode(character_section_bytes)
@@ -28,6 +34,9 @@ pub fn encode_for_format(
     character: &Character,
 ) -> Result<Vec<u8>, ParseHardError> {
     match format_id {
+        FormatId::V71 | FormatId::V87 | FormatId::V89 | FormatId::V92 | FormatId::V96 => {
+            CharacterCodecV96::encode(character)
+        }
         FormatId::V99 => CharacterCodecV99::encode(character),
         FormatId::V105 | FormatId::Unknown(_) => CharacterCodecV105::encode(character),
     }
@@ -35,7 +44,21 @@ pub fn encode_for_format(
 
 pub fn expected_length_for_format(format_id: FormatId) -> usize {
     match format_id {
+        FormatId::V71 | FormatId::V87 | FormatId::V89 | FormatId::V92 | FormatId::V96 => {
+            CharacterCodecV96::CHARACTER_LENGTH
+        }
         FormatId::V99 => CharacterCodecV99::CHARACTER_LENGTH,
         FormatId::V105 | FormatId::Unknown(_) => CharacterCodecV105::CHARACTER_LENGTH,
     }
 }
+
+/// Named byte spans of the character section for a format, relative to its start.
+pub(crate) fn field_spans_for_format(format_id: FormatId) -> Vec<(Range<usize>, String)> {
+    match format_id {
+        FormatId::V71 | FormatId::V87 | FormatId::V89 | FormatId::V92 | FormatId::V96 => {
+            crate::character::v96::field_spans()
+        }
+        FormatId::V99 => crate::character::v99::field_spans(),
+        FormatId::V105 | FormatId::Unknown(_) => crate::character::v105::field_spans(),
+    }
+}
@@ -0,0 +1,133 @@
+//! Player experience thresholds from the D2R `experience.txt` table.
+
+#[cfg(test)]
+mod tests;
+
+/// Highest character level.
+pub const MAX_LEVEL: u8 = 99;
+
+/// Experience needed to reach each level, indexed by `level - 1`.
+///
+/// The level 99 entry is also the experience cap.
+const EXPERIENCE_THRESHOLDS: [u32; MAX_LEVEL as usize] = [
+    0,
+    500,
+    1_500,
+    3_750,
+    7_875,
+    14_175,
+    22_680,
+    32_886,
+    44_396,
+    57_715,
+    72_144,
+    90_180,
+    112_725,
+    140_906,
+    176_132,
+    220_165,
+    275_207,
+    344_008,
+    430_010,
+    537_513,
+    671_891,
+    839_864,
+    1_049_830,
+    1_312_287,
+    1_640_359,
+    2_050_449,
+    2_563_061,
+    3_203_826,
+    3_902_260,
+    4_663_553,
+    5_493_363,
+    6_397_855,
+    7_383_752,
+    8_458_379,
+    9_629_723,
+    10_906_488,
+    12_298_162,
+    13_815_086,
+    15_468_534,
+    17_270_791,
+    19_235_252,
+    21_376_515,
+    23_710_491,
+    26_254_525,
+    29_027_522,
+    32_050_088,
+    35_344_686,
+    38_935_798,
+    42_850_109,
+    47_116_709,
+    51_767_302,
+    56_836_449,
+    62_361_819,
+    68_384_473,
+    74_949_165,
+    82_104_680,
+    89_904_191,
+    98_405_658,
+    107_672_256,
+    117_772_849,
+    128_782_495,
+    140_783_010,
+    153_863_570,
+    168_121_381,
+    183_662_396,
+    200_602_101,
+    219_066_380,
+    239_192_444,
+    261_129_853,
+    285_041_630,
+    311_105_466,
+    339_515_048,
+    370_481_492,
+    404_234_916,
+    441_026_148,
+    481_128_591,
+    524_840_254,
+    572_485_967,
+    624_419_793,
+    681_027_665,
+    742_730_244,
+    809_986_056,
+    883_294_891,
+    963_201_521,
+    1_050_299_747,
+    1_145_236_814,
+    1_248_718_217,
+    1_361_512_946,
+    1_484_459_201,
+    1_618_470_619,
+    1_764_543_065,
+    1_923_762_030,
+    2_097_310_703,
+    2_286_478_756,
+    2_492_671_933,
+    2_717_422_497,
+    2_962_400_612,
+    3_229_426_756,
+    3_520_485_254,
+];
+
+/// Experience needed to reach `level`, or `None` outside `1..=99`.
+pub fn experience_for_level(level: u8) -> Option<u32> {
+    level.checked_sub(1).and_then(|index| EXPERIENCE_THRESHOLDS.get(usize::from(index))).copied()
+}
+
+/// Experience the character can hold at `level`: from its threshold up to just below the next.
+///
+/// At level 99 the range ends at the experience cap. Returns `None` outside `1..=99`.
+pub fn experience_range_for_level(level: u8) -> Option<std::ops::RangeInclusive<u32>> {
+    let start = experience_for_level(level)?;
+    let end = experience_for_level(level + 1).map_or(start, |next| next - 1);
+    Some(start..=end)
+}
+
+/// Resolve the player level reached with `experience`.
+///
+/// Experience above the cap resolves to level 99.
+pub fn level_from_experience(experience: u32) -> u8 {
+    EXPERIENCE_THRESHOLDS.partition_point(|&threshold| threshold <= experience) as u8
+}
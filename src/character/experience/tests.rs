@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn thresholds_increase_and_match_known_levels() {
+    assert!(EXPERIENCE_THRESHOLDS.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(experience_for_level(2), Some(500));
+    assert_eq!(experience_for_level(90), Some(1_618_470_619));
+    assert_eq!(experience_for_level(0), None);
+    assert_eq!(experience_for_level(100), None);
+}
+
+#[test]
+fn level_from_experience_uses_thresholds() {
+    assert_eq!(level_from_experience(0), 1);
+    assert_eq!(level_from_experience(499), 1);
+    assert_eq!(level_from_experience(500), 2);
+    assert_eq!(level_from_experience(3_520_485_253), 98);
+    assert_eq!(level_from_experience(u32::MAX), MAX_LEVEL);
+
+    for level in 1..=MAX_LEVEL {
+        let range = experience_range_for_level(level).unwrap();
+        assert_eq!(level_from_experience(*range.start()), level);
+        assert_eq!(level_from_experience(*range.end()), level);
+    }
+    assert_eq!(experience_range_for_level(99), Some(3_520_485_254..=3_520_485_254));
+}
@@ -8,9 +8,9 @@ use std::fmt;
 
 use bit::BitIndex;
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, Bytes};
+use serde_with::serde_as;
 
-use crate::utils::get_sys_time_in_secs;
+use crate::utils::{get_sys_time_in_secs, HexBytes};
 use crate::Act;
 use crate::Class;
 use crate::Difficulty;
@@ -20,16 +20,22 @@ use mercenary::Mercenary;
 
 pub mod codec;
 pub mod common;
+pub mod experience;
 pub mod mercenary;
+mod skill_binding;
 #[cfg(test)]
 mod tests;
 pub mod v105;
+pub mod v96;
 pub mod v99;
 
 pub use codec::decode_for_format;
 pub use codec::encode_for_format;
 pub use codec::expected_length_for_format;
 pub use codec::CharacterCodec;
+pub use skill_binding::{
+    SkillBinding, SkillBindingError, SkillBindingSlot, HOTKEY_COUNT, UNASSIGNED_SKILL,
+};
 pub use v105::CharacterCodecV105;
 pub use v99::CharacterCodecV99;
 
@@ -56,26 +62,27 @@ pub struct Character {
     level: u8,
     /// Last-played timestamp as stored in the save.
     pub last_played: u32,
-    /// Assigned skill ids in the skill bar/hotkeys area.
-    pub assigned_skills: [u32; 16],
+    /// Assigned skill ids in the skill bar/hotkeys area. See [`Character::binding`].
+    pub assigned_skills: [u32; HOTKEY_COUNT],
     pub left_mouse_skill: u32,
     pub right_mouse_skill: u32,
     pub left_mouse_switch_skill: u32,
     pub right_mouse_switch_skill: u32,
     /// Legacy menu appearance bytes.
+    #[serde_as(as = "HexBytes")]
     pub menu_appearance: [u8; 32],
     pub difficulty: Difficulty,
     pub act: Act,
     pub map_seed: u32,
     pub mercenary: Mercenary,
     /// Resurrected preview appearance bytes.
-    #[serde_as(as = "Bytes")]
+    #[serde_as(as = "HexBytes")]
     pub resurrected_menu_appearance: [u8; 48],
     /// Character name string encoded in the character section.
     pub name: String,
     /// Raw encoded character section bytes used to preserve unknown regions on write.
     #[serde(default)]
-    #[serde_as(as = "Bytes")]
+    #[serde_as(as = "HexBytes")]
     pub raw_section: Vec<u8>,
 }
 
@@ -164,7 +171,7 @@ impl Default for Character {
             class: DEFAULT_CLASS,
             level: 1,
             last_played: get_sys_time_in_secs(),
-            assigned_skills: [0x0000FFFF; 16],
+            assigned_skills: [UNASSIGNED_SKILL; HOTKEY_COUNT],
             left_mouse_skill: 0,
             right_mouse_skill: 0,
             left_mouse_switch_skill: 0,
@@ -0,0 +1,156 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::Character;
+use crate::data::GameData;
+use crate::skills::{d2r_skill_slot, NamedSkillError};
+use crate::Class;
+
+/// Raw value the save stores for an empty hotkey.
+pub const UNASSIGNED_SKILL: u32 = 0x0000_FFFF;
+/// Number of skill hotkeys stored in the character section.
+pub const HOTKEY_COUNT: usize = 16;
+
+/// Skill assigned to a hotkey or mouse button.
+///
+/// Bindings hold global skill ids, so they can name skills outside the class tree such as
+/// Attack (id 0) or skills granted by items.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, Serialize, Deserialize)]
+pub enum SkillBinding {
+    Unassigned,
+    /// Global skill id (`*Id` column of `skills.txt`).
+    Skill(u16),
+}
+
+impl From<u32> for SkillBinding {
+    /// Read a raw binding. Values outside the 16-bit id range read as unassigned.
+    fn from(raw: u32) -> Self {
+        match u16::try_from(raw) {
+            Ok(skill_id) if u32::from(skill_id) != UNASSIGNED_SKILL => Self::Skill(skill_id),
+            _ => Self::Unassigned,
+        }
+    }
+}
+
+impl From<SkillBinding> for u32 {
+    fn from(binding: SkillBinding) -> u32 {
+        match binding {
+            SkillBinding::Unassigned => UNASSIGNED_SKILL,
+            SkillBinding::Skill(skill_id) => u32::from(skill_id),
+        }
+    }
+}
+
+impl SkillBinding {
+    /// Class-local [`crate::skills::SkillPoints`] slot of the bound skill in the default
+    /// D2R tree, or `None` when unassigned or outside the class tree.
+    pub fn class_slot_d2r(self, class: Class) -> Result<Option<usize>, NamedSkillError> {
+        match self {
+            Self::Unassigned => Ok(None),
+            Self::Skill(skill_id) => d2r_skill_slot(class, skill_id),
+        }
+    }
+
+    /// [`SkillBinding::class_slot_d2r`] with the skill table from `game_data`.
+    pub fn class_slot(
+        self,
+        game_data: &GameData,
+        class: Class,
+    ) -> Result<Option<usize>, NamedSkillError> {
+        match self {
+            Self::Unassigned => Ok(None),
+            Self::Skill(skill_id) => game_data.skill_slot(class, skill_id),
+        }
+    }
+}
+
+/// Hotkey or mouse button that holds a [`SkillBinding`].
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, Serialize, Deserialize)]
+pub enum SkillBindingSlot {
+    /// Hotkey `0..16`, shown in game as F1 to F16 by default.
+    Hotkey(usize),
+    LeftMouse,
+    RightMouse,
+    LeftMouseSwitch,
+    RightMouseSwitch,
+}
+
+impl fmt::Display for SkillBindingSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hotkey(index) => write!(f, "F{}", index + 1),
+            Self::LeftMouse => write!(f, "Left mouse"),
+            Self::RightMouse => write!(f, "Right mouse"),
+            Self::LeftMouseSwitch => write!(f, "Left mouse (weapon switch)"),
+            Self::RightMouseSwitch => write!(f, "Right mouse (weapon switch)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkillBindingError {
+    /// The hotkey index is outside `0..16`.
+    InvalidHotkey(usize),
+    /// The skill belongs to the class tree but has no points.
+    NoSkillPoints { slot: SkillBindingSlot, skill_id: u16, skill_index: usize },
+    /// The skill id could not be checked against the class tree.
+    Unresolved(NamedSkillError),
+}
+
+impl fmt::Display for SkillBindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHotkey(index) => {
+                write!(f, "Invalid hotkey {index}, expected 0..{HOTKEY_COUNT}")
+            }
+            Self::NoSkillPoints { slot, skill_id, skill_index } => write!(
+                f,
+                "Cannot bind skill {skill_id} to {slot}: skill slot {skill_index} has no points"
+            ),
+            Self::Unresolved(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for SkillBindingError {}
+
+impl From<NamedSkillError> for SkillBindingError {
+    fn from(error: NamedSkillError) -> Self {
+        Self::Unresolved(error)
+    }
+}
+
+impl Character {
+    /// Skill bound to a hotkey or mouse button. Returns `None` for a hotkey outside `0..16`.
+    pub fn binding(&self, slot: SkillBindingSlot) -> Option<SkillBinding> {
+        let raw = match slot {
+            SkillBindingSlot::Hotkey(index) => *self.assigned_skills.get(index)?,
+            SkillBindingSlot::LeftMouse => self.left_mouse_skill,
+            SkillBindingSlot::RightMouse => self.right_mouse_skill,
+            SkillBindingSlot::LeftMouseSwitch => self.left_mouse_switch_skill,
+            SkillBindingSlot::RightMouseSwitch => self.right_mouse_switch_skill,
+        };
+        Some(SkillBinding::from(raw))
+    }
+
+    /// Bind a skill without checking skill points. See [`crate::Save::bind_skill`].
+    pub fn set_binding(
+        &mut self,
+        slot: SkillBindingSlot,
+        binding: SkillBinding,
+    ) -> Result<(), SkillBindingError> {
+        let target = match slot {
+            SkillBindingSlot::Hotkey(index) => self
+                .assigned_skills
+                .get_mut(index)
+                .ok_or(SkillBindingError::InvalidHotkey(index))?,
+            SkillBindingSlot::LeftMouse => &mut self.left_mouse_skill,
+            SkillBindingSlot::RightMouse => &mut self.right_mouse_skill,
+            SkillBindingSlot::LeftMouseSwitch => &mut self.left_mouse_switch_skill,
+            SkillBindingSlot::RightMouseSwitch => &mut self.right_mouse_switch_skill,
+        };
+        *target = u32::from(binding);
+        Ok(())
+    }
+}
@@ -204,3 +204,32 @@ fn title_helper_maps_default_d2r_rules() {
     character.progression = 9;
     assert_eq!(character.title_d2r(ExpansionType::Expansion), None);
 }
+
+#[test]
+fn skill_bindings_read_and_write_raw_ids() {
+    let mut character = Character::default_class(Class::Sorceress);
+    character.assigned_skills[..5].copy_from_slice(&[40, 59, 54, 42, 65535]);
+    character.left_mouse_skill = 0;
+
+    let f2 = character.binding(SkillBindingSlot::Hotkey(1)).unwrap();
+    assert_eq!(f2, SkillBinding::Skill(59));
+    // Blizzard, in slot order after the first Sorceress skill id (36).
+    assert_eq!(f2.class_slot_d2r(Class::Sorceress).unwrap(), Some(23));
+    assert_eq!(crate::skills::d2r_skill_name(Class::Sorceress, 23).unwrap(), "Blizzard");
+    assert_eq!(character.binding(SkillBindingSlot::Hotkey(4)), Some(SkillBinding::Unassigned));
+    assert_eq!(character.binding(SkillBindingSlot::Hotkey(16)), None);
+    // Attack is outside the class tree.
+    let attack = character.binding(SkillBindingSlot::LeftMouse).unwrap();
+    assert_eq!(attack.class_slot_d2r(Class::Sorceress).unwrap(), None);
+    assert_eq!(SkillBinding::from(0x0001_0025), SkillBinding::Unassigned);
+
+    character.set_binding(SkillBindingSlot::Hotkey(1), SkillBinding::Unassigned).unwrap();
+    character.set_binding(SkillBindingSlot::RightMouseSwitch, SkillBinding::Skill(54)).unwrap();
+    assert_eq!(character.assigned_skills[1], UNASSIGNED_SKILL);
+    assert_eq!(character.right_mouse_switch_skill, 54);
+    assert_eq!(
+        character.set_binding(SkillBindingSlot::Hotkey(16), SkillBinding::Unassigned),
+        Err(SkillBindingError::InvalidHotkey(16))
+    );
+    assert_eq!(SkillBindingSlot::Hotkey(0).to_string(), "F1");
+}
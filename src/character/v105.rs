@@ -52,6 +52,47 @@ pub const RANGE_UNKNOWN_REGION_FOUR: Range<usize> = 351..387;
 
 const ASSIGNED_SKILL_SLOT_COUNT: usize = 16;
 
+/// Named byte spans of the character section, relative to its start.
+///
+/// Bytes not listed here are not interpreted by the codec.
+pub(crate) fn field_spans() -> Vec<(Range<usize>, String)> {
+    let span = |offset: usize, length: usize| offset..(offset + length);
+    let mut spans = vec![
+        (span(OFFSET_WEAPON_SET, 4), "weapon_set".to_string()),
+        (span(OFFSET_STATUS, 1), "status".to_string()),
+        (span(OFFSET_PROGRESSION, 1), "progression".to_string()),
+        (span(OFFSET_CLASS, 1), "class".to_string()),
+        (span(OFFSET_RESERVED_VERSION_MARKER_ONE, 1), "reserved_version_marker_one".to_string()),
+        (span(OFFSET_RESERVED_VERSION_MARKER_TWO, 1), "reserved_version_marker_two".to_string()),
+        (span(OFFSET_LEVEL, 1), "level".to_string()),
+        (span(OFFSET_LAST_PLAYED, 4), "last_played".to_string()),
+        (RANGE_RESERVED_CHECKSUM_MASK, "reserved_checksum_mask".to_string()),
+    ];
+    for assigned_skill_index in 0..ASSIGNED_SKILL_SLOT_COUNT {
+        let skill_offset = RANGE_ASSIGNED_SKILLS.start + (assigned_skill_index * 4);
+        spans.push((span(skill_offset, 4), format!("assigned_skills[{assigned_skill_index}]")));
+    }
+    spans.extend([
+        (span(OFFSET_LEFT_MOUSE_SKILL, 4), "left_mouse_skill".to_string()),
+        (span(OFFSET_RIGHT_MOUSE_SKILL, 4), "right_mouse_skill".to_string()),
+        (span(OFFSET_LEFT_MOUSE_SWITCH_SKILL, 4), "left_mouse_switch_skill".to_string()),
+        (span(OFFSET_RIGHT_MOUSE_SWITCH_SKILL, 4), "right_mouse_switch_skill".to_string()),
+        (RANGE_MENU_APPEARANCE, "menu_appearance".to_string()),
+        (RANGE_DIFFICULTY, "difficulty".to_string()),
+        (span(OFFSET_MAP_SEED, 4), "map_seed".to_string()),
+        (RANGE_MERCENARY, "mercenary".to_string()),
+        (RANGE_UNKNOWN_REGION_ONE, "unknown_region_one".to_string()),
+        (RANGE_UNKNOWN_REGION_TWO.start..OFFSET_MODE_MARKER, "unknown_region_two".to_string()),
+        (span(OFFSET_MODE_MARKER, 1), "mode_marker".to_string()),
+        (OFFSET_MODE_MARKER + 1..RANGE_UNKNOWN_REGION_TWO.end, "unknown_region_two".to_string()),
+        (RANGE_RESURRECTED_MENU_APPEARANCE, "resurrected_menu_appearance".to_string()),
+        (RANGE_NAME, "name".to_string()),
+        (RANGE_UNKNOWN_REGION_THREE, "unknown_region_three".to_string()),
+        (RANGE_UNKNOWN_REGION_FOUR, "unknown_region_four".to_string()),
+    ]);
+    spans
+}
+
 pub fn mode_marker(character: &Character) -> Option<u8> {
     if character.raw_section.len() != V105_CHARACTER_LENGTH {
         return None;
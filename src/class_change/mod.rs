@@ -0,0 +1,168 @@
+//! Class conversion that keeps skills, bindings, appearance and base stats consistent.
+//!
+//! Changing `character.class` alone leaves the previous class's skill points, hotkeys and
+//! menu appearance in place. [`crate::Save::change_class`] checks the class against the
+//! save's format and expansion type with the same rules as
+//! [`crate::Save::check_compatibility`], then:
+//!
+//! - refunds or clears every skill slot, depending on [`crate::class_change::ClassChangePolicy`],
+//! - resets strength, dexterity, vitality and energy to the new class base and refunds
+//!   earned stat points, as [`crate::Save::respec_stats`] does,
+//! - unassigns hotkeys and sets mouse buttons to Attack where they held a class skill,
+//! - resets both menu appearance blocks so the game redraws the character from its gear.
+//!
+//! Bindings to skills outside both class trees, such as Attack or skills from items, are
+//! kept, as are bindings that cannot be resolved against a tree. Quests, waypoints and
+//! items are not touched.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::character::{SkillBinding, SkillBindingSlot, HOTKEY_COUNT};
+use crate::data::{ClassData, GameData};
+use crate::respec::{self, RespecError};
+use crate::{format, Class, CompatibilityIssue, Save};
+
+#[cfg(test)]
+mod tests;
+
+/// What happens to the skill points spent in the previous class's tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ClassChangePolicy {
+    /// Clear every skill slot and make all points earned from levels and quest rewards
+    /// unspent, as Akara's respec does.
+    #[default]
+    RefundSkills,
+    /// Clear every skill slot and drop the points spent in it. Unspent points are kept.
+    ClearSkills,
+}
+
+/// What [`crate::Save::change_class`] changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassChangeReport {
+    pub previous_class: Class,
+    pub class: Class,
+    /// Points that were spent in skill slots before the change.
+    pub skill_points_cleared: u32,
+    /// Unspent skill points after the change.
+    pub newskills: u32,
+    /// Unspent stat points after base stats were reset.
+    pub statpts: u32,
+    /// Hotkeys and mouse buttons whose class skill was replaced.
+    pub bindings_reset: Vec<SkillBindingSlot>,
+    /// Whether either menu appearance block differed from the blank default.
+    pub appearance_reset: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum ClassChangeError {
+    /// The class cannot be used with the save's format or expansion type.
+    Incompatible(Vec<CompatibilityIssue>),
+    /// No class table row for the new class, so base stats are unknown.
+    NoClassData(Class),
+}
+
+impl fmt::Display for ClassChangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Incompatible(issues) => {
+                let messages: Vec<&str> =
+                    issues.iter().map(|issue| issue.message.as_str()).collect();
+                write!(f, "{}", messages.join(" "))
+            }
+            Self::NoClassData(class) => write!(f, "No class base stats for class {class}"),
+        }
+    }
+}
+
+impl std::error::Error for ClassChangeError {}
+
+impl From<RespecError> for ClassChangeError {
+    fn from(error: RespecError) -> Self {
+        match error {
+            RespecError::NoClassData(class) => Self::NoClassData(class),
+        }
+    }
+}
+
+const BINDING_SLOTS: [SkillBindingSlot; 4] = [
+    SkillBindingSlot::LeftMouse,
+    SkillBindingSlot::RightMouse,
+    SkillBindingSlot::LeftMouseSwitch,
+    SkillBindingSlot::RightMouseSwitch,
+];
+
+/// Whether `binding` names a skill in the tree of `class`. Ids that cannot be resolved, for
+/// example because the class has no skill table, are not class skills and are kept.
+fn is_class_skill(game_data: Option<&GameData>, binding: SkillBinding, class: Class) -> bool {
+    let slot = match game_data {
+        Some(game_data) => binding.class_slot(game_data, class),
+        None => binding.class_slot_d2r(class),
+    };
+    matches!(slot, Ok(Some(_)))
+}
+
+pub(crate) fn change_class(
+    save: &mut Save,
+    game_data: Option<&GameData>,
+    class: Class,
+    policy: ClassChangePolicy,
+) -> Result<ClassChangeReport, ClassChangeError> {
+    let issues: Vec<CompatibilityIssue> =
+        format::class_compatibility_issues(class, save.expansion_type(), save.format())
+            .into_iter()
+            .filter(|issue| issue.blocking)
+            .collect();
+    if !issues.is_empty() {
+        return Err(ClassChangeError::Incompatible(issues));
+    }
+    let class_data: ClassData = respec::class_data(game_data, class)?;
+
+    let previous_class = save.character.class;
+    let skill_points_cleared: u32 =
+        save.skills.points.iter().map(|points| u32::from(*points)).sum();
+    save.character.class = class;
+    match policy {
+        ClassChangePolicy::RefundSkills => respec::respec_skills(save, &class_data),
+        ClassChangePolicy::ClearSkills => save.skills.set_all(0),
+    }
+    respec::respec_stats(save, &class_data);
+
+    let mut bindings_reset = Vec::new();
+    let slots = (0..HOTKEY_COUNT).map(SkillBindingSlot::Hotkey).chain(BINDING_SLOTS);
+    for slot in slots {
+        let Some(binding) = save.character.binding(slot) else {
+            continue;
+        };
+        let replacement = match slot {
+            SkillBindingSlot::Hotkey(_) => SkillBinding::Unassigned,
+            // Mouse buttons always hold a skill; Attack is global skill 0.
+            _ => SkillBinding::Skill(0),
+        };
+        if binding == replacement
+            || !(is_class_skill(game_data, binding, previous_class)
+                || is_class_skill(game_data, binding, class))
+        {
+            continue;
+        }
+        save.character.set_binding(slot, replacement).expect("binding slots are in range");
+        bindings_reset.push(slot);
+    }
+
+    let blank = crate::character::Character::default();
+    let appearance_reset = save.character.menu_appearance != blank.menu_appearance
+        || save.character.resurrected_menu_appearance != blank.resurrected_menu_appearance;
+    save.character.menu_appearance = blank.menu_appearance;
+    save.character.resurrected_menu_appearance = blank.resurrected_menu_appearance;
+
+    Ok(ClassChangeReport {
+        previous_class,
+        class,
+        skill_points_cleared,
+        newskills: save.attributes.newskills.value,
+        statpts: save.attributes.statpts.value,
+        bindings_reset,
+        appearance_reset,
+    })
+}
@@ -0,0 +1,124 @@
+use super::*;
+use crate::character::UNASSIGNED_SKILL;
+use crate::format::FormatId;
+use crate::quests::QuestId;
+use crate::{CompatibilityCode, Difficulty, ExpansionType};
+
+fn sorceress() -> Save {
+    let mut save = Save::new(FormatId::V99, Class::Sorceress);
+    save.set_level_with_experience(30);
+    save.grant_quest_reward(Difficulty::Normal, QuestId::DenOfEvil);
+    save.skills.set_by_name_d2r(Class::Sorceress, "Ice Bolt", 10).unwrap();
+    save.skills.set_by_name_d2r(Class::Sorceress, "Frozen Armor", 5).unwrap();
+    save.attributes.newskills.value = 15;
+    save.attributes.energy.value += 50;
+    save.attributes.statpts.value = 95;
+    // Ice Bolt, Battle Orders from an item, and Frozen Armor on the right mouse button.
+    save.character.assigned_skills[..3].copy_from_slice(&[39, 149, 40]);
+    save.character.left_mouse_skill = 0;
+    save.character.right_mouse_skill = 40;
+    save.character.menu_appearance[0] = 0x39;
+    save
+}
+
+#[test]
+fn change_class_refunds_skills_and_resets_class_state() {
+    let mut save = sorceress();
+    let report = save.change_class(Class::Paladin, ClassChangePolicy::RefundSkills).unwrap();
+
+    assert_eq!(
+        report,
+        ClassChangeReport {
+            previous_class: Class::Sorceress,
+            class: Class::Paladin,
+            skill_points_cleared: 15,
+            newskills: 30,
+            statpts: 145,
+            bindings_reset: vec![
+                SkillBindingSlot::Hotkey(0),
+                SkillBindingSlot::Hotkey(2),
+                SkillBindingSlot::RightMouse,
+            ],
+            appearance_reset: true,
+        }
+    );
+    assert_eq!(save.character.class, Class::Paladin);
+    assert_eq!(save.skills.points, [0; 30]);
+    assert_eq!(&save.character.assigned_skills[..3], [UNASSIGNED_SKILL, 149, UNASSIGNED_SKILL]);
+    assert_eq!((save.character.left_mouse_skill, save.character.right_mouse_skill), (0, 0));
+    assert_eq!(save.attributes.strength.value, 25);
+    assert!(save.validate().issues.is_empty(), "{:?}", save.validate().issues);
+}
+
+#[test]
+fn change_class_can_clear_skills_without_refund() {
+    let mut save = sorceress();
+    let report = save.change_class(Class::Amazon, ClassChangePolicy::ClearSkills).unwrap();
+
+    assert_eq!((report.skill_points_cleared, report.newskills), (15, 15));
+    assert_eq!(save.skills.points, [0; 30]);
+    // Without the refund, the cleared points are missing from the budget.
+    assert!(save
+        .validate()
+        .issues
+        .iter()
+        .any(|issue| issue.code == crate::validation::ValidationCode::QuestRewardPointsMismatch));
+}
+
+#[test]
+fn change_class_follows_compatibility_rules() {
+    let mut save = sorceress();
+    save.set_expansion_type(ExpansionType::Classic);
+    let before = save.clone();
+
+    let error = save.change_class(Class::Druid, ClassChangePolicy::RefundSkills).unwrap_err();
+    let ClassChangeError::Incompatible(issues) = error else {
+        panic!("expected a compatibility error");
+    };
+    assert_eq!(issues[0].code, CompatibilityCode::ExpansionClassRequiresExpansionMode);
+    assert_eq!(save, before);
+
+    let error = save.change_class(Class::Warlock, ClassChangePolicy::RefundSkills).unwrap_err();
+    let ClassChangeError::Incompatible(issues) = error else {
+        panic!("expected a compatibility error");
+    };
+    let codes: Vec<_> = issues.iter().map(|issue| issue.code).collect();
+    assert_eq!(
+        codes,
+        [CompatibilityCode::WarlockRequiresRotW, CompatibilityCode::WarlockRequiresRotWExpansion]
+    );
+
+    // RotW allows the Warlock, but there is no built-in class table for it yet.
+    let mut rotw = Save::new(FormatId::V105, Class::Sorceress);
+    assert!(matches!(
+        rotw.change_class(Class::Warlock, ClassChangePolicy::RefundSkills),
+        Err(ClassChangeError::NoClassData(Class::Warlock))
+    ));
+    assert_eq!(rotw.character.class, Class::Sorceress);
+}
+
+#[test]
+fn change_class_from_warlock_keeps_item_skill_bindings() {
+    let mut save = Save::parse(
+        include_bytes!("../../assets/test/Warlock_v105.d2s"),
+        crate::Strictness::Strict,
+    )
+    .unwrap()
+    .save;
+    // Battle Orders from an item and an id outside every class tree.
+    save.character.assigned_skills[..2].copy_from_slice(&[149, 1000]);
+    let report = save.change_class(Class::Sorceress, ClassChangePolicy::RefundSkills).unwrap();
+
+    // Warlock skill ids are not mapped yet, so Miasma Bolt on the right mouse button stays too.
+    assert!(report.bindings_reset.is_empty());
+    assert_eq!(&save.character.assigned_skills[..2], [149, 1000]);
+    assert_eq!(save.character.right_mouse_skill, 395);
+
+    // A class without a skill table only resets skills of the new class, Magic Arrow here.
+    save.character.class = Class::Unknown(0xFF);
+    save.character.assigned_skills[0] = 6;
+    save.character.right_mouse_skill = 39;
+    let report = save.change_class(Class::Amazon, ClassChangePolicy::RefundSkills).unwrap();
+    assert_eq!(report.bindings_reset, [SkillBindingSlot::Hotkey(0)]);
+    assert_eq!(save.character.right_mouse_skill, 39);
+}
@@ -0,0 +1,564 @@
+//! Game data tables loaded from a mod's `.txt` files.
+//!
+//! The built-in constants (named D2R skills, attribute bit widths, mercenary XP rates)
+//! match the unmodded game. Overhaul mods change all of these; a [`GameData`] loaded
+//! from the mod's `skills.txt`, `itemstatcost.txt`, `hireling.txt` and `charstats.txt`
+//! can be passed to [`crate::attributes::Attributes::parse_with`],
+//! [`crate::skills::SkillPoints::set_by_name`], [`crate::Save::validate_with`] and
+//! [`crate::Save::respec_stats_with`] instead.
+//!
+//! Files are tab-separated with a header row, as shipped in the game's `data/global/excel`.
+//! Columns are looked up by name, so extra or reordered columns are fine.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::items::{ItemStatCost, StatCost};
+use crate::skills::{eq_normalized, has_no_ascii_alnum, NamedSkillError};
+use crate::{Class, ParseHardError};
+
+#[cfg(test)]
+mod tests;
+
+pub const SKILLS_FILE: &str = "skills.txt";
+pub const ITEM_STAT_COST_FILE: &str = "itemstatcost.txt";
+pub const HIRELING_FILE: &str = "hireling.txt";
+pub const CHARSTATS_FILE: &str = "charstats.txt";
+
+/// Separator rows in `charstats.txt` and `hireling.txt` carry this key and no data.
+const EXPANSION_SEPARATOR: &str = "Expansion";
+/// Optional `charstats.txt` column with the `skills.txt` class code. Without it the built-in
+/// D2R codes are used, so classes past the Assassin need it.
+const CLASS_CODE_COLUMN: &str = "*classcode";
+
+/// One class skill from `skills.txt`.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct SkillData {
+    /// Global skill id (`*Id` column).
+    pub id: u16,
+    /// Skill key (`skill` column), for example `"Fire Bolt"`.
+    pub name: String,
+    /// Character level needed to put points in the skill (`reqlevel`); 0 when absent.
+    #[serde(default)]
+    pub required_level: u8,
+    /// Skill keys that need a hard point first (`reqskill1` to `reqskill3`).
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+}
+
+/// Save metadata for one character stat from `itemstatcost.txt`.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterStatData {
+    pub id: u16,
+    /// Stat key (`Stat` column), for example `"strength"`.
+    pub name: String,
+    /// Width of the value in the attributes section (`CSvBits`).
+    pub bit_length: usize,
+}
+
+/// Mercenary type metadata from `hireling.txt`, keyed by the save's variant id.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct HirelingData {
+    pub variant_id: u16,
+    /// Hireling type (`Hireling` column), for example `"Rogue Scout"`.
+    pub name: String,
+    /// Experience multiplier (`Exp/Lvl` column).
+    pub xp_rate: u32,
+    /// Number of names this type can use, from the `NameFirst`..`NameLast` string keys.
+    pub name_count: Option<usize>,
+}
+
+/// Base stats and per-level gains for one class from `charstats.txt`.
+///
+/// Life, mana and stamina gains are stored in quarter points, as in the file.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct ClassData {
+    pub class: Class,
+    /// Three-letter code used by the `charclass` column of `skills.txt`, for example `"sor"`.
+    pub code: String,
+    pub strength: u32,
+    pub dexterity: u32,
+    pub energy: u32,
+    pub vitality: u32,
+    pub stamina: u32,
+    /// Life added on top of vitality at level 1 (`hpadd`).
+    pub hp_add: u32,
+    pub life_per_level: u32,
+    pub stamina_per_level: u32,
+    pub mana_per_level: u32,
+    pub life_per_vitality: u32,
+    pub stamina_per_vitality: u32,
+    pub mana_per_energy: u32,
+    pub stat_points_per_level: u32,
+    pub skill_points_per_level: u32,
+}
+
+/// Default D2R `charstats.txt` values in [`ClassData::from_row`] column order, by class id.
+const D2R_CLASS_STATS: [(&str, [u32; 14]); 7] = [
+    ("ama", [20, 25, 15, 20, 84, 30, 8, 4, 6, 12, 4, 6, 5, 1]),
+    ("sor", [10, 25, 35, 10, 74, 30, 4, 4, 8, 8, 4, 8, 5, 1]),
+    ("nec", [15, 25, 25, 15, 79, 30, 6, 4, 8, 8, 4, 8, 5, 1]),
+    ("pal", [25, 20, 15, 25, 89, 30, 8, 4, 6, 12, 4, 6, 5, 1]),
+    ("bar", [30, 20, 10, 25, 92, 30, 8, 4, 4, 16, 4, 4, 5, 1]),
+    ("dru", [15, 20, 20, 25, 84, 30, 6, 4, 8, 8, 4, 8, 5, 1]),
+    ("ass", [20, 20, 25, 20, 95, 30, 8, 5, 6, 12, 5, 7, 5, 1]),
+];
+
+impl ClassData {
+    /// Default D2R `charstats.txt` row for `class`.
+    ///
+    /// Returns `None` for the Warlock and unknown classes, which have no built-in row;
+    /// load their values through [`GameData`].
+    pub fn d2r(class: Class) -> Option<ClassData> {
+        if let Class::Unknown(_) = class {
+            return None;
+        }
+        let (code, values) = D2R_CLASS_STATS.get(usize::from(class.id()))?;
+        Some(ClassData::from_row(class, code.to_string(), *values))
+    }
+
+    /// Build from `str`, `dex`, `int`, `vit`, `stamina`, `hpadd`, `LifePerLevel`,
+    /// `StaminaPerLevel`, `ManaPerLevel`, `LifePerVitality`, `StaminaPerVitality`,
+    /// `ManaPerMagic`, `StatPerLevel` and `SkillsPerLevel`.
+    fn from_row(class: Class, code: String, values: [u32; 14]) -> ClassData {
+        ClassData {
+            class,
+            code,
+            strength: values[0],
+            dexterity: values[1],
+            energy: values[2],
+            vitality: values[3],
+            stamina: values[4],
+            hp_add: values[5],
+            life_per_level: values[6],
+            stamina_per_level: values[7],
+            mana_per_level: values[8],
+            life_per_vitality: values[9],
+            stamina_per_vitality: values[10],
+            mana_per_energy: values[11],
+            stat_points_per_level: values[12],
+            skill_points_per_level: values[13],
+        }
+    }
+}
+
+/// Game tables loaded from `.txt` files, used in place of the built-in D2R constants.
+#[derive(PartialEq, Eq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameData {
+    classes: Vec<ClassData>,
+    /// Class skills keyed by class id, in class-local slot order.
+    skills: BTreeMap<u8, Vec<SkillData>>,
+    item_stat_cost: ItemStatCost,
+    character_stats: BTreeMap<u16, CharacterStatData>,
+    hirelings: BTreeMap<u16, HirelingData>,
+}
+
+impl GameData {
+    /// Load `skills.txt`, `itemstatcost.txt`, `hireling.txt` and `charstats.txt` from `directory`.
+    ///
+    /// File names are matched case-insensitively, so `ItemStatCost.txt` works as well.
+    pub fn load(directory: impl AsRef<Path>) -> Result<GameData, ParseHardError> {
+        let directory = directory.as_ref();
+        let entries = std::fs::read_dir(directory).map_err(|error| ParseHardError {
+            message: format!("Cannot read game data directory {}: {error}", directory.display()),
+        })?;
+        let mut paths = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|error| ParseHardError {
+                message: format!(
+                    "Cannot read game data directory {}: {error}",
+                    directory.display()
+                ),
+            })?;
+            paths.push(entry.path());
+        }
+
+        let read = |file: &str| -> Result<String, ParseHardError> {
+            let path = paths
+                .iter()
+                .find(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.eq_ignore_ascii_case(file))
+                })
+                .ok_or_else(|| ParseHardError {
+                    message: format!("Missing {file} in {}.", directory.display()),
+                })?;
+            std::fs::read(path).map(|bytes| String::from_utf8_lossy(&bytes).into_owned()).map_err(
+                |error| ParseHardError {
+                    message: format!("Cannot read {}: {error}", path.display()),
+                },
+            )
+        };
+
+        GameData::parse(
+            &read(SKILLS_FILE)?,
+            &read(ITEM_STAT_COST_FILE)?,
+            &read(HIRELING_FILE)?,
+            &read(CHARSTATS_FILE)?,
+        )
+    }
+
+    /// Build game data from the contents of the four tables.
+    pub fn parse(
+        skills: &str,
+        item_stat_cost: &str,
+        hireling: &str,
+        charstats: &str,
+    ) -> Result<GameData, ParseHardError> {
+        let classes = parse_charstats(&Table::parse(CHARSTATS_FILE, charstats)?)?;
+        let skills = parse_skills(&Table::parse(SKILLS_FILE, skills)?, &classes)?;
+        let (item_stat_cost, character_stats) =
+            parse_item_stat_cost(&Table::parse(ITEM_STAT_COST_FILE, item_stat_cost)?)?;
+        let hirelings = parse_hirelings(&Table::parse(HIRELING_FILE, hireling)?)?;
+
+        Ok(GameData { classes, skills, item_stat_cost, character_stats, hirelings })
+    }
+
+    /// Classes in class id order.
+    pub fn classes(&self) -> &[ClassData] {
+        &self.classes
+    }
+
+    pub fn class_data(&self, class: Class) -> Option<&ClassData> {
+        self.classes.get(usize::from(class.id()))
+    }
+
+    /// Skills of `class` in class-local slot order, as stored in [`crate::skills::SkillPoints`].
+    pub fn skills(&self, class: Class) -> &[SkillData] {
+        self.skills.get(&class.id()).map_or(&[], Vec::as_slice)
+    }
+
+    /// Resolve a class-local skill slot to its name.
+    pub fn skill_name(&self, class: Class, skill_index: usize) -> Result<&str, NamedSkillError> {
+        let class_skills = self.class_skills(class)?;
+        class_skills
+            .get(skill_index)
+            .map(|skill| skill.name.as_str())
+            .ok_or(NamedSkillError::InvalidSkillIndex { class, skill_index })
+    }
+
+    /// Resolve a skill name to its class-local slot.
+    ///
+    /// Name matching is normalized to ignore case and non-alphanumeric characters.
+    pub fn skill_index(&self, class: Class, skill_name: &str) -> Result<usize, NamedSkillError> {
+        let unknown =
+            || NamedSkillError::UnknownSkillName { class, skill_name: skill_name.to_string() };
+        if has_no_ascii_alnum(skill_name) {
+            return Err(unknown());
+        }
+
+        self.class_skills(class)?
+            .iter()
+            .position(|skill| eq_normalized(&skill.name, skill_name))
+            .ok_or_else(unknown)
+    }
+
+    /// Resolve a class-local skill slot to its global skill id.
+    pub fn skill_id(&self, class: Class, skill_index: usize) -> Result<u16, NamedSkillError> {
+        self.class_skills(class)?
+            .get(skill_index)
+            .map(|skill| skill.id)
+            .ok_or(NamedSkillError::InvalidSkillIndex { class, skill_index })
+    }
+
+    /// Resolve a global skill id to its class-local slot, or `None` for skills outside the
+    /// class tree.
+    pub fn skill_slot(
+        &self,
+        class: Class,
+        skill_id: u16,
+    ) -> Result<Option<usize>, NamedSkillError> {
+        Ok(self.class_skills(class)?.iter().position(|skill| skill.id == skill_id))
+    }
+
+    fn class_skills(&self, class: Class) -> Result<&[SkillData], NamedSkillError> {
+        self.skills
+            .get(&class.id())
+            .map(Vec::as_slice)
+            .ok_or(NamedSkillError::UnsupportedClass(class))
+    }
+
+    /// Item stat save widths, for [`crate::items::PropertyList`].
+    pub fn item_stat_cost(&self) -> &ItemStatCost {
+        &self.item_stat_cost
+    }
+
+    /// Character stat saved in the attributes section under `stat_id`, if any.
+    pub fn character_stat(&self, stat_id: u16) -> Option<&CharacterStatData> {
+        self.character_stats.get(&stat_id)
+    }
+
+    /// Iterate character stats in id order.
+    pub fn character_stats(&self) -> impl Iterator<Item = &CharacterStatData> {
+        self.character_stats.values()
+    }
+
+    /// Mercenary type for a save's mercenary variant id.
+    pub fn hireling(&self, variant_id: u16) -> Option<&HirelingData> {
+        self.hirelings.get(&variant_id)
+    }
+}
+
+/// Tab-separated table with a header row.
+struct Table<'a> {
+    file: &'static str,
+    columns: Vec<&'a str>,
+    rows: Vec<Vec<&'a str>>,
+}
+
+impl<'a> Table<'a> {
+    fn parse(file: &'static str, text: &'a str) -> Result<Table<'a>, ParseHardError> {
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        let columns = lines
+            .next()
+            .ok_or_else(|| ParseHardError { message: format!("{file} is empty.") })?
+            .split('\t')
+            .map(str::trim)
+            .collect();
+        let rows = lines.map(|line| line.split('\t').collect()).collect();
+        Ok(Table { file, columns, rows })
+    }
+
+    /// Index of the first column matching one of `names`, ignoring case.
+    fn find_column(&self, names: &[&str]) -> Option<usize> {
+        names.iter().find_map(|name| {
+            self.columns.iter().position(|column| column.eq_ignore_ascii_case(name))
+        })
+    }
+
+    fn column(&self, name: &str) -> Result<usize, ParseHardError> {
+        self.find_column(&[name]).ok_or_else(|| ParseHardError {
+            message: format!("{}: missing column `{name}`.", self.file),
+        })
+    }
+
+    /// Data rows with their 1-based line numbers, skipping rows with an empty `key_column`.
+    fn rows(&self, key_column: usize) -> impl Iterator<Item = (usize, &[&'a str])> {
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| (index + 2, row.as_slice()))
+            .filter(move |(_, row)| !cell(row, key_column).is_empty())
+    }
+
+    /// Numeric cell; empty cells read as zero.
+    fn number<T: FromStr + Default>(
+        &self,
+        line: usize,
+        row: &[&str],
+        column: usize,
+    ) -> Result<T, ParseHardError> {
+        let value = cell(row, column);
+        if value.is_empty() {
+            return Ok(T::default());
+        }
+        value.parse().map_err(|_| ParseHardError {
+            message: format!(
+                "{} line {line}: invalid number '{value}' in column `{}`.",
+                self.file, self.columns[column]
+            ),
+        })
+    }
+}
+
+fn cell<'a>(row: &[&'a str], column: usize) -> &'a str {
+    row.get(column).map_or("", |value| value.trim())
+}
+
+fn parse_charstats(table: &Table) -> Result<Vec<ClassData>, ParseHardError> {
+    let class_column = table.column("class")?;
+    let code_column = table.find_column(&[CLASS_CODE_COLUMN]);
+    let columns = [
+        "str",
+        "dex",
+        "int",
+        "vit",
+        "stamina",
+        "hpadd",
+        "LifePerLevel",
+        "StaminaPerLevel",
+        "ManaPerLevel",
+        "LifePerVitality",
+        "StaminaPerVitality",
+        "ManaPerMagic",
+        "StatPerLevel",
+        "SkillsPerLevel",
+    ]
+    .map(|name| table.column(name))
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()?;
+
+    let mut classes = Vec::new();
+    for (line, row) in table.rows(class_column) {
+        let name = cell(row, class_column);
+        if name == EXPANSION_SEPARATOR {
+            continue;
+        }
+
+        let mut values = [0u32; 14];
+        for (value, column) in values.iter_mut().zip(&columns) {
+            *value = table.number(line, row, *column)?;
+        }
+        let class_id = u8::try_from(classes.len()).map_err(|_| ParseHardError {
+            message: format!("{} line {line}: too many classes.", table.file),
+        })?;
+        let code = match code_column.map(|column| cell(row, column)) {
+            Some(code) if !code.is_empty() => code.to_ascii_lowercase(),
+            _ => D2R_CLASS_STATS
+                .get(usize::from(class_id))
+                .map(|(code, _)| code.to_string())
+                .ok_or_else(|| ParseHardError {
+                    message: format!(
+                        "{} line {line}: no class code for '{name}', add a `{CLASS_CODE_COLUMN}` column.",
+                        table.file
+                    ),
+                })?,
+        };
+        classes.push(ClassData::from_row(Class::from_id(class_id), code, values));
+    }
+    Ok(classes)
+}
+
+fn parse_skills(
+    table: &Table,
+    classes: &[ClassData],
+) -> Result<BTreeMap<u8, Vec<SkillData>>, ParseHardError> {
+    let name_column = table.column("skill")?;
+    let class_column = table.column("charclass")?;
+    let id_column = table.find_column(&["*Id", "Id"]);
+    let level_column = table.find_column(&["reqlevel"]);
+    let prerequisite_columns: Vec<usize> = ["reqskill1", "reqskill2", "reqskill3"]
+        .into_iter()
+        .filter_map(|name| table.find_column(&[name]))
+        .collect();
+
+    let mut skills: BTreeMap<u8, Vec<SkillData>> = BTreeMap::new();
+    for (row_index, (line, row)) in table.rows(name_column).enumerate() {
+        let id = match id_column {
+            Some(column) => table.number(line, row, column)?,
+            // Files without an id column list every skill in id order.
+            None => u16::try_from(row_index).unwrap_or(u16::MAX),
+        };
+
+        let code = cell(row, class_column);
+        if code.is_empty() {
+            continue;
+        }
+        let class = classes
+            .iter()
+            .find(|class| class.code.eq_ignore_ascii_case(code))
+            .ok_or_else(|| ParseHardError {
+                message: format!(
+                    "{} line {line}: class code '{code}' does not match any class in {CHARSTATS_FILE}.",
+                    table.file
+                ),
+            })?;
+        let required_level = match level_column {
+            Some(column) => table.number(line, row, column)?,
+            None => 0,
+        };
+        let prerequisites = prerequisite_columns
+            .iter()
+            .map(|column| cell(row, *column))
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+        skills.entry(class.class.id()).or_default().push(SkillData {
+            id,
+            name: cell(row, name_column).to_string(),
+            required_level,
+            prerequisites,
+        });
+    }
+
+    for class_skills in skills.values_mut() {
+        class_skills.sort_by_key(|skill| skill.id);
+    }
+    Ok(skills)
+}
+
+fn parse_item_stat_cost(
+    table: &Table,
+) -> Result<(ItemStatCost, BTreeMap<u16, CharacterStatData>), ParseHardError> {
+    let name_column = table.column("Stat")?;
+    let id_column = table.find_column(&["*ID", "ID"]).ok_or_else(|| ParseHardError {
+        message: format!("{}: missing column `*ID`.", table.file),
+    })?;
+    let save_bits_column = table.column("Save Bits")?;
+    let save_add_column = table.column("Save Add")?;
+    let save_param_bits_column = table.column("Save Param Bits")?;
+    let character_bits_column = table.column("CSvBits")?;
+
+    let mut item_stats = Vec::new();
+    let mut character_stats = BTreeMap::new();
+    for (line, row) in table.rows(name_column) {
+        let id: u16 = table.number(line, row, id_column)?;
+        let name = cell(row, name_column).to_string();
+
+        let value_bits: usize = table.number(line, row, save_bits_column)?;
+        if value_bits > 0 {
+            item_stats.push(StatCost {
+                id,
+                name: name.clone(),
+                value_bits,
+                param_bits: table.number(line, row, save_param_bits_column)?,
+                save_add: table.number(line, row, save_add_column)?,
+                grouped_stats: ItemStatCost::d2r()
+                    .get(id)
+                    .map_or_else(Vec::new, |stat| stat.grouped_stats.clone()),
+            });
+        }
+
+        let bit_length: usize = table.number(line, row, character_bits_column)?;
+        if bit_length > 0 {
+            character_stats.insert(id, CharacterStatData { id, name, bit_length });
+        }
+    }
+    Ok((ItemStatCost::new(item_stats), character_stats))
+}
+
+fn parse_hirelings(table: &Table) -> Result<BTreeMap<u16, HirelingData>, ParseHardError> {
+    let name_column = table.column("Hireling")?;
+    let id_column = table.column("Id")?;
+    let xp_rate_column = table.column("Exp/Lvl")?;
+    let name_first_column = table.column("NameFirst")?;
+    let name_last_column = table.column("NameLast")?;
+
+    let mut hirelings = BTreeMap::new();
+    for (line, row) in table.rows(name_column) {
+        let name = cell(row, name_column);
+        if name == EXPANSION_SEPARATOR {
+            continue;
+        }
+
+        // Each type has one row per difficulty and level band; they share the id and rate.
+        let variant_id: u16 = table.number(line, row, id_column)?;
+        if hirelings.contains_key(&variant_id) {
+            continue;
+        }
+        let name_count = name_number(cell(row, name_first_column))
+            .zip(name_number(cell(row, name_last_column)))
+            .and_then(|(first, last)| last.checked_sub(first))
+            .map(|span| span + 1);
+        hirelings.insert(
+            variant_id,
+            HirelingData {
+                variant_id,
+                name: name.to_string(),
+                xp_rate: table.number(line, row, xp_rate_column)?,
+                name_count,
+            },
+        );
+    }
+    Ok(hirelings)
+}
+
+/// Trailing number of a name string key, for example `41` for `merc41`.
+fn name_number(key: &str) -> Option<usize> {
+    let prefix_length = key.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    key[prefix_length..].parse().ok()
+}
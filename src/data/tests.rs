@@ -0,0 +1,178 @@
+use super::*;
+use crate::attributes::Attributes;
+use crate::items::Property;
+use crate::skills::SkillPoints;
+use crate::utils::BytePosition;
+use crate::validation::ValidationCode;
+use crate::{items, Save, Strictness};
+
+const DATA_DIRECTORY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/test/data");
+
+fn game_data() -> GameData {
+    GameData::load(DATA_DIRECTORY).expect("test game data should load")
+}
+
+#[test]
+fn load_reads_all_tables() {
+    let game_data = game_data();
+
+    assert_eq!(game_data.classes().len(), 7);
+    let assassin = game_data.class_data(Class::Assassin).unwrap();
+    assert_eq!(assassin.code, "ass");
+    assert_eq!((assassin.stamina_per_level, assassin.mana_per_energy), (5, 7));
+    assert_eq!(game_data.class_data(Class::Sorceress).unwrap().energy, 35);
+
+    let sorceress_skills: Vec<u16> =
+        game_data.skills(Class::Sorceress).iter().map(|skill| skill.id).collect();
+    assert_eq!(sorceress_skills, [36, 37]);
+    assert_eq!(game_data.skill_index(Class::Barbarian, "cleaving-strike").unwrap(), 2);
+    assert_eq!(game_data.skill_name(Class::Amazon, 0).unwrap(), "Magic Arrow");
+    let cleaving_strike = &game_data.skills(Class::Barbarian)[2];
+    assert_eq!(
+        (cleaving_strike.required_level, cleaving_strike.prerequisites.as_slice()),
+        (6, &["Bash".to_string()][..])
+    );
+    assert!(game_data.skills(Class::Barbarian)[0].prerequisites.is_empty());
+    assert_eq!(game_data.skill_id(Class::Barbarian, 1).unwrap(), 127);
+    assert_eq!(game_data.skill_slot(Class::Barbarian, 128).unwrap(), Some(2));
+    assert_eq!(game_data.skill_slot(Class::Barbarian, 0).unwrap(), None);
+    assert!(game_data.skills(Class::Druid).is_empty());
+
+    assert_eq!(game_data.character_stat(0).unwrap().bit_length, 12);
+    assert!(game_data.character_stat(16).is_none());
+    assert_eq!(game_data.item_stat_cost().get(17).unwrap().grouped_stats, [18]);
+    assert!(game_data.item_stat_cost().get(4).is_none());
+
+    for class_data in game_data.classes() {
+        assert_eq!(ClassData::d2r(class_data.class).as_ref(), Some(class_data));
+    }
+    assert!(ClassData::d2r(Class::Warlock).is_none());
+
+    let rogue = game_data.hireling(1).unwrap();
+    assert_eq!((rogue.xp_rate, rogue.name_count), (200, Some(41)));
+    assert_eq!(game_data.hireling(6).unwrap().name_count, Some(21));
+    assert!(game_data.hireling(2).is_none());
+}
+
+#[test]
+fn parse_reports_missing_columns_and_class_codes() {
+    let read = |file: &str| std::fs::read_to_string(format!("{DATA_DIRECTORY}/{file}")).unwrap();
+    let (skills, item_stat_cost, hireling, charstats) =
+        (read(SKILLS_FILE), read(ITEM_STAT_COST_FILE), read(HIRELING_FILE), read(CHARSTATS_FILE));
+
+    let error =
+        GameData::parse(&skills, &item_stat_cost.replace("CSvBits", "CSv"), &hireling, &charstats)
+            .unwrap_err();
+    assert_eq!(error.message, "itemstatcost.txt: missing column `CSvBits`.");
+
+    let error = GameData::parse(
+        &skills.replace("\tama\t", "\tzon\t"),
+        &item_stat_cost,
+        &hireling,
+        &charstats,
+    )
+    .unwrap_err();
+    assert!(error.message.contains("class code 'zon'"), "{error}");
+
+    let renamed =
+        GameData::parse(&skills, &item_stat_cost, &hireling, &charstats.replace("\tass", "\tasn"))
+            .unwrap();
+    assert_eq!(renamed.class_data(Class::Assassin).unwrap().code, "asn");
+
+    let without_codes: String = charstats
+        .lines()
+        .map(|line| line.rsplit_once('\t').map_or(line, |(kept, _)| kept).to_string() + "\n")
+        .collect();
+    let built_in = GameData::parse(&skills, &item_stat_cost, &hireling, &without_codes).unwrap();
+    assert_eq!(built_in.classes(), game_data().classes());
+    let error = GameData::parse(
+        &skills,
+        &item_stat_cost,
+        &hireling,
+        &(without_codes + "Warlock\t20\t20\t20\t20\t84\t30\t100\t8\t4\t6\t12\t4\t6\t5\t1\n"),
+    )
+    .unwrap_err();
+    assert!(error.message.contains("no class code for 'Warlock'"), "{error}");
+
+    let error = GameData::load(format!("{DATA_DIRECTORY}/missing")).unwrap_err();
+    assert!(error.message.starts_with("Cannot read game data directory"), "{error}");
+}
+
+#[test]
+fn named_skills_resolve_through_game_data() {
+    let game_data = game_data();
+    let mut skills = SkillPoints::default();
+
+    skills.set_by_name(&game_data, Class::Barbarian, "Cleaving Strike", 7).unwrap();
+    assert_eq!(skills.get(2), 7);
+    assert_eq!(skills.get_by_name(&game_data, Class::Barbarian, "cleaving strike").unwrap(), 7);
+    assert!(skills.set_by_name(&game_data, Class::Barbarian, "Double Swing", 1).is_err());
+    assert_eq!(
+        skills.get_by_name(&game_data, Class::Druid, "Firestorm"),
+        Err(NamedSkillError::UnsupportedClass(Class::Druid))
+    );
+}
+
+#[test]
+fn attributes_parse_with_uses_table_widths() {
+    let game_data = game_data();
+    let mut attributes = Attributes::new_save_defaults();
+    attributes.strength.bit_length = 12;
+    attributes.strength.value = 4000;
+    let bytes = attributes.to_bytes().unwrap();
+
+    let parsed = Attributes::parse_with(&bytes, &mut BytePosition::default(), &game_data).unwrap();
+    assert_eq!(parsed.strength, attributes.strength);
+    assert_eq!(parsed.to_bytes().unwrap(), bytes);
+
+    let default_widths = Attributes::parse(&bytes, &mut BytePosition::default());
+    assert!(default_widths.map_or(true, |parsed| parsed.strength != attributes.strength));
+}
+
+#[test]
+fn items_parse_and_encode_with_table_widths() {
+    let game_data = game_data();
+    let save = Save::parse(include_bytes!("../../assets/test/Ayame.d2s"), Strictness::Strict)
+        .unwrap()
+        .save;
+    let format = save.format();
+    let mut player_items = save.items.clone();
+    // The test table stores faster cast rate in 8 bits instead of 7.
+    let amulet = player_items.player.items.iter_mut().find(|item| item.code == "amu").unwrap();
+    amulet.extended.as_mut().unwrap().properties.set(Property::new(105, 150));
+
+    let expansion_type = save.expansion_type();
+    let hired = save.character.mercenary.is_hired();
+    assert!(items::generate(&player_items, format, expansion_type, hired).is_err());
+
+    let stat_costs = items::stat_costs_for(format, Some(&game_data));
+    let bytes =
+        items::generate_with(&player_items, format, expansion_type, hired, &stat_costs).unwrap();
+    assert_eq!(items::parse_with(&bytes, format, &stat_costs).unwrap(), player_items);
+    assert!(items::parse(&bytes, format).map_or(true, |parsed| parsed != player_items));
+}
+
+#[test]
+fn validate_with_uses_hireling_experience_rates() {
+    let game_data = game_data();
+    let mut save =
+        Save::parse(include_bytes!("../../assets/test/Joe.d2s"), Strictness::Strict).unwrap().save;
+    save.set_level(1);
+    save.character.mercenary.id = 1;
+    save.character.mercenary.variant_id = 1;
+    // Level 1 at the modded rate of 200, level 2 at the default rate of 105.
+    save.character.mercenary.experience = 1300;
+
+    let mercenary_issue = |issues: &[crate::validation::ValidationIssue]| {
+        issues.iter().any(|issue| issue.code == ValidationCode::MercenaryLevelImpossible)
+    };
+    assert!(mercenary_issue(&save.validate().issues));
+    assert!(!mercenary_issue(&save.validate_with(&game_data).issues));
+
+    save.character.mercenary.variant_id = 2;
+    assert!(save
+        .validate_with(&game_data)
+        .issues
+        .iter()
+        .any(|issue| issue.code == ValidationCode::MercenaryVariantUnknown));
+}
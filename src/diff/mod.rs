@@ -0,0 +1,699 @@
+//! Field-level comparison of two saves.
+//!
+//! [`crate::Save::diff`] walks the typed sections and reports every field whose value
+//! differs. Character bytes that are not modeled are compared as raw byte ranges.
+//!
+//! Item lists are compared item by item: items at the same location are paired, and
+//! each list reports added, removed and changed items as [`ItemChange`]s.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::attributes::AttributeId;
+use crate::character::{self, Character};
+use crate::items::{Item, ItemList, ItemMode, ItemStorage};
+use crate::npcs::{DifficultyNpcs, Npc};
+use crate::quests::{DifficultyQuests, ALL_QUEST_FLAGS};
+use crate::skills::d2r_skill_name;
+use crate::waypoints::{DifficultyWaypoints, WaypointState};
+use crate::{Act, Save};
+
+#[cfg(test)]
+mod tests;
+
+/// Save section a [`Change`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffSection {
+    Header,
+    Character,
+    Mercenary,
+    Attributes,
+    Skills,
+    Quests,
+    Waypoints,
+    Npcs,
+    Items,
+    Raw,
+}
+
+impl fmt::Display for DiffSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let section = match self {
+            DiffSection::Header => "header",
+            DiffSection::Character => "character",
+            DiffSection::Mercenary => "mercenary",
+            DiffSection::Attributes => "attributes",
+            DiffSection::Skills => "skills",
+            DiffSection::Quests => "quests",
+            DiffSection::Waypoints => "waypoints",
+            DiffSection::Npcs => "npcs",
+            DiffSection::Items => "items",
+            DiffSection::Raw => "raw",
+        };
+        write!(f, "{section}")
+    }
+}
+
+/// Value on one side of a [`Change`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DiffValue {
+    Bool(bool),
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for DiffValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffValue::Bool(value) => write!(f, "{value}"),
+            DiffValue::Number(value) => write!(f, "{value}"),
+            DiffValue::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<bool> for DiffValue {
+    fn from(value: bool) -> Self {
+        DiffValue::Bool(value)
+    }
+}
+
+impl From<u8> for DiffValue {
+    fn from(value: u8) -> Self {
+        DiffValue::Number(value.into())
+    }
+}
+
+impl From<u16> for DiffValue {
+    fn from(value: u16) -> Self {
+        DiffValue::Number(value.into())
+    }
+}
+
+impl From<u32> for DiffValue {
+    fn from(value: u32) -> Self {
+        DiffValue::Number(value.into())
+    }
+}
+
+impl From<String> for DiffValue {
+    fn from(value: String) -> Self {
+        DiffValue::Text(value)
+    }
+}
+
+impl From<&str> for DiffValue {
+    fn from(value: &str) -> Self {
+        DiffValue::Text(value.to_string())
+    }
+}
+
+/// One changed field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    pub section: DiffSection,
+    /// Dotted field path, e.g. `quests.normal.act1.q1.Started` or `skills.Bash`.
+    pub path: String,
+    pub before: DiffValue,
+    pub after: DiffValue,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {} -> {}", self.section, self.path, self.before, self.after)
+    }
+}
+
+/// Item list an [`ItemChange`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemListKind {
+    Player,
+    /// Corpse by index in [`crate::items::Items::corpses`].
+    Corpse(usize),
+    Mercenary,
+    IronGolem,
+}
+
+impl fmt::Display for ItemListKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemListKind::Player => write!(f, "player"),
+            ItemListKind::Corpse(index) => write!(f, "corpses[{index}]"),
+            ItemListKind::Mercenary => write!(f, "mercenary"),
+            ItemListKind::IronGolem => write!(f, "iron_golem"),
+        }
+    }
+}
+
+/// Where an item sits. Items at the same location in both saves are compared with each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemLocation {
+    pub mode: ItemMode,
+    pub storage: ItemStorage,
+    pub equip_slot: u8,
+    pub x: u8,
+    pub y: u8,
+}
+
+impl ItemLocation {
+    pub fn of(item: &Item) -> Self {
+        ItemLocation {
+            mode: item.mode,
+            storage: item.storage,
+            equip_slot: item.equip_slot,
+            x: item.x,
+            y: item.y,
+        }
+    }
+}
+
+impl fmt::Display for ItemLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            ItemMode::Stored => write!(f, "{:?} ({}, {})", self.storage, self.x, self.y),
+            ItemMode::Equipped => write!(f, "equip slot {}", self.equip_slot),
+            ItemMode::Belt => write!(f, "belt slot {}", self.x),
+            mode => write!(f, "{mode:?}"),
+        }
+    }
+}
+
+/// Whether an item was added, removed or changed in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One item that differs between two saves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemChange {
+    pub list: ItemListKind,
+    pub location: ItemLocation,
+    pub kind: ItemChangeKind,
+    /// The item in the first save. `None` when added.
+    pub before: Option<Item>,
+    /// The item in the second save. `None` when removed.
+    pub after: Option<Item>,
+}
+
+fn item_label(item: Option<&Item>) -> String {
+    match item {
+        Some(item) if item.ear.is_some() => "ear".to_string(),
+        Some(item) => item.code.clone(),
+        None => "none".to_string(),
+    }
+}
+
+impl fmt::Display for ItemChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] items.{} {}: {} -> {}",
+            DiffSection::Items,
+            self.list,
+            self.location,
+            item_label(self.before.as_ref()),
+            item_label(self.after.as_ref())
+        )
+    }
+}
+
+/// Result of [`crate::Save::diff`], in section order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveDiff {
+    pub changes: Vec<Change>,
+    /// Added, removed and changed items, by list.
+    #[serde(default)]
+    pub items: Vec<ItemChange>,
+}
+
+impl SaveDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.items.is_empty()
+    }
+
+    /// Number of field and item changes.
+    pub fn len(&self) -> usize {
+        self.changes.len() + self.items.len()
+    }
+
+    /// Field changes. Item changes are in [`SaveDiff::items`].
+    pub fn iter(&self) -> std::slice::Iter<'_, Change> {
+        self.changes.iter()
+    }
+
+    /// Changes belonging to one section.
+    pub fn in_section(&self, section: DiffSection) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(move |change| change.section == section)
+    }
+
+    /// Find a change by exact path.
+    pub fn get(&self, path: &str) -> Option<&Change> {
+        self.changes.iter().find(|change| change.path == path)
+    }
+
+    fn push<T: PartialEq + Into<DiffValue>>(
+        &mut self,
+        section: DiffSection,
+        path: impl Into<String>,
+        before: T,
+        after: T,
+    ) {
+        if before != after {
+            self.changes.push(Change {
+                section,
+                path: path.into(),
+                before: before.into(),
+                after: after.into(),
+            });
+        }
+    }
+}
+
+impl fmt::Display for SaveDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No changes.");
+        }
+        let lines = self
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.items.iter().map(ToString::to_string));
+        for (index, line) in lines.enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+fn act_key(act: Act) -> &'static str {
+    match act {
+        Act::Act1 => "act1",
+        Act::Act2 => "act2",
+        Act::Act3 => "act3",
+        Act::Act4 => "act4",
+        Act::Act5 => "act5",
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub(crate) fn diff(before: &Save, after: &Save) -> SaveDiff {
+    let mut diff = SaveDiff::default();
+
+    diff.push(DiffSection::Header, "version", before.version(), after.version());
+    diff.push(
+        DiffSection::Header,
+        "expansion_type",
+        before.expansion_type().label(),
+        after.expansion_type().label(),
+    );
+
+    diff_character(&mut diff, &before.character, &after.character);
+    diff_mercenary(&mut diff, &before.character, &after.character);
+    diff_attributes(&mut diff, before, after);
+    diff_skills(&mut diff, before, after);
+
+    for (difficulty, before_quests, after_quests) in [
+        ("normal", &before.quests.normal, &after.quests.normal),
+        ("nightmare", &before.quests.nightmare, &after.quests.nightmare),
+        ("hell", &before.quests.hell, &after.quests.hell),
+    ] {
+        diff_quests(&mut diff, difficulty, before_quests, after_quests);
+    }
+    for (difficulty, before_waypoints, after_waypoints) in [
+        ("normal", &before.waypoints.normal, &after.waypoints.normal),
+        ("nightmare", &before.waypoints.nightmare, &after.waypoints.nightmare),
+        ("hell", &before.waypoints.hell, &after.waypoints.hell),
+    ] {
+        diff_waypoints(&mut diff, difficulty, before_waypoints, after_waypoints);
+    }
+    for (difficulty, before_npcs, after_npcs) in [
+        ("normal", &before.npcs.normal, &after.npcs.normal),
+        ("nightmare", &before.npcs.nightmare, &after.npcs.nightmare),
+        ("hell", &before.npcs.hell, &after.npcs.hell),
+    ] {
+        diff_npcs(&mut diff, difficulty, before_npcs, after_npcs);
+    }
+
+    diff_items(&mut diff, before, after);
+    diff_raw(&mut diff, before, after);
+    diff
+}
+
+fn diff_character(diff: &mut SaveDiff, before: &Character, after: &Character) {
+    let section = DiffSection::Character;
+    diff.push(section, "character.name", before.name.as_str(), after.name.as_str());
+    diff.push(section, "character.class", before.class.to_string(), after.class.to_string());
+    diff.push(section, "character.level", before.level(), after.level());
+    diff.push(section, "character.progression", before.progression, after.progression);
+    diff.push(section, "character.hardcore", before.is_hardcore(), after.is_hardcore());
+    diff.push(section, "character.ladder", before.is_ladder(), after.is_ladder());
+    diff.push(section, "character.died", before.has_died(), after.has_died());
+    diff.push(
+        section,
+        "character.legacy_expansion",
+        before.status().is_expansion(),
+        after.status().is_expansion(),
+    );
+    diff.push(section, "character.weapon_switch", before.weapon_switch, after.weapon_switch);
+    diff.push(
+        section,
+        "character.difficulty",
+        before.difficulty.to_string(),
+        after.difficulty.to_string(),
+    );
+    diff.push(section, "character.act", before.act.to_string(), after.act.to_string());
+    diff.push(section, "character.map_seed", before.map_seed, after.map_seed);
+    diff.push(section, "character.last_played", before.last_played, after.last_played);
+    for (index, (before_skill, after_skill)) in
+        before.assigned_skills.iter().zip(after.assigned_skills.iter()).enumerate()
+    {
+        diff.push(
+            section,
+            format!("character.assigned_skills[{index}]"),
+            *before_skill,
+            *after_skill,
+        );
+    }
+    diff.push(
+        section,
+        "character.left_mouse_skill",
+        before.left_mouse_skill,
+        after.left_mouse_skill,
+    );
+    diff.push(
+        section,
+        "character.right_mouse_skill",
+        before.right_mouse_skill,
+        after.right_mouse_skill,
+    );
+    diff.push(
+        section,
+        "character.left_mouse_switch_skill",
+        before.left_mouse_switch_skill,
+        after.left_mouse_switch_skill,
+    );
+    diff.push(
+        section,
+        "character.right_mouse_switch_skill",
+        before.right_mouse_switch_skill,
+        after.right_mouse_switch_skill,
+    );
+    diff.push(
+        section,
+        "character.menu_appearance",
+        hex(&before.menu_appearance),
+        hex(&after.menu_appearance),
+    );
+    diff.push(
+        section,
+        "character.resurrected_menu_appearance",
+        hex(&before.resurrected_menu_appearance),
+        hex(&after.resurrected_menu_appearance),
+    );
+}
+
+fn diff_mercenary(diff: &mut SaveDiff, before: &Character, after: &Character) {
+    let section = DiffSection::Mercenary;
+    let (before, after) = (&before.mercenary, &after.mercenary);
+    diff.push(section, "mercenary.hired", before.is_hired(), after.is_hired());
+    diff.push(section, "mercenary.is_dead", before.is_dead, after.is_dead);
+    diff.push(section, "mercenary.id", before.id, after.id);
+    diff.push(section, "mercenary.name_id", before.name_id, after.name_id);
+    diff.push(section, "mercenary.variant_id", before.variant_id, after.variant_id);
+    diff.push(section, "mercenary.experience", before.experience, after.experience);
+}
+
+fn diff_attributes(diff: &mut SaveDiff, before: &Save, after: &Save) {
+    for attribute in AttributeId::ALL {
+        diff.push(
+            DiffSection::Attributes,
+            format!("attributes.{}", attribute.name()),
+            before.attributes.stat(attribute).value,
+            after.attributes.stat(attribute).value,
+        );
+    }
+
+    // Modded stats missing on one side read as zero, as the game omits zero-valued stats.
+    let extra_ids: BTreeSet<u32> =
+        before.attributes.extra.iter().chain(&after.attributes.extra).map(|stat| stat.id).collect();
+    for stat_id in extra_ids {
+        let [before_stat, after_stat] = [before, after]
+            .map(|save| save.attributes.extra.iter().find(|stat| stat.id == stat_id));
+        let path = match before_stat.or(after_stat).filter(|stat| !stat.name.is_empty()) {
+            Some(stat) => format!("attributes.{}", stat.name),
+            None => format!("attributes.stat{stat_id}"),
+        };
+        diff.push(
+            DiffSection::Attributes,
+            path,
+            before_stat.map_or(0, |stat| stat.value),
+            after_stat.map_or(0, |stat| stat.value),
+        );
+    }
+}
+
+fn diff_skills(diff: &mut SaveDiff, before: &Save, after: &Save) {
+    let class = before.character.class;
+    let same_class = class == after.character.class;
+    for (index, (before_points, after_points)) in
+        before.skills.points.iter().zip(after.skills.points.iter()).enumerate()
+    {
+        let path = match d2r_skill_name(class, index) {
+            Ok(name) if same_class => format!("skills.{name}"),
+            _ => format!("skills[{index}]"),
+        };
+        diff.push(DiffSection::Skills, path, *before_points, *after_points);
+    }
+}
+
+fn diff_quests(
+    diff: &mut SaveDiff,
+    difficulty: &str,
+    before: &DifficultyQuests,
+    after: &DifficultyQuests,
+) {
+    for ((act, quest_key, before_quest), (_, _, after_quest)) in
+        before.labeled().into_iter().zip(after.labeled())
+    {
+        if before_quest == after_quest {
+            continue;
+        }
+        for flag in ALL_QUEST_FLAGS {
+            diff.push(
+                DiffSection::Quests,
+                format!("quests.{difficulty}.{}.{quest_key}.{flag:?}", act_key(act)),
+                before_quest.state.contains(&flag),
+                after_quest.state.contains(&flag),
+            );
+        }
+    }
+}
+
+fn waypoint_states(waypoints: &DifficultyWaypoints) -> impl Iterator<Item = &WaypointState> {
+    waypoints
+        .act1
+        .iter()
+        .chain(waypoints.act2.iter())
+        .chain(waypoints.act3.iter())
+        .chain(waypoints.act4.iter())
+        .chain(waypoints.act5.iter())
+}
+
+fn diff_waypoints(
+    diff: &mut SaveDiff,
+    difficulty: &str,
+    before: &DifficultyWaypoints,
+    after: &DifficultyWaypoints,
+) {
+    for (before_waypoint, after_waypoint) in waypoint_states(before).zip(waypoint_states(after)) {
+        let waypoint = before_waypoint.id;
+        diff.push(
+            DiffSection::Waypoints,
+            format!("waypoints.{difficulty}.{}.{}", act_key(waypoint.act()), waypoint.name()),
+            before_waypoint.acquired,
+            after_waypoint.acquired,
+        );
+    }
+}
+
+fn diff_npcs(
+    diff: &mut SaveDiff,
+    difficulty: &str,
+    before: &DifficultyNpcs,
+    after: &DifficultyNpcs,
+) {
+    for (field, before_bits, after_bits) in [
+        ("introduced", before.introduced, after.introduced),
+        ("congratulated", before.congratulated, after.congratulated),
+    ] {
+        for bit in (0..u64::BITS as usize).filter(|bit| (before_bits ^ after_bits) >> bit & 1 != 0)
+        {
+            let name = match Npc::ALL.iter().find(|npc| npc.bit() == bit) {
+                Some(npc) => format!("{npc:?}"),
+                None => format!("bit{bit}"),
+            };
+            diff.push(
+                DiffSection::Npcs,
+                format!("npcs.{difficulty}.{field}.{name}"),
+                before_bits >> bit & 1 != 0,
+                after_bits >> bit & 1 != 0,
+            );
+        }
+    }
+}
+
+fn diff_items(diff: &mut SaveDiff, before: &Save, after: &Save) {
+    let empty = ItemList::default();
+    diff_item_list(
+        diff,
+        ItemListKind::Player,
+        &before.items.player.items,
+        &after.items.player.items,
+    );
+    let corpse_count = before.items.corpses.len().max(after.items.corpses.len());
+    for index in 0..corpse_count {
+        let [before_items, after_items] = [before, after].map(|save| {
+            &save.items.corpses.get(index).map_or(&empty, |corpse| &corpse.items).items
+        });
+        diff_item_list(diff, ItemListKind::Corpse(index), before_items, after_items);
+    }
+    diff_item_list(
+        diff,
+        ItemListKind::Mercenary,
+        &before.items.mercenary.items,
+        &after.items.mercenary.items,
+    );
+    diff_item_list(
+        diff,
+        ItemListKind::IronGolem,
+        before.items.iron_golem.as_slice(),
+        after.items.iron_golem.as_slice(),
+    );
+}
+
+/// Pair items by location, in list order, and report the ones that differ.
+fn diff_item_list(diff: &mut SaveDiff, list: ItemListKind, before: &[Item], after: &[Item]) {
+    let mut paired = vec![false; after.len()];
+    for before_item in before {
+        let location = ItemLocation::of(before_item);
+        let partner = (0..after.len())
+            .find(|index| !paired[*index] && ItemLocation::of(&after[*index]) == location);
+        let (kind, after_item) = match partner {
+            Some(index) => {
+                paired[index] = true;
+                if after[index] == *before_item {
+                    continue;
+                }
+                (ItemChangeKind::Changed, Some(after[index].clone()))
+            }
+            None => (ItemChangeKind::Removed, None),
+        };
+        diff.items.push(ItemChange {
+            list,
+            location,
+            kind,
+            before: Some(before_item.clone()),
+            after: after_item,
+        });
+    }
+    for (after_item, _) in after.iter().zip(paired).filter(|(_, paired)| !paired) {
+        diff.items.push(ItemChange {
+            list,
+            location: ItemLocation::of(after_item),
+            kind: ItemChangeKind::Added,
+            before: None,
+            after: Some(after_item.clone()),
+        });
+    }
+}
+
+/// Mark the character section bytes that encoding copies from `raw_section` instead of
+/// deriving from typed fields.
+fn unmodeled_character_bytes(save: &Save) -> Option<Vec<bool>> {
+    let format = save.format();
+    let length = character::expected_length_for_format(format);
+    if save.character.raw_section.len() != length {
+        return None;
+    }
+
+    let mut probe = save.character.clone();
+    probe.raw_section = vec![0x00; length];
+    let zeros = character::encode_for_format(format, &probe).ok()?;
+    probe.raw_section = vec![0xFF; length];
+    let ones = character::encode_for_format(format, &probe).ok()?;
+    Some(zeros.iter().zip(ones.iter()).map(|(zero, one)| zero != one).collect())
+}
+
+fn diff_raw(diff: &mut SaveDiff, before: &Save, after: &Save) {
+    let unmodeled =
+        if before.format() == after.format() { unmodeled_character_bytes(before) } else { None };
+    if let Some(unmodeled) = unmodeled {
+        push_byte_ranges(
+            diff,
+            "character.raw_section",
+            &before.character.raw_section,
+            &after.character.raw_section,
+            |index| unmodeled[index],
+        );
+    }
+
+    push_byte_ranges(
+        diff,
+        "items.rotw_section",
+        before.items.rotw_section(),
+        after.items.rotw_section(),
+        |_| true,
+    );
+    push_byte_ranges(diff, "items.raw", before.items.raw(), after.items.raw(), |_| true);
+}
+
+/// Report each contiguous run of differing bytes, restricted to indexes accepted by `include`.
+fn push_byte_ranges(
+    diff: &mut SaveDiff,
+    path: &str,
+    before: &[u8],
+    after: &[u8],
+    include: impl Fn(usize) -> bool,
+) {
+    if before.len() != after.len() {
+        diff.push(
+            DiffSection::Raw,
+            format!("{path}.length"),
+            before.len() as u32,
+            after.len() as u32,
+        );
+        return;
+    }
+
+    let differs = |index: usize| include(index) && before[index] != after[index];
+    let mut index = 0;
+    while index < before.len() {
+        if !differs(index) {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < before.len() && differs(index) {
+            index += 1;
+        }
+        diff.push(
+            DiffSection::Raw,
+            format!("{path}[{start:#x}..{index:#x}]"),
+            hex(&before[start..index]),
+            hex(&after[start..index]),
+        );
+    }
+}
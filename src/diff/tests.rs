@@ -0,0 +1,118 @@
+use super::*;
+use crate::npcs::Npc;
+use crate::quests::QuestFlag;
+use crate::waypoints::Waypoint;
+use crate::Strictness;
+
+fn joe() -> Save {
+    Save::parse(include_bytes!("../../assets/test/Joe.d2s"), Strictness::Strict).unwrap().save
+}
+
+fn ayame() -> Save {
+    Save::parse(include_bytes!("../../assets/test/Ayame.d2s"), Strictness::Strict).unwrap().save
+}
+
+#[test]
+fn identical_saves_have_no_changes() {
+    let save = joe();
+    let diff = save.diff(&save.clone());
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "No changes.");
+}
+
+#[test]
+fn diff_reports_typed_field_changes() {
+    let before = joe();
+    let mut after = before.clone();
+    after.set_level(12);
+    after.attributes.strength.value += 5;
+    after.skills.set_by_name_d2r(after.character.class, "Bash", 3).unwrap();
+    after.quests.normal.act1.q1.state.insert(QuestFlag::Started);
+    after.waypoints.nightmare.act2.set(Waypoint::DryHills, true).unwrap();
+    after.character.mercenary.experience += 100;
+    after.character.set_hardcore(true);
+
+    let diff = before.diff(&after);
+    let change = |path: &str| diff.get(path).unwrap_or_else(|| panic!("missing {path}:\n{diff}"));
+
+    assert_eq!(change("character.level").after, DiffValue::Number(12));
+    assert_eq!(change("attributes.level").after, DiffValue::Number(12));
+    assert_eq!(change("character.hardcore").after, DiffValue::Bool(true));
+    assert_eq!(change("skills.Bash").after, DiffValue::Number(3));
+    assert_eq!(change("quests.normal.act1.q1.Started").before, DiffValue::Bool(false));
+    assert_eq!(change("waypoints.nightmare.act2.Dry Hills").after, DiffValue::Bool(true));
+    assert_eq!(change("mercenary.experience").section, DiffSection::Mercenary);
+    assert_eq!(diff.in_section(DiffSection::Quests).count(), 1);
+    assert_eq!(diff.in_section(DiffSection::Raw).count(), 0);
+    assert!(diff.to_string().contains("[skills] skills.Bash: 0 -> 3"));
+
+    let json = serde_json::to_string(&diff).unwrap();
+    assert_eq!(serde_json::from_str::<SaveDiff>(&json).unwrap(), diff);
+}
+
+#[test]
+fn diff_reports_unmodeled_character_bytes_only() {
+    let before = joe();
+    let unmodeled = unmodeled_character_bytes(&before).unwrap();
+    let raw_index = unmodeled.iter().position(|unmodeled| *unmodeled).unwrap();
+    let modeled_index = unmodeled.iter().position(|unmodeled| !*unmodeled).unwrap();
+
+    let mut after = before.clone();
+    after.character.raw_section[raw_index] ^= 0xFF;
+    after.character.raw_section[modeled_index] ^= 0xFF;
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.len(), 1, "{diff}");
+    let change = &diff.changes[0];
+    assert_eq!(change.section, DiffSection::Raw);
+    assert_eq!(change.path, format!("character.raw_section[{raw_index:#x}..{:#x}]", raw_index + 1));
+}
+
+#[test]
+fn diff_reports_npc_bits() {
+    let before = joe();
+    let mut after = before.clone();
+    after
+        .npcs
+        .nightmare
+        .set_introduced(Npc::WarrivAct2, !before.npcs.nightmare.is_introduced(Npc::WarrivAct2));
+    after.npcs.hell.congratulated ^= 1 << 63;
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.in_section(DiffSection::Npcs).count(), 2, "{diff}");
+    assert!(diff.get("npcs.nightmare.introduced.WarrivAct2").is_some());
+    assert!(diff.get("npcs.hell.congratulated.bit63").is_some());
+}
+
+#[test]
+fn diff_reports_added_removed_and_changed_items() {
+    let before = ayame();
+    let mut after = before.clone();
+    let removed = after.items.player.items.remove(0);
+    let changed = &mut after.items.player.items[0];
+    changed.flags.set_ethereal(!changed.flags.is_ethereal());
+    let changed = changed.clone();
+    after.items.iron_golem = Some(removed.clone());
+
+    let diff = before.diff(&after);
+    assert!(diff.changes.is_empty(), "{diff}");
+    let kinds: Vec<_> = diff.items.iter().map(|change| (change.list, change.kind)).collect();
+    assert_eq!(
+        kinds,
+        [
+            (ItemListKind::Player, ItemChangeKind::Removed),
+            (ItemListKind::Player, ItemChangeKind::Changed),
+            (ItemListKind::IronGolem, ItemChangeKind::Added),
+        ]
+    );
+    assert_eq!(diff.items[0].location, ItemLocation::of(&removed));
+    assert_eq!(diff.items[0].before.as_ref(), Some(&removed));
+    assert_eq!(diff.items[1].after.as_ref(), Some(&changed));
+    assert_eq!(diff.len(), 3);
+    assert!(diff
+        .to_string()
+        .contains(&format!("[items] items.iron_golem {}", diff.items[2].location)));
+
+    let json = serde_json::to_string(&diff).unwrap();
+    assert_eq!(serde_json::from_str::<SaveDiff>(&json).unwrap(), diff);
+}
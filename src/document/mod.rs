@@ -0,0 +1,114 @@
+//! Versioned JSON document format for a full save.
+//!
+//! The document is a JSON object with these top-level keys:
+//!
+//! - `schema_version`: document layout version, currently [`SCHEMA_VERSION`].
+//! - `format`: numeric save version (`99`, `105`, ...).
+//! - `expansion_type`: `"Classic"`, `"Expansion"` or `"RotW"`.
+//! - `character`, `quests`, `waypoints`, `npcs`, `attributes`, `skills`, `items`: section models.
+//!
+//! Raw byte regions and the 64-bit NPC bitfields are lowercase hex strings, and quest flags
+//! are listed in bit order, so the same save always produces the same document.
+//!
+//! Requires the `json` feature.
+
+use serde::{Deserialize, Serialize};
+
+use crate::attributes::Attributes;
+use crate::character::Character;
+use crate::format::FormatId;
+use crate::items::Items;
+use crate::npcs::Npcs;
+use crate::quests::Quests;
+use crate::skills::SkillPoints;
+use crate::waypoints::Waypoints;
+use crate::{EncodeError, ExpansionType, ParseHardError, Save, SaveMeta};
+
+#[cfg(test)]
+mod tests;
+
+/// Current document schema version. Bumped on any breaking change to the document layout.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct SaveDocumentRef<'a> {
+    schema_version: u32,
+    format: u32,
+    expansion_type: ExpansionType,
+    character: &'a Character,
+    quests: &'a Quests,
+    waypoints: &'a Waypoints,
+    npcs: &'a Npcs,
+    attributes: &'a Attributes,
+    skills: &'a SkillPoints,
+    items: &'a Items,
+}
+
+#[derive(Deserialize)]
+struct SaveDocument {
+    format: u32,
+    expansion_type: ExpansionType,
+    character: Character,
+    quests: Quests,
+    waypoints: Waypoints,
+    npcs: Npcs,
+    attributes: Attributes,
+    skills: SkillPoints,
+    items: Items,
+}
+
+#[derive(Deserialize)]
+struct SchemaVersionProbe {
+    schema_version: u32,
+}
+
+pub(crate) fn to_json_document(save: &Save) -> Result<String, EncodeError> {
+    let document = SaveDocumentRef {
+        schema_version: SCHEMA_VERSION,
+        format: save.version(),
+        expansion_type: save.expansion_type(),
+        character: &save.character,
+        quests: &save.quests,
+        waypoints: &save.waypoints,
+        npcs: &save.npcs,
+        attributes: &save.attributes,
+        skills: &save.skills,
+        items: &save.items,
+    };
+
+    serde_json::to_string_pretty(&document)
+        .map_err(|error| EncodeError::new(format!("Cannot serialize save document: {error}")))
+}
+
+pub(crate) fn from_json_document(json: &str) -> Result<Save, ParseHardError> {
+    // Check the version first so a newer document fails with a clear message
+    // instead of a field-level deserialization error.
+    let probe: SchemaVersionProbe = serde_json::from_str(json)
+        .map_err(|error| ParseHardError { message: format!("Invalid save document: {error}") })?;
+    if probe.schema_version != SCHEMA_VERSION {
+        return Err(ParseHardError {
+            message: format!(
+                "Unsupported save document schema version {}, expected {SCHEMA_VERSION}.",
+                probe.schema_version
+            ),
+        });
+    }
+
+    let document: SaveDocument = serde_json::from_str(json)
+        .map_err(|error| ParseHardError { message: format!("Invalid save document: {error}") })?;
+
+    let format =
+        FormatId::from_version(document.format).unwrap_or(FormatId::Unknown(document.format));
+    Ok(Save {
+        version: format.version(),
+        expansion_type: document.expansion_type,
+        character: document.character,
+        quests: document.quests,
+        waypoints: document.waypoints,
+        npcs: document.npcs,
+        attributes: document.attributes,
+        skills: document.skills,
+        items: document.items,
+        meta: SaveMeta { format },
+    })
+}
@@ -0,0 +1,88 @@
+use crate::document::SCHEMA_VERSION;
+use crate::format::FormatId;
+use crate::quests::QuestFlag;
+use crate::{CompatibilityChecks, Save, Strictness};
+
+const FIXTURES: [(&str, &[u8]); 3] = [
+    ("Joe", include_bytes!("../../assets/test/Joe.d2s")),
+    ("Ayame", include_bytes!("../../assets/test/Ayame.d2s")),
+    ("Warlock_v105", include_bytes!("../../assets/test/Warlock_v105.d2s")),
+];
+
+#[test]
+fn json_document_roundtrip_keeps_model_and_bytes() {
+    for (name, bytes) in FIXTURES {
+        let save = Save::parse(bytes, Strictness::Strict).unwrap().save;
+        let json = save.to_json_document().unwrap();
+        let imported = Save::from_json_document(&json).unwrap();
+
+        assert_eq!(imported, save, "{name}: model changed through JSON document");
+        let encoded = imported.encode_for(imported.format(), CompatibilityChecks::Enforce).unwrap();
+        assert_eq!(encoded, save.encode_for(save.format(), CompatibilityChecks::Enforce).unwrap());
+    }
+}
+
+#[test]
+fn json_document_is_deterministic() {
+    let mut save = Save::parse(FIXTURES[1].1, Strictness::Strict).unwrap().save;
+    let quest = &mut save.quests.normal.act1.q1;
+    for flag in [QuestFlag::CompletedBefore, QuestFlag::Started, QuestFlag::RewardGranted] {
+        quest.state.insert(flag);
+    }
+
+    let json = save.to_json_document().unwrap();
+    let reexported = Save::from_json_document(&json).unwrap().to_json_document().unwrap();
+    assert_eq!(json, reexported);
+
+    let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(document["schema_version"], SCHEMA_VERSION);
+    assert_eq!(document["format"], 99);
+    let flags = document["quests"]["normal"]["act1"]["q1"]["state"].as_array().unwrap();
+    let names: Vec<&str> = flags.iter().map(|flag| flag.as_str().unwrap()).collect();
+    let mut sorted = names.clone();
+    sorted.sort_by_key(|name| {
+        serde_json::from_value::<QuestFlag>(serde_json::Value::from(*name)).unwrap()
+    });
+    assert_eq!(names, sorted);
+
+    let raw_section = document["character"]["raw_section"].as_str().unwrap();
+    assert!(raw_section.chars().all(|character| character.is_ascii_hexdigit()));
+    assert_eq!(raw_section.len(), save.character.raw_section.len() * 2);
+}
+
+#[test]
+fn json_document_rejects_other_schema_versions() {
+    let save = Save::new(FormatId::V105, crate::Class::Sorceress);
+    let json = save.to_json_document().unwrap();
+    let mut document: serde_json::Value = serde_json::from_str(&json).unwrap();
+    document["schema_version"] = serde_json::Value::from(SCHEMA_VERSION + 1);
+
+    let error = Save::from_json_document(&document.to_string()).unwrap_err();
+    assert!(error.message.contains("schema version"), "unexpected error: {error}");
+    assert!(Save::from_json_document("{}").is_err());
+}
+
+#[test]
+fn json_document_accepts_byte_arrays_for_raw_regions() {
+    let save = Save::parse(FIXTURES[0].1, Strictness::Strict).unwrap().save;
+    let mut document: serde_json::Value =
+        serde_json::from_str(&save.to_json_document().unwrap()).unwrap();
+    document["character"]["raw_section"] =
+        serde_json::Value::from(save.character.raw_section.clone());
+
+    assert_eq!(Save::from_json_document(&document.to_string()).unwrap(), save);
+}
+
+#[test]
+fn json_document_keeps_high_npc_bits() {
+    let mut save = Save::new(FormatId::V105, crate::Class::Sorceress);
+    save.npcs.hell.introduced = u64::MAX - 1;
+    let mut document: serde_json::Value =
+        serde_json::from_str(&save.to_json_document().unwrap()).unwrap();
+    assert_eq!(document["npcs"]["hell"]["introduced"], "fffffffffffffffe");
+    assert_eq!(Save::from_json_document(&document.to_string()).unwrap(), save);
+
+    document["npcs"]["hell"]["introduced"] = serde_json::Value::from(0x30u64);
+    let imported = Save::from_json_document(&document.to_string()).unwrap();
+    assert_eq!(imported.npcs.hell.introduced, 0x30);
+}
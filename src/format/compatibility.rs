@@ -2,7 +2,12 @@ use crate::{CompatibilityCode, CompatibilityIssue, EncodeError, ExpansionType, G
 
 use super::FormatId;
 
-fn class_compatibility_issues(
+/// Longest name that fits the 16-byte NUL-terminated v96 name field.
+const CLASSIC_NAME_MAX_LENGTH: usize = 15;
+
+// Newer clippy asks to fold the Druid/Assassin check into a match guard; keep one arm per class.
+#[allow(clippy::collapsible_match)]
+pub(crate) fn class_compatibility_issues(
     class: crate::Class,
     expansion_type: ExpansionType,
     target: FormatId,
@@ -10,16 +15,12 @@ fn class_compatibility_issues(
     let mut issues = Vec::new();
 
     match class {
-        crate::Class::Unknown(class_id) => {
-            if matches!(target, FormatId::V99 | FormatId::V105) {
-                issues.push(CompatibilityIssue {
-                    code: CompatibilityCode::UnknownClassRequiresKnownTarget,
-                    blocking: true,
-                    message: format!(
-                        "Unknown class id {class_id} cannot be converted to {target:?}."
-                    ),
-                });
-            }
+        crate::Class::Unknown(class_id) if FormatId::encodable_formats().contains(&target) => {
+            issues.push(CompatibilityIssue {
+                code: CompatibilityCode::UnknownClassRequiresKnownTarget,
+                blocking: true,
+                message: format!("Unknown class id {class_id} cannot be converted to {target:?}."),
+            });
         }
         crate::Class::Warlock => {
             if target.edition().is_some_and(|edition| edition != GameEdition::RotW) {
@@ -57,21 +58,39 @@ pub(crate) fn compatibility_issues(save: &Save, target: FormatId) -> Vec<Compati
     let mut issues =
         class_compatibility_issues(save.character.class, save.expansion_type(), target);
 
-    if save.items.mercenary_hire_state_changed(save.character.mercenary.is_hired()) {
+    if target.edition().is_some_and(|edition| edition != GameEdition::RotW)
+        && save.expansion_type() == ExpansionType::RotW
+    {
+        issues.push(CompatibilityIssue {
+            code: CompatibilityCode::RotWExpansionRequiresRotWEdition,
+            blocking: true,
+            message: "RotW expansion mode cannot be encoded to non-RotW formats.".to_string(),
+        });
+    }
+
+    if target.edition() == Some(GameEdition::Classic)
+        && (!save.character.name.is_ascii() || save.character.name.len() > CLASSIC_NAME_MAX_LENGTH)
+    {
         issues.push(CompatibilityIssue {
-            code: CompatibilityCode::MercenaryHireStateToggleUnsupported,
+            code: CompatibilityCode::ClassicNameRequiresShortAscii,
             blocking: true,
-            message: "Changing mercenary.id between 0 (no mercenary hired) and nonzero (mercenary hired) is not supported by this version of halbu, because the mercenary item subsection in the raw item tail is not rewritten yet.".to_string(),
+            message: format!(
+                "Name '{}' must be ASCII and at most {CLASSIC_NAME_MAX_LENGTH} bytes for {target:?}.",
+                save.character.name
+            ),
         });
     }
 
-    if target.edition().is_some_and(|edition| edition != GameEdition::RotW)
-        && save.expansion_type() == ExpansionType::RotW
+    if save.items.is_raw()
+        && save.format().edition().is_some_and(|edition| Some(edition) != target.edition())
     {
         issues.push(CompatibilityIssue {
-            code: CompatibilityCode::RotWExpansionRequiresRotWEdition,
+            code: CompatibilityCode::RawItemsRequireSourceEdition,
             blocking: true,
-            message: "RotW expansion mode cannot be encoded to non-RotW formats.".to_string(),
+            message: format!(
+                "Undecoded items from {:?} cannot be written to {target:?}.",
+                save.format()
+            ),
         });
     }
 
@@ -1,9 +1,10 @@
 use crate::attributes::Attributes;
 use crate::character::decode_for_format as decode_character_for_format;
-use crate::items;
-use crate::npcs::Placeholder as NPCs;
+use crate::data::GameData;
+use crate::items::{self, ITEM_LIST_HEADER};
+use crate::npcs::Npcs;
 use crate::quests::Quests;
-use crate::skills::{SkillPoints, SKILLS_SECTION_LENGTH};
+use crate::skills::{SkillPoints, SECTION_HEADER as SKILLS_HEADER, SKILLS_SECTION_LENGTH};
 use crate::utils::BytePosition;
 use crate::waypoints::Waypoints;
 use crate::{
@@ -20,6 +21,28 @@ use super::FormatId;
 
 /// Decode a save with configurable strictness.
 pub(crate) fn decode(bytes: &[u8], strictness: Strictness) -> Result<ParsedSave, ParseHardError> {
+    decode_with(bytes, strictness, None)
+}
+
+/// Find the skills section after an attributes stream that could not be read to its end.
+///
+/// Matches the skills header followed, one section later, by the item list header.
+pub(crate) fn find_skills_offset(bytes: &[u8], start: usize) -> Option<usize> {
+    (start..bytes.len()).find(|&offset| {
+        let item_list_offset = offset + SKILLS_SECTION_LENGTH;
+        bytes.get(offset..offset + SKILLS_HEADER.len()) == Some(&SKILLS_HEADER[..])
+            && bytes.get(item_list_offset..item_list_offset + ITEM_LIST_HEADER.len())
+                == Some(&ITEM_LIST_HEADER[..])
+    })
+}
+
+/// Decode a save, reading attribute and item property widths and modded stats from
+/// `game_data` when given.
+pub(crate) fn decode_with(
+    bytes: &[u8],
+    strictness: Strictness,
+    game_data: Option<&GameData>,
+) -> Result<ParsedSave, ParseHardError> {
     let mut parsed_save = Save::default();
     let mut issues: Vec<ParseIssue> = Vec::new();
     let (header_checksum, computed_checksum) = checksum_metadata(bytes);
@@ -252,7 +275,7 @@ pub(crate) fn decode(bytes: &[u8], strictness: Strictness) -> Result<ParsedSave,
             Ok(finalize(parsed_save, issues, detected_format, decoded_layout, edition_hint))
         };
     }
-    match NPCs::parse(&bytes[npcs_range.start..npcs_range.end]) {
+    match Npcs::parse(&bytes[npcs_range.start..npcs_range.end]) {
         Ok(parsed_npcs) => parsed_save.npcs = parsed_npcs,
         Err(parse_error) => {
             push_issue(
@@ -331,9 +354,51 @@ pub(crate) fn decode(bytes: &[u8], strictness: Strictness) -> Result<ParsedSave,
     }
 
     let mut byte_position = BytePosition::default();
-    match Attributes::parse(attribute_bytes, &mut byte_position) {
-        Ok(parsed_attributes) => {
+    let mut skills_offset = None;
+    match Attributes::parse_until_unknown(attribute_bytes, &mut byte_position, game_data) {
+        Ok((parsed_attributes, unknown_stat)) => {
             parsed_save.attributes = parsed_attributes;
+            if let Some(unknown_stat) = unknown_stat {
+                let unknown_stat_offset =
+                    selected_layout.attributes_offset() + byte_position.current_byte;
+                push_issue(
+                    &mut issues,
+                    IssueSeverity::Error,
+                    IssueKind::InvalidValue,
+                    IssueContext {
+                        section_name: section_name_option("attributes"),
+                        message: format!(
+                            "{} Stats from this entry on were kept undecoded.",
+                            ParseHardError::from(unknown_stat)
+                        ),
+                        offset: Some(unknown_stat_offset),
+                        expected: None,
+                        found: None,
+                    },
+                );
+
+                if strictness == Strictness::Strict {
+                    return Err(ParseHardError {
+                        message: "Cannot parse save: attributes section payload is invalid."
+                            .to_string(),
+                    });
+                }
+
+                skills_offset = find_skills_offset(bytes, unknown_stat_offset);
+                let Some(skills_offset) = skills_offset else {
+                    return Ok(finalize(
+                        parsed_save,
+                        issues,
+                        detected_format,
+                        decoded_layout,
+                        edition_hint,
+                    ));
+                };
+                parsed_save.attributes.keep_unread(
+                    &bytes[selected_layout.attributes_offset()..skills_offset],
+                    byte_position,
+                )?;
+            }
         }
         Err(parse_error) => {
             push_issue(
@@ -366,7 +431,8 @@ pub(crate) fn decode(bytes: &[u8], strictness: Strictness) -> Result<ParsedSave,
         }
     }
 
-    let skills_offset = selected_layout.attributes_offset() + byte_position.next_byte_offset();
+    let skills_offset = skills_offset
+        .unwrap_or(selected_layout.attributes_offset() + byte_position.next_byte_offset());
     if (skills_offset + SKILLS_LENGTH) > bytes.len() {
         let expected_length = SKILLS_LENGTH;
         let found_length = bytes.len().saturating_sub(skills_offset);
@@ -447,8 +513,27 @@ pub(crate) fn decode(bytes: &[u8], strictness: Strictness) -> Result<ParsedSave,
         };
     }
 
-    parsed_save.items =
-        items::parse(&bytes[items_offset..], parsed_save.character.mercenary.is_hired());
+    let stat_costs = items::stat_costs_for(decoded_layout, game_data);
+    parsed_save.items = match items::parse_with(&bytes[items_offset..], decoded_layout, &stat_costs)
+    {
+        Ok(items) => items,
+        Err(parse_error) => {
+            // Undecodable items are kept verbatim so the save still round-trips.
+            push_issue(
+                &mut issues,
+                IssueSeverity::Warning,
+                IssueKind::InvalidValue,
+                IssueContext {
+                    section_name: section_name_option("items"),
+                    message: format!("Items section kept as raw bytes: {parse_error}"),
+                    offset: Some(items_offset),
+                    expected: None,
+                    found: None,
+                },
+            );
+            items::parse_raw(&bytes[items_offset..])
+        }
+    };
 
     Ok(finalize(parsed_save, issues, detected_format, decoded_layout, edition_hint))
 }
@@ -25,6 +25,7 @@ struct EditionEvidence {
 
 fn format_for_edition(edition: GameEdition) -> FormatId {
     match edition {
+        GameEdition::Classic => FormatId::V96,
         GameEdition::D2RLegacy => FormatId::V99,
         GameEdition::RotW => FormatId::V105,
     }
@@ -32,6 +33,10 @@ fn format_for_edition(edition: GameEdition) -> FormatId {
 
 fn reserved_marker_offsets(edition: GameEdition) -> (usize, usize) {
     match edition {
+        GameEdition::Classic => (
+            CHARACTER_SECTION_START + crate::character::v96::OFFSET_RESERVED_VERSION_MARKER_ONE,
+            CHARACTER_SECTION_START + crate::character::v96::OFFSET_RESERVED_VERSION_MARKER_TWO,
+        ),
         GameEdition::D2RLegacy => (
             CHARACTER_SECTION_START + crate::character::v99::OFFSET_RESERVED_VERSION_MARKER_ONE,
             CHARACTER_SECTION_START + crate::character::v99::OFFSET_RESERVED_VERSION_MARKER_TWO,
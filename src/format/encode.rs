@@ -1,6 +1,8 @@
 use crate::character::encode_for_format as encode_character_for_format;
+use crate::data::GameData;
 use crate::items;
-use crate::{calc_checksum, CompatibilityChecks, EncodeError, ExpansionType, Save};
+use crate::utils::get_sys_time_in_secs;
+use crate::{calc_checksum, CompatibilityChecks, EncodeError, EncodeOptions, RawRegions, Save};
 
 use super::compatibility::validate_encode_compatibility;
 use super::layout::{
@@ -9,34 +11,24 @@ use super::layout::{
 };
 use super::FormatId;
 
-fn empty_items_layout_for_encode(
-    target: FormatId,
-    expansion_type: ExpansionType,
-) -> items::EmptyLayout {
-    match target {
-        FormatId::V99 => {
-            if expansion_type == ExpansionType::Classic {
-                items::EmptyLayout::LegacyClassic
-            } else {
-                items::EmptyLayout::LegacyExpansion
-            }
-        }
-        FormatId::V105 | FormatId::Unknown(_) => match expansion_type {
-            ExpansionType::Classic => items::EmptyLayout::V105Classic,
-            ExpansionType::RotW => items::EmptyLayout::V105RotW,
-            ExpansionType::Expansion => items::EmptyLayout::V105Expansion,
-        },
-    }
-}
-
 /// Encode a [`Save`] into bytes for a target layout.
 ///
-/// Placeholder sections keep raw bytes when available; otherwise section-specific defaults
-/// are generated (for example empty-item trailers).
+/// Raw-preserving sections keep their bytes when available, unless `options`
+/// asks for regenerated character regions.
 pub(crate) fn encode(
     save: &Save,
     target: FormatId,
-    compatibility_checks: CompatibilityChecks,
+    options: EncodeOptions,
+) -> Result<Vec<u8>, EncodeError> {
+    encode_with(save, target, options, None)
+}
+
+/// [`encode`] with item property widths from `game_data` when given.
+pub(crate) fn encode_with(
+    save: &Save,
+    target: FormatId,
+    options: EncodeOptions,
+    game_data: Option<&GameData>,
 ) -> Result<Vec<u8>, EncodeError> {
     if let FormatId::Unknown(version) = target {
         return Err(EncodeError::new(format!(
@@ -44,10 +36,32 @@ pub(crate) fn encode(
         )));
     }
 
-    if compatibility_checks == CompatibilityChecks::Enforce {
+    if !FormatId::encodable_formats().contains(&target) {
+        return Err(EncodeError::new(format!(
+            "Cannot encode to format version {}. Supported targets are {:?}.",
+            target.version(),
+            FormatId::encodable_formats().map(FormatId::version)
+        )));
+    }
+
+    if options.compatibility_checks == CompatibilityChecks::Enforce {
         validate_encode_compatibility(save, target)?;
     }
 
+    if options.block_on_validation {
+        let report = save.validate();
+        if report.has_blocking_issues() {
+            let message = report
+                .issues
+                .iter()
+                .filter(|issue| issue.blocking)
+                .map(|issue| issue.message.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            return Err(EncodeError::new(message));
+        }
+    }
+
     let selected_layout = layout_for_encode(target);
     let mut encoded_bytes = vec![0x00; selected_layout.attributes_offset()];
 
@@ -56,6 +70,16 @@ pub(crate) fn encode(
         .copy_from_slice(&target.version().to_le_bytes());
 
     let mut character_for_encode = save.character.clone();
+    // Unmodeled bytes only carry over within one edition: v96 and v99 share a
+    // section length but not the meaning of their unknown regions.
+    if options.raw_regions == RawRegions::Regenerate
+        || save.format().edition().is_some_and(|edition| Some(edition) != target.edition())
+    {
+        character_for_encode.raw_section.clear();
+    }
+    if options.refresh_last_played {
+        character_for_encode.last_played = get_sys_time_in_secs();
+    }
     apply_expansion_type_for_encode(
         &mut character_for_encode,
         selected_layout.format_id(),
@@ -72,16 +96,24 @@ pub(crate) fn encode(
     encoded_bytes[selected_layout.waypoints_range()].copy_from_slice(&save.waypoints.to_bytes());
     encoded_bytes[selected_layout.npcs_range()].copy_from_slice(&save.npcs.to_bytes());
 
-    let mut attribute_bytes =
-        save.attributes.to_bytes().map_err(|error| EncodeError::new(error.to_string()))?;
+    let mut attribute_bytes = save
+        .attributes
+        .to_bytes_with(options.attribute_omission)
+        .map_err(|error| EncodeError::new(error.to_string()))?;
     encoded_bytes.append(&mut attribute_bytes);
 
     let mut skill_bytes = save.skills.to_bytes();
     encoded_bytes.append(&mut skill_bytes);
 
-    let items_layout = empty_items_layout_for_encode(target, save.expansion_type());
-    let mut item_bytes =
-        items::generate(&save.items, items_layout, character_for_encode.mercenary.is_hired());
+    let stat_costs = items::stat_costs_for(selected_layout.format_id(), game_data);
+    let mut item_bytes = items::generate_with(
+        &save.items,
+        selected_layout.format_id(),
+        save.expansion_type(),
+        character_for_encode.mercenary.is_hired(),
+        &stat_costs,
+    )
+    .map_err(|error| EncodeError::new(error.to_string()))?;
     encoded_bytes.append(&mut item_bytes);
 
     let file_size = encoded_bytes.len() as u32;
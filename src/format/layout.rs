@@ -24,6 +24,11 @@ pub(crate) const WAYPOINTS_LENGTH: usize = 80;
 pub(crate) const NPCS_LENGTH: usize = 52;
 pub(crate) const SKILLS_LENGTH: usize = SKILLS_SECTION_LENGTH;
 
+const V96_QUESTS_START: usize = 335;
+const V96_WAYPOINTS_START: usize = 633;
+const V96_NPCS_START: usize = 713;
+const V96_ATTRIBUTES_OFFSET: usize = 765;
+
 const V99_QUESTS_START: usize = 335;
 const V99_WAYPOINTS_START: usize = 633;
 const V99_NPCS_START: usize = 713;
@@ -40,7 +45,8 @@ struct FormatCompatibilityEntry {
     edition: GameEdition,
 }
 
-const FORMAT_COMPATIBILITY_TABLE: [FormatCompatibilityEntry; 2] = [
+const FORMAT_COMPATIBILITY_TABLE: [FormatCompatibilityEntry; 3] = [
+    FormatCompatibilityEntry { format: FormatId::V96, edition: GameEdition::Classic },
     FormatCompatibilityEntry { format: FormatId::V99, edition: GameEdition::D2RLegacy },
     FormatCompatibilityEntry { format: FormatId::V105, edition: GameEdition::RotW },
 ];
@@ -48,6 +54,16 @@ const FORMAT_COMPATIBILITY_TABLE: [FormatCompatibilityEntry; 2] = [
 #[non_exhaustive]
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize, Default)]
 pub enum FormatId {
+    /// Diablo II 1.00–1.06. Recognized but not decoded.
+    V71,
+    /// Diablo II 1.07 and Lord of Destruction 1.07. Recognized but not decoded.
+    V87,
+    /// Diablo II 1.08. Recognized but not decoded.
+    V89,
+    /// Diablo II 1.09. Recognized but not decoded.
+    V92,
+    /// Diablo II 1.10–1.14d.
+    V96,
     #[default]
     V99,
     V105,
@@ -59,6 +75,11 @@ impl FormatId {
     /// Map numeric file version to a known format identifier.
     pub fn from_version(version: u32) -> Option<Self> {
         match version {
+            71 => Some(Self::V71),
+            87 => Some(Self::V87),
+            89 => Some(Self::V89),
+            92 => Some(Self::V92),
+            96 => Some(Self::V96),
             99 => Some(Self::V99),
             105 => Some(Self::V105),
             _ => None,
@@ -66,13 +87,14 @@ impl FormatId {
     }
 
     /// Formats this library can encode.
-    pub const fn encodable_formats() -> [Self; 2] {
-        [Self::V99, Self::V105]
+    pub const fn encodable_formats() -> [Self; 3] {
+        [Self::V96, Self::V99, Self::V105]
     }
 
     /// Coarse edition family for known formats.
     pub const fn edition(self) -> Option<GameEdition> {
         match self {
+            Self::V71 | Self::V87 | Self::V89 | Self::V92 | Self::V96 => Some(GameEdition::Classic),
             Self::V99 => Some(GameEdition::D2RLegacy),
             Self::V105 => Some(GameEdition::RotW),
             Self::Unknown(_) => None,
@@ -111,6 +133,11 @@ impl FormatId {
     /// Numeric version value written in the save header.
     pub const fn version(self) -> u32 {
         match self {
+            Self::V71 => 71,
+            Self::V87 => 87,
+            Self::V89 => 89,
+            Self::V92 => 92,
+            Self::V96 => 96,
             Self::V99 => 99,
             Self::V105 => 105,
             Self::Unknown(version) => version,
@@ -148,6 +175,36 @@ pub trait Layout {
     }
 }
 
+/// Concrete layout metadata for [`FormatId::V96`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LayoutV96;
+
+impl Layout for LayoutV96 {
+    fn format_id(&self) -> FormatId {
+        FormatId::V96
+    }
+
+    fn character_length(&self) -> usize {
+        character_length_for_format(FormatId::V96)
+    }
+
+    fn quests_start(&self) -> usize {
+        V96_QUESTS_START
+    }
+
+    fn waypoints_start(&self) -> usize {
+        V96_WAYPOINTS_START
+    }
+
+    fn npcs_start(&self) -> usize {
+        V96_NPCS_START
+    }
+
+    fn attributes_offset(&self) -> usize {
+        V96_ATTRIBUTES_OFFSET
+    }
+}
+
 /// Concrete layout metadata for [`FormatId::V99`].
 #[derive(Debug, Clone, Copy, Default)]
 pub struct LayoutV99;
@@ -208,6 +265,7 @@ impl Layout for LayoutV105 {
     }
 }
 
+static V96_LAYOUT: LayoutV96 = LayoutV96;
 static V99_LAYOUT: LayoutV99 = LayoutV99;
 static V105_LAYOUT: LayoutV105 = LayoutV105;
 
@@ -273,6 +331,9 @@ pub(crate) fn range_readable(
 
 pub(crate) fn layout_for_encode(target: FormatId) -> &'static dyn Layout {
     match target {
+        FormatId::V71 | FormatId::V87 | FormatId::V89 | FormatId::V92 | FormatId::V96 => {
+            &V96_LAYOUT
+        }
         FormatId::V99 => &V99_LAYOUT,
         FormatId::V105 | FormatId::Unknown(_) => &V105_LAYOUT,
     }
@@ -291,7 +352,12 @@ pub(crate) fn expansion_type_from_decoded_character(
     character: &crate::character::Character,
 ) -> ExpansionType {
     match format_id {
-        FormatId::V99 => expansion_type_from_v99_status(character),
+        FormatId::V71
+        | FormatId::V87
+        | FormatId::V89
+        | FormatId::V92
+        | FormatId::V96
+        | FormatId::V99 => expansion_type_from_v99_status(character),
         FormatId::V105 | FormatId::Unknown(_) => {
             crate::character::v105::expansion_type(character).unwrap_or(ExpansionType::RotW)
         }
@@ -304,7 +370,12 @@ pub(crate) fn apply_expansion_type_for_encode(
     expansion_type: ExpansionType,
 ) {
     match target {
-        FormatId::V99 => {
+        FormatId::V71
+        | FormatId::V87
+        | FormatId::V89
+        | FormatId::V92
+        | FormatId::V96
+        | FormatId::V99 => {
             character.set_legacy_expansion_flag(!matches!(expansion_type, ExpansionType::Classic));
         }
         FormatId::V105 | FormatId::Unknown(_) => {
@@ -321,6 +392,13 @@ pub(crate) fn layout_for_decode(
     issues: &mut Vec<ParseIssue>,
 ) -> Result<&'static dyn Layout, ParseHardError> {
     match detected_format {
+        FormatId::V71 | FormatId::V87 | FormatId::V89 | FormatId::V92 => Err(ParseHardError {
+            message: format!(
+                "Save version {} predates Diablo II 1.10 and uses stat and item layouts that cannot be decoded.",
+                detected_format.version()
+            ),
+        }),
+        FormatId::V96 => Ok(&V96_LAYOUT),
         FormatId::V99 => Ok(&V99_LAYOUT),
         FormatId::V105 => Ok(&V105_LAYOUT),
         FormatId::Unknown(version) => {
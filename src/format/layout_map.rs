@@ -0,0 +1,208 @@
+use std::fmt::Write;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::attributes::Attributes;
+use crate::character::codec::field_spans_for_format as character_field_spans;
+use crate::data::GameData;
+use crate::skills::{d2r_skill_name, SKILLS_SECTION_LENGTH};
+use crate::utils::{read_bits, BytePosition};
+use crate::{npcs, quests, waypoints, Class, ParseHardError, Strictness};
+
+use super::layout::{
+    detect_format, layout_for_decode, CHARACTER_SECTION_START, CHECKSUM_RANGE, FILE_SIZE_RANGE,
+    SIGNATURE_RANGE, VERSION_RANGE,
+};
+
+const HEX_DUMP_BYTES_PER_LINE: usize = 16;
+
+/// One labeled region of a save file.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutSpan {
+    /// Absolute byte range. For bit-packed fields, every byte the field touches.
+    pub range: Range<usize>,
+    /// Absolute bit range for fields of the packed attributes stream.
+    pub bits: Option<Range<usize>>,
+    pub section: String,
+    pub field: String,
+}
+
+impl LayoutSpan {
+    fn bytes(section: &str, field: impl Into<String>, range: Range<usize>) -> Self {
+        Self { range, bits: None, section: section.to_string(), field: field.into() }
+    }
+
+    fn bits(section: &str, field: impl Into<String>, bits: Range<usize>) -> Self {
+        Self {
+            range: (bits.start / 8)..bits.end.div_ceil(8),
+            bits: Some(bits),
+            section: section.to_string(),
+            field: field.into(),
+        }
+    }
+}
+
+/// Map every byte of a save file to the section and field it belongs to.
+///
+/// Spans are returned in file order. Bytes inside a known section that no codec
+/// interprets are reported with field `unknown`; the attributes stream is mapped
+/// bit by bit. Unknown versions are mapped with the layout the decoder would fall back to.
+/// Attribute stats with no known width are mapped as one `unread` span.
+pub fn layout_map(bytes: &[u8]) -> Result<Vec<LayoutSpan>, ParseHardError> {
+    layout_map_from(bytes, None)
+}
+
+/// [`layout_map`] with attribute stat widths from `game_data`.
+pub fn layout_map_with(
+    bytes: &[u8],
+    game_data: &GameData,
+) -> Result<Vec<LayoutSpan>, ParseHardError> {
+    layout_map_from(bytes, Some(game_data))
+}
+
+fn layout_map_from(
+    bytes: &[u8],
+    game_data: Option<&GameData>,
+) -> Result<Vec<LayoutSpan>, ParseHardError> {
+    let detected_format = detect_format(bytes)?;
+    let layout = layout_for_decode(detected_format, bytes, Strictness::Lax, &mut Vec::new())?;
+    if bytes.len() < layout.attributes_offset() {
+        return Err(ParseHardError {
+            message: format!(
+                "Cannot map save layout: expected at least {} bytes, found {}.",
+                layout.attributes_offset(),
+                bytes.len()
+            ),
+        });
+    }
+
+    let mut spans = vec![
+        LayoutSpan::bytes("header", "signature", SIGNATURE_RANGE),
+        LayoutSpan::bytes("header", "version", VERSION_RANGE),
+        LayoutSpan::bytes("header", "file_size", FILE_SIZE_RANGE),
+        LayoutSpan::bytes("header", "checksum", CHECKSUM_RANGE),
+    ];
+
+    let character_fields = character_field_spans(layout.format_id());
+    let class_offset = character_fields
+        .iter()
+        .find(|(_, field)| field == "class")
+        .map(|(range, _)| CHARACTER_SECTION_START + range.start);
+    push_section(&mut spans, "character", layout.character_range(), character_fields);
+    push_section(&mut spans, "quests", layout.quests_range(), quests::field_spans());
+    push_section(&mut spans, "waypoints", layout.waypoints_range(), waypoints::field_spans());
+    push_section(&mut spans, "npcs", layout.npcs_range(), npcs::field_spans());
+
+    let attributes_start_bit = layout.attributes_offset() * 8;
+    let mut attributes_end_bit = attributes_start_bit;
+    for (bits, field) in Attributes::bit_spans(&bytes[layout.attributes_offset()..], game_data)? {
+        let bits = (attributes_start_bit + bits.start)..(attributes_start_bit + bits.end);
+        attributes_end_bit = bits.end;
+        spans.push(LayoutSpan::bits("attributes", field, bits));
+    }
+
+    let skills_start = attributes_end_bit.div_ceil(8);
+    let skills_range = skills_start..(skills_start + SKILLS_SECTION_LENGTH);
+    if bytes.len() < skills_range.end {
+        return Err(ParseHardError {
+            message: format!(
+                "Cannot map save layout: skills section needs bytes {skills_range:?}, found {}.",
+                bytes.len()
+            ),
+        });
+    }
+    let class = class_offset.map(|offset| Class::from(bytes[offset]));
+    let mut skill_fields = vec![(0..2, "header".to_string())];
+    for skill_index in 0..(SKILLS_SECTION_LENGTH - 2) {
+        let name = class
+            .and_then(|class| d2r_skill_name(class, skill_index).ok())
+            .map_or_else(|| format!("skills[{skill_index}]"), str::to_string);
+        skill_fields.push(((2 + skill_index)..(3 + skill_index), name));
+    }
+    push_section(&mut spans, "skills", skills_range.clone(), skill_fields);
+
+    if skills_range.end < bytes.len() {
+        spans.push(LayoutSpan::bytes("items", "items", skills_range.end..bytes.len()));
+    }
+    Ok(spans)
+}
+
+/// Push relative `fields` of a section at `section_range`, labeling uncovered bytes as `unknown`.
+fn push_section(
+    spans: &mut Vec<LayoutSpan>,
+    section: &str,
+    section_range: Range<usize>,
+    mut fields: Vec<(Range<usize>, String)>,
+) {
+    fields.sort_by_key(|(range, _)| range.start);
+    let mut cursor = section_range.start;
+    for (range, field) in fields {
+        let range = (section_range.start + range.start)..(section_range.start + range.end);
+        if cursor < range.start {
+            spans.push(LayoutSpan::bytes(section, "unknown", cursor..range.start));
+        }
+        cursor = cursor.max(range.end);
+        spans.push(LayoutSpan::bytes(section, field, range));
+    }
+    if cursor < section_range.end {
+        spans.push(LayoutSpan::bytes(section, "unknown", cursor..section_range.end));
+    }
+}
+
+/// Render `spans` over `bytes` as an annotated hex dump, one span per entry.
+///
+/// Each entry starts with the byte range and `section.field` label, followed by the
+/// bytes in hex wrapped at 16 per line. Bit-packed fields also show their bit range and value.
+pub fn annotated_hex_dump(bytes: &[u8], spans: &[LayoutSpan]) -> String {
+    let label_width = spans
+        .iter()
+        .map(|span| span.section.len() + span.field.len() + 1)
+        .max()
+        .unwrap_or_default();
+    let mut dump = String::new();
+
+    for span in spans {
+        let label = format!("{}.{}", span.section, span.field);
+        let span_bytes = bytes.get(span.range.clone()).unwrap_or_default();
+        let mut chunks = span_bytes.chunks(HEX_DUMP_BYTES_PER_LINE);
+        let first_chunk = chunks.next().unwrap_or_default();
+
+        let _ = write!(
+            dump,
+            "{:#07x}..{:#07x}  {label:<label_width$}  {}",
+            span.range.start,
+            span.range.end,
+            hex(first_chunk)
+        );
+        if let Some(bits) = &span.bits {
+            let _ = write!(dump, "  [bits {}..{}]", bits.start, bits.end);
+            if let Some(value) = read_bit_span(bytes, bits) {
+                let _ = write!(dump, " = {value}");
+            }
+        }
+        dump.push('\n');
+
+        for (chunk_index, chunk) in chunks.enumerate() {
+            let offset = span.range.start + (chunk_index + 1) * HEX_DUMP_BYTES_PER_LINE;
+            let _ = writeln!(
+                dump,
+                "{offset:#07x}{:width$}{}",
+                "",
+                hex(chunk),
+                width = label_width + 13
+            );
+        }
+    }
+    dump
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect::<Vec<_>>().join(" ")
+}
+
+fn read_bit_span(bytes: &[u8], bits: &Range<usize>) -> Option<u32> {
+    let mut byte_position =
+        BytePosition { current_byte: bits.start / 8, current_bit: bits.start % 8 };
+    read_bits(bytes, &mut byte_position, bits.end - bits.start).ok()
+}
@@ -8,6 +8,8 @@ mod decode;
 mod edition_hint;
 mod encode;
 mod layout;
+mod layout_map;
+mod roundtrip;
 mod summary;
 
 pub use edition_hint::detect_edition_hint;
@@ -15,11 +17,19 @@ pub use layout::detect_format;
 pub use layout::FormatId;
 pub use layout::Layout;
 pub use layout::LayoutV105;
+pub use layout::LayoutV96;
 pub use layout::LayoutV99;
+pub use layout_map::annotated_hex_dump;
+pub use layout_map::layout_map;
+pub use layout_map::layout_map_with;
+pub use layout_map::LayoutSpan;
+pub use roundtrip::RoundtripMismatch;
+pub use roundtrip::RoundtripReport;
 
-pub(crate) use compatibility::compatibility_issues;
-pub(crate) use decode::decode;
-pub(crate) use encode::encode;
+pub(crate) use compatibility::{class_compatibility_issues, compatibility_issues};
+pub(crate) use decode::{decode, decode_with, find_skills_offset};
+pub(crate) use encode::{encode, encode_with};
+pub(crate) use roundtrip::verify_roundtrip;
 pub(crate) use summary::summarize;
 
 #[cfg(test)]
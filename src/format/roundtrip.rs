@@ -0,0 +1,88 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CompatibilityChecks, ParseHardError, Save, Strictness};
+
+use super::layout::FormatId;
+use super::layout_map::layout_map;
+
+/// Bytes of one field that changed when a save was re-encoded.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct RoundtripMismatch {
+    /// Absolute range from the first to the last differing byte of the field.
+    pub range: Range<usize>,
+    pub section: String,
+    pub field: String,
+}
+
+/// Result of [`Save::verify_roundtrip`].
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct RoundtripReport {
+    /// Format the save was parsed from and re-encoded to.
+    pub format: FormatId,
+    pub original_length: usize,
+    pub encoded_length: usize,
+    /// Differing fields in file order. Empty when the re-encode is byte-identical.
+    pub mismatches: Vec<RoundtripMismatch>,
+}
+
+impl RoundtripReport {
+    pub fn is_exact(&self) -> bool {
+        self.mismatches.is_empty() && self.original_length == self.encoded_length
+    }
+}
+
+/// Parse `bytes` strictly, re-encode them to the same format and compare.
+pub(crate) fn verify_roundtrip(bytes: &[u8]) -> Result<RoundtripReport, ParseHardError> {
+    let save = Save::parse(bytes, Strictness::Strict)?.save;
+    let format = save.format();
+    // Compatibility with the save's own format is not in question here; only the bytes are.
+    let encoded = save.encode_for(format, CompatibilityChecks::Ignore).map_err(|error| {
+        ParseHardError { message: format!("Cannot re-encode save as {format:?}: {error}") }
+    })?;
+    compare(bytes, &encoded, format)
+}
+
+/// Map every byte that differs between `original` and `encoded` to its field in `original`.
+pub(crate) fn compare(
+    original: &[u8],
+    encoded: &[u8],
+    format: FormatId,
+) -> Result<RoundtripReport, ParseHardError> {
+    let differing: Vec<usize> = (0..original.len().max(encoded.len()))
+        .filter(|&offset| original.get(offset) != encoded.get(offset))
+        .collect();
+
+    let mut mismatches = Vec::new();
+    if !differing.is_empty() {
+        let spans = layout_map(original)?;
+        for span in &spans {
+            let mut span_differing =
+                differing.iter().filter(|offset| span.range.contains(offset)).copied();
+            let Some(first) = span_differing.next() else { continue };
+            let last = span_differing.last().unwrap_or(first);
+            mismatches.push(RoundtripMismatch {
+                range: first..(last + 1),
+                section: span.section.clone(),
+                field: span.field.clone(),
+            });
+        }
+
+        // Bytes past the end of `original` have no layout entry.
+        if encoded.len() > original.len() {
+            mismatches.push(RoundtripMismatch {
+                range: original.len()..encoded.len(),
+                section: "file".to_string(),
+                field: "trailing".to_string(),
+            });
+        }
+    }
+
+    Ok(RoundtripReport {
+        format,
+        original_length: original.len(),
+        encoded_length: encoded.len(),
+        mismatches,
+    })
+}
@@ -11,20 +11,21 @@ fn encode_v105_with_mode(mode_marker: u8, mercenary_hired: bool) -> Vec<u8> {
         save.character.mercenary.id = 1;
     }
 
-    encode(&save, FormatId::V105, CompatibilityChecks::Enforce).expect("v105 save should encode")
+    encode(&save, FormatId::V105, CompatibilityChecks::Enforce.into())
+        .expect("v105 save should encode")
 }
 
 #[test]
 fn encodable_formats_lists_supported_versions() {
     let formats = FormatId::encodable_formats();
 
-    assert_eq!(formats, [FormatId::V99, FormatId::V105]);
-    assert_eq!(formats.map(FormatId::version), [99, 105]);
+    assert_eq!(formats, [FormatId::V96, FormatId::V99, FormatId::V105]);
+    assert_eq!(formats.map(FormatId::version), [96, 99, 105]);
 }
 
 #[test]
 fn fallback_for_unknown_version_prefers_edition_hint() {
-    assert_eq!(FormatId::fallback_for_unknown_version(96, Some(GameEdition::RotW)), FormatId::V105);
+    assert_eq!(FormatId::fallback_for_unknown_version(97, Some(GameEdition::RotW)), FormatId::V105);
     assert_eq!(
         FormatId::fallback_for_unknown_version(200, Some(GameEdition::D2RLegacy)),
         FormatId::V99
@@ -33,7 +34,8 @@ fn fallback_for_unknown_version_prefers_edition_hint() {
 
 #[test]
 fn fallback_for_unknown_version_uses_closest_when_hint_missing() {
-    assert_eq!(FormatId::fallback_for_unknown_version(96, None), FormatId::V99);
+    assert_eq!(FormatId::fallback_for_unknown_version(98, None), FormatId::V99);
+    assert_eq!(FormatId::fallback_for_unknown_version(95, None), FormatId::V96);
     assert_eq!(FormatId::fallback_for_unknown_version(104, None), FormatId::V105);
 }
 
@@ -82,14 +84,14 @@ fn decode_unknown_version_prefers_v99_when_markers_match_legacy() {
 #[test]
 fn decode_unknown_version_prefers_v105_when_markers_match_rotw() {
     let mut bytes = include_bytes!("../../assets/test/Warlock_v105.d2s").to_vec();
-    bytes[4..8].copy_from_slice(&96u32.to_le_bytes());
+    bytes[4..8].copy_from_slice(&97u32.to_le_bytes());
 
     let parsed = decode(&bytes, Strictness::Lax).expect("unknown-version rotw save should parse");
 
-    assert_eq!(parsed.detected_format, FormatId::Unknown(96));
+    assert_eq!(parsed.detected_format, FormatId::Unknown(97));
     assert_eq!(parsed.decoded_layout, FormatId::V105);
     assert_eq!(parsed.edition_hint, Some(GameEdition::RotW));
-    assert_eq!(parsed.save.format(), FormatId::Unknown(96));
+    assert_eq!(parsed.save.format(), FormatId::Unknown(97));
     assert_eq!(parsed.save.character.class, Class::Warlock);
 }
 
@@ -102,11 +104,22 @@ fn encode_v105_empty_items_layout() {
         0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x6B, 0x66, 0x00, 0x01, 0x00,
         0x6C, 0x66, 0x00, 0x00,
     ];
+    const EXPANSION_MERC_NO_ITEMS: [u8; 17] = [
+        0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x4A, 0x4D, 0x00, 0x00, 0x6B,
+        0x66, 0x00,
+    ];
+    const ROTW_MERC_NO_ITEMS: [u8; 23] = [
+        0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x4A, 0x4D, 0x00, 0x00, 0x6B,
+        0x66, 0x00, 0x01, 0x00, 0x6C, 0x66, 0x00, 0x00,
+    ];
 
-    let cases: [(u8, bool, &[u8]); 3] = [
+    let cases: [(u8, bool, &[u8]); 6] = [
         (MODE_CLASSIC, false, &CLASSIC_NO_ITEMS),
         (MODE_EXPANSION, false, &EXPANSION_NO_ITEMS),
         (MODE_ROTW, false, &ROTW_NO_ITEMS),
+        (MODE_CLASSIC, true, &CLASSIC_NO_ITEMS),
+        (MODE_EXPANSION, true, &EXPANSION_MERC_NO_ITEMS),
+        (MODE_ROTW, true, &ROTW_MERC_NO_ITEMS),
     ];
 
     for (mode_marker, mercenary_hired, expected_suffix) in cases {
@@ -119,32 +132,27 @@ fn encode_v105_empty_items_layout() {
 }
 
 #[test]
-fn encode_rejects_mercenary_hire_state_toggle_when_enforced() {
+fn check_compatibility_allows_mercenary_hire_state_toggle() {
     let mut save = Save::new(FormatId::V105, Class::Barbarian);
     save.character.mercenary.id = 1;
 
-    let error = encode(&save, FormatId::V105, CompatibilityChecks::Enforce)
-        .expect_err("mercenary hire-state toggle should be rejected");
-    assert!(
-        error.to_string().contains("Changing mercenary.id between 0"),
-        "unexpected error message: {error}"
-    );
-
-    let issues = save.check_compatibility(FormatId::V105);
-    assert!(issues.iter().any(|issue| {
-        issue.code == crate::CompatibilityCode::MercenaryHireStateToggleUnsupported
-            && issue.blocking
-    }));
+    assert!(save.check_compatibility(FormatId::V105).is_empty());
 }
 
 #[test]
-fn encode_allows_mercenary_hire_state_toggle_when_ignored() {
-    let mut save = Save::new(FormatId::V105, Class::Barbarian);
-    save.character.mercenary.id = 1;
-
-    let encoded = encode(&save, FormatId::V105, CompatibilityChecks::Ignore)
-        .expect("mercenary hire-state toggle should be force-encodable");
-    assert!(!encoded.is_empty(), "forced encode should still produce bytes");
+fn encode_removes_mercenary_items_when_fired() {
+    const EXPANSION_NO_ITEMS: [u8; 13] =
+        [0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x6B, 0x66, 0x00];
+    let mut save = Save::parse(include_bytes!("../../assets/test/Ayame.d2s"), Strictness::Strict)
+        .expect("Ayame should parse")
+        .save;
+    assert!(save.character.mercenary.is_hired());
+    save.items = crate::items::Items::default();
+    save.character.mercenary = crate::character::mercenary::Mercenary::default();
+
+    let encoded = encode(&save, save.format(), CompatibilityChecks::Enforce.into())
+        .expect("firing a mercenary should encode");
+    assert!(encoded.ends_with(&EXPANSION_NO_ITEMS));
 }
 
 #[test]
@@ -152,7 +160,7 @@ fn encode_v105_preserves_legacy_expansion_status_bit() {
     let mut save = Save::new(FormatId::V105, Class::Barbarian);
     save.character.set_legacy_expansion_flag(true);
 
-    let encoded = encode(&save, FormatId::V105, CompatibilityChecks::Enforce)
+    let encoded = encode(&save, FormatId::V105, CompatibilityChecks::Enforce.into())
         .expect("v105 save should encode");
     let encoded_status = encoded[CHARACTER_SECTION_START + OFFSET_STATUS];
 
@@ -168,7 +176,7 @@ fn encode_v99_rejects_rotw_expansion_type() {
     let mut save = Save::new(FormatId::V99, Class::Barbarian);
     save.set_expansion_type(ExpansionType::RotW);
 
-    let error = encode(&save, FormatId::V99, CompatibilityChecks::Enforce)
+    let error = encode(&save, FormatId::V99, CompatibilityChecks::Enforce.into())
         .expect_err("v99 should reject RotW expansion type");
     assert!(
         error.to_string().contains("RotW expansion mode cannot be encoded to non-RotW formats"),
@@ -240,12 +248,12 @@ fn summarize_v105_uses_mode_marker_over_status_bit_for_expansion_type() {
 #[test]
 fn summarize_unknown_version_uses_layout_fallback_and_reports_issue() {
     let mut bytes = include_bytes!("../../assets/test/Warlock_v105.d2s").to_vec();
-    bytes[4..8].copy_from_slice(&96u32.to_le_bytes());
+    bytes[4..8].copy_from_slice(&97u32.to_le_bytes());
 
     let summary = Save::summarize(&bytes, Strictness::Lax).expect("summary should parse");
 
-    assert_eq!(summary.version, Some(96));
-    assert_eq!(summary.format, Some(FormatId::Unknown(96)));
+    assert_eq!(summary.version, Some(97));
+    assert_eq!(summary.format, Some(FormatId::Unknown(97)));
     assert_eq!(summary.edition, None);
     assert_eq!(summary.class, Some(Class::Warlock));
     assert_eq!(summary.expansion_type, Some(ExpansionType::RotW));
@@ -303,3 +311,270 @@ fn summarize_warns_when_unhired_mercenary_has_nonzero_fields() {
             && issue.message.contains("mercenary id is 0")
     }));
 }
+
+#[test]
+fn layout_map_covers_every_byte_in_order() {
+    for bytes in [
+        &include_bytes!("../../assets/test/Joe.d2s")[..],
+        &include_bytes!("../../assets/test/Warlock_v105.d2s")[..],
+    ] {
+        let spans = layout_map(bytes).expect("fixture should map");
+        let mut byte_cursor = 0;
+        let mut bit_cursor: Option<usize> = None;
+        for span in &spans {
+            match &span.bits {
+                Some(bits) => {
+                    assert_eq!(bits.start, bit_cursor.unwrap_or(byte_cursor * 8), "{span:?}");
+                    bit_cursor = Some(bits.end);
+                }
+                None => {
+                    assert_eq!(span.range.start, byte_cursor, "gap or overlap before {span:?}");
+                    bit_cursor = None;
+                }
+            }
+            byte_cursor = span.range.end;
+        }
+        assert_eq!(byte_cursor, bytes.len());
+    }
+}
+
+#[test]
+fn layout_map_uses_codec_and_layout_offsets() {
+    let bytes = include_bytes!("../../assets/test/Warlock_v105.d2s");
+    let spans = layout_map(bytes).unwrap();
+    let find = |section: &str, field: &str| {
+        spans
+            .iter()
+            .find(|span| span.section == section && span.field == field)
+            .unwrap_or_else(|| panic!("missing {section}.{field}"))
+    };
+
+    let mode_marker = CHARACTER_SECTION_START + crate::character::v105::OFFSET_MODE_MARKER;
+    assert_eq!(find("character", "mode_marker").range, mode_marker..mode_marker + 1);
+    let unknown_four = crate::character::v105::RANGE_UNKNOWN_REGION_FOUR;
+    assert_eq!(
+        find("character", "unknown_region_four").range,
+        (CHARACTER_SECTION_START + unknown_four.start)
+            ..(CHARACTER_SECTION_START + unknown_four.end)
+    );
+    assert_eq!(find("quests", "header").range.start, LayoutV105.quests_start());
+    assert_eq!(find("npcs", "header").range.start, LayoutV105.npcs_start());
+
+    let attributes_header = find("attributes", "header");
+    assert_eq!(
+        attributes_header.bits,
+        Some(LayoutV105.attributes_offset() * 8..LayoutV105.attributes_offset() * 8 + 16)
+    );
+    let level = find("attributes", "level");
+    assert_eq!(level.bits.as_ref().map(|bits| bits.len()), Some(7));
+}
+
+#[test]
+fn annotated_hex_dump_labels_spans() {
+    let bytes = include_bytes!("../../assets/test/Joe.d2s");
+    let spans = layout_map(bytes).unwrap();
+    let dump = annotated_hex_dump(bytes, &spans);
+
+    assert!(dump.starts_with("0x00000..0x00004  header.signature"), "{dump}");
+    assert!(dump.lines().next().unwrap().ends_with("55 aa 55 aa"));
+    let level_line = dump.lines().find(|line| line.contains("attributes.level ")).unwrap();
+    assert!(
+        level_line.ends_with(&format!(
+            "= {}",
+            Save::parse(bytes, Strictness::Strict).unwrap().save.character.level()
+        )),
+        "{level_line}"
+    );
+    assert!(dump.contains("character.unknown_region_one"));
+}
+
+fn joe_with_modded_stat() -> (Save, Vec<u8>) {
+    let mut save =
+        decode(include_bytes!("../../assets/test/Joe.d2s"), Strictness::Strict).unwrap().save;
+    save.attributes.extra.push(crate::attributes::Stat {
+        id: 359,
+        name: "souls_collected".to_string(),
+        bit_length: 16,
+        value: 1234,
+    });
+    let bytes = encode(&save, FormatId::V99, CompatibilityChecks::Enforce.into()).unwrap();
+    (save, bytes)
+}
+
+fn test_game_data() -> crate::data::GameData {
+    crate::data::GameData::load(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/test/data")).unwrap()
+}
+
+/// [`joe_with_modded_stat`] with the attribute widths of the test tables.
+fn joe_with_test_table_widths() -> (Save, Vec<u8>) {
+    let (mut save, _) = joe_with_modded_stat();
+    // The test tables also widen strength to 12 bits.
+    save.attributes.strength.bit_length = 12;
+    let bytes = encode(&save, FormatId::V99, CompatibilityChecks::Enforce.into()).unwrap();
+    (save, bytes)
+}
+
+#[test]
+fn decode_with_game_data_keeps_modded_stats() {
+    let game_data = test_game_data();
+    let (save, bytes) = joe_with_test_table_widths();
+
+    let parsed = decode_with(&bytes, Strictness::Strict, Some(&game_data)).unwrap();
+    assert!(parsed.issues.is_empty(), "{:?}", parsed.issues);
+    assert_eq!(parsed.save.attributes.extra, save.attributes.extra);
+    assert_eq!(parsed.save.attributes.strength, save.attributes.strength);
+    assert!(parsed.save.attributes.present_stats().unwrap().contains(&359));
+    assert_eq!(
+        encode(&parsed.save, FormatId::V99, CompatibilityChecks::Enforce.into()).unwrap(),
+        bytes
+    );
+}
+
+#[test]
+fn decode_keeps_unknown_stats_raw_in_lax_mode() {
+    let (save, bytes) = joe_with_modded_stat();
+
+    decode(&bytes, Strictness::Strict).expect_err("strict decode should reject unknown stat ids");
+
+    let parsed = decode(&bytes, Strictness::Lax).unwrap();
+    let issue = parsed
+        .issues
+        .iter()
+        .find(|issue| matches!(issue.kind, IssueKind::InvalidValue))
+        .expect("unknown stat should be reported");
+    assert!(issue.message.contains("Unknown attributes stat id 359"), "{}", issue.message);
+
+    assert!(parsed.save.attributes.extra.is_empty());
+    assert!(parsed.save.attributes.unread.is_some());
+    assert_eq!(
+        parsed.save.attributes.stat(crate::attributes::AttributeId::Gold),
+        save.attributes.stat(crate::attributes::AttributeId::Gold)
+    );
+    assert_eq!(parsed.save.skills, save.skills);
+    assert_eq!(parsed.save.items, save.items);
+    assert_eq!(
+        encode(&parsed.save, FormatId::V99, CompatibilityChecks::Enforce.into()).unwrap(),
+        bytes
+    );
+
+    // Edited stats shift the unread bits, which are written back after them.
+    let mut edited = parsed.save;
+    edited.attributes.gold.value = 0;
+    edited.attributes.goldbank.value = 123_456;
+    let bytes = encode(&edited, FormatId::V99, CompatibilityChecks::Enforce.into()).unwrap();
+    let reparsed = decode(&bytes, Strictness::Lax).unwrap().save;
+    assert_eq!(reparsed.attributes.goldbank.value, 123_456);
+    assert_eq!(reparsed.attributes.unread, edited.attributes.unread);
+    assert_eq!(reparsed.skills, save.skills);
+
+    let read = |file: &str| {
+        std::fs::read_to_string(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/assets/test/data/").to_string() + file,
+        )
+        .unwrap()
+    };
+    // The test tables with the default strength width, to read the shifted modded stat.
+    let game_data = crate::data::GameData::parse(
+        &read("skills.txt"),
+        &read("itemstatcost.txt")
+            .replace("strength\t0\t\t8\t32\t\t12", "strength\t0\t\t8\t32\t\t10"),
+        &read("hireling.txt"),
+        &read("charstats.txt"),
+    )
+    .unwrap();
+    let with_table = decode_with(&bytes, Strictness::Strict, Some(&game_data)).unwrap();
+    assert_eq!(with_table.save.attributes.extra, save.attributes.extra);
+    assert_eq!(with_table.save.attributes.goldbank.value, 123_456);
+}
+
+#[test]
+fn layout_map_spans_unknown_and_table_stats() {
+    let unread_span = |spans: &[LayoutSpan]| {
+        spans.iter().any(|span| span.section == "attributes" && span.field == "unread")
+    };
+
+    let (_, bytes) = joe_with_modded_stat();
+    let spans = layout_map(&bytes).unwrap();
+    assert!(unread_span(&spans));
+    assert!(!spans.iter().any(|span| span.field == "trailer"));
+    assert_eq!(spans.last().map(|span| span.range.end), Some(bytes.len()));
+
+    let (_, bytes) = joe_with_test_table_widths();
+    let spans = layout_map_with(&bytes, &test_game_data()).unwrap();
+    assert!(!unread_span(&spans));
+    let souls = spans.iter().find(|span| span.field == "souls_collected").unwrap();
+    assert_eq!(souls.bits.as_ref().map(|bits| bits.len()), Some(16));
+    let strength = spans.iter().find(|span| span.field == "strength").unwrap();
+    assert_eq!(strength.bits.as_ref().map(|bits| bits.len()), Some(12));
+    assert_eq!(spans.last().map(|span| span.range.end), Some(bytes.len()));
+}
+
+#[test]
+fn encode_options_drive_the_encode_pipeline() {
+    use crate::attributes::AttributeOmission;
+    use crate::{EncodeOptions, RawRegions};
+
+    let source = include_bytes!("../../assets/test/Joe.d2s");
+    let mut save = decode(source, Strictness::Strict).unwrap().save;
+    let plain = encode(&save, FormatId::V99, EncodeOptions::new()).unwrap();
+    assert_eq!(plain, source);
+
+    let refreshed =
+        encode(&save, FormatId::V99, EncodeOptions::new().refresh_last_played(true)).unwrap();
+    let refreshed = decode(&refreshed, Strictness::Strict).unwrap().save;
+    assert!(refreshed.character.last_played > save.character.last_played);
+
+    // Byte 4 of the v99 character section is not modeled.
+    save.character.raw_section[4] = 0xAB;
+    let preserved = encode(&save, FormatId::V99, EncodeOptions::new()).unwrap();
+    assert_eq!(preserved[CHARACTER_SECTION_START + 4], 0xAB);
+    let regenerated =
+        encode(&save, FormatId::V99, EncodeOptions::new().raw_regions(RawRegions::Regenerate))
+            .unwrap();
+    assert_eq!(regenerated[CHARACTER_SECTION_START + 4], 0);
+    let regenerated = decode(&regenerated, Strictness::Strict).unwrap().save;
+    assert_eq!(regenerated.character.name, save.character.name);
+
+    save.attributes.gold.value = 0;
+    let omitted = EncodeOptions::new().attribute_omission(AttributeOmission::OmitZeros);
+    let omitted =
+        decode(&encode(&save, FormatId::V99, omitted).unwrap(), Strictness::Strict).unwrap().save;
+    assert!(!omitted.attributes.present_stats().unwrap().contains(&14));
+
+    save.character.set_level(save.character.level() + 1);
+    assert!(encode(&save, FormatId::V99, EncodeOptions::new()).is_ok());
+    let error = encode(&save, FormatId::V99, EncodeOptions::new().block_on_validation(true))
+        .expect_err("level mismatch should block the encode");
+    assert!(error.to_string().contains("does not match attributes level"), "{error}");
+}
+
+#[test]
+fn roundtrip_compare_maps_differences_to_fields() {
+    let source = include_bytes!("../../assets/test/Joe.d2s");
+    let report = Save::verify_roundtrip(source).unwrap();
+    assert!(report.is_exact(), "{report:?}");
+
+    let mut changed = source.to_vec();
+    let name_offset = CHARACTER_SECTION_START + crate::character::v99::RANGE_NAME.start;
+    changed[name_offset + 1] = b'a';
+    changed[name_offset + 2] = b'y';
+    changed.push(0xFF);
+
+    let report = roundtrip::compare(source, &changed, FormatId::V99).unwrap();
+    assert!(!report.is_exact());
+    assert_eq!(report.encoded_length, source.len() + 1);
+    let mismatches: Vec<_> = report
+        .mismatches
+        .iter()
+        .map(|mismatch| {
+            (mismatch.range.clone(), mismatch.section.as_str(), mismatch.field.as_str())
+        })
+        .collect();
+    assert_eq!(
+        mismatches,
+        [
+            ((name_offset + 1)..(name_offset + 3), "character", "name"),
+            (source.len()..(source.len() + 1), "file", "trailing"),
+        ]
+    );
+}
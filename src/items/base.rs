@@ -0,0 +1,110 @@
+//! Default D2R base-item classification.
+//!
+//! Extended items carry defense, durability and quantity fields depending on
+//! whether their base type is an armor, a weapon or a stackable item. The save
+//! format does not store this, so it is looked up from the item code.
+
+/// Base-item category relevant to the item bit layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BaseKind {
+    Armor,
+    Weapon,
+    Other,
+}
+
+const ARMOR_CODES: [&str; 202] = [
+    // Helms and circlets.
+    "cap", "skp", "hlm", "fhl", "ghm", "crn", "msk", "bhm", "xap", "xkp", "xlm", "xhl", "xhm",
+    "xrn", "xsk", "xh9", "uap", "ukp", "ulm", "uhl", "uhm", "urn", "usk", "uh9", "ci0", "ci1",
+    "ci2", "ci3", // Body armor.
+    "qui", "lea", "hla", "stu", "rng", "scl", "chn", "brs", "spl", "plt", "fld", "gth", "ful",
+    "aar", "ltp", "xui", "xea", "xla", "xtu", "xng", "xcl", "xhn", "xrs", "xpl", "xlt", "xld",
+    "xth", "xul", "xar", "xtp", "uui", "uea", "ula", "utu", "ung", "ucl", "uhn", "urs", "upl",
+    "ult", "uld", "uth", "uul", "uar", "utp", // Shields.
+    "buc", "sml", "lrg", "kit", "tow", "gts", "bsh", "spk", "xuc", "xml", "xrg", "xit", "xow",
+    "xts", "xsh", "xpk", "uuc", "uml", "urg", "uit", "uow", "uts", "ush", "upk",
+    // Gloves.
+    "lgl", "vgl", "mgl", "tgl", "hgl", "xlg", "xvg", "xmg", "xtg", "xhg", "ulg", "uvg", "umg",
+    "utg", "uhg", // Boots.
+    "lbt", "vbt", "mbt", "tbt", "hbt", "xlb", "xvb", "xmb", "xtb", "xhb", "ulb", "uvb", "umb",
+    "utb", "uhb", // Belts.
+    "lbl", "vbl", "mbl", "tbl", "hbl", "zlb", "zvb", "zmb", "ztb", "zhb", "ulc", "uvc", "umc",
+    "utc", "uhc",
+    // Class-specific armor: Barbarian helms, Druid pelts, Paladin shields, Necromancer heads.
+    "ba1", "ba2", "ba3", "ba4", "ba5", "ba6", "ba7", "ba8", "ba9", "baa", "bab", "bac", "bad",
+    "bae", "baf", "dr1", "dr2", "dr3", "dr4", "dr5", "dr6", "dr7", "dr8", "dr9", "dra", "drb",
+    "drc", "drd", "dre", "drf", "pa1", "pa2", "pa3", "pa4", "pa5", "pa6", "pa7", "pa8", "pa9",
+    "paa", "pab", "pac", "pad", "pae", "paf", "ne1", "ne2", "ne3", "ne4", "ne5", "ne6", "ne7",
+    "ne8", "ne9", "nea", "neb", "nec", "ned", "nee", "nef",
+];
+
+const WEAPON_CODES: [&str; 306] = [
+    // Axes.
+    "hax", "axe", "2ax", "mpi", "wax", "lax", "bax", "btx", "gax", "gix", "9ha", "9ax", "92a",
+    "9mp", "9wa", "9la", "9ba", "9bt", "9ga", "9gi", "7ha", "7ax", "72a", "7mp", "7wa", "7la",
+    "7ba", "7bt", "7ga", "7gi", // Wands.
+    "wnd", "ywn", "bwn", "gwn", "9wn", "9yw", "9bw", "9gw", "7wn", "7yw", "7bw", "7gw",
+    // Clubs, maces and hammers.
+    "clb", "scp", "gsc", "wsp", "spc", "mac", "mst", "fla", "whm", "mau", "gma", "9cl", "9sc",
+    "9qs", "9ws", "9sp", "9ma", "9mt", "9fl", "9wh", "9m9", "9gm", "7cl", "7sc", "7qs", "7ws",
+    "7sp", "7ma", "7mt", "7fl", "7wh", "7m7", "7gm", // Swords.
+    "ssd", "scm", "sbr", "flc", "crs", "bsd", "lsd", "wsd", "2hs", "clm", "gis", "bsw", "flb",
+    "gsd", "9ss", "9sm", "9sb", "9fc", "9cr", "9bs", "9ls", "9wd", "92h", "9cm", "9gs", "9b9",
+    "9fb", "9gd", "7ss", "7sm", "7sb", "7fc", "7cr", "7bs", "7ls", "7wd", "72h", "7cm", "7gs",
+    "7b7", "7fb", "7gd", // Daggers.
+    "dgr", "dir", "kri", "bld", "9dg", "9di", "9kr", "9bl", "7dg", "7di", "7kr", "7bl",
+    // Throwing knives and axes.
+    "tkf", "tax", "bkf", "bal", "9tk", "9ta", "9bk", "9b8", "7tk", "7ta", "7bk", "7b8",
+    // Javelins.
+    "jav", "pil", "ssp", "glv", "tsp", "9ja", "9pi", "9s9", "9gl", "9ts", "7ja", "7pi", "7s7",
+    "7gl", "7ts", // Spears.
+    "spr", "tri", "brn", "spt", "pik", "9sr", "9tr", "9br", "9st", "9p9", "7sr", "7tr", "7br",
+    "7st", "7p7", // Polearms.
+    "bar", "vou", "scy", "pax", "hal", "wsc", "9b7", "9vo", "9s8", "9pa", "9h9", "9wc", "7o7",
+    "7vo", "7s8", "7pa", "7h7", "7wc", // Staves.
+    "sst", "lst", "cst", "bst", "wst", "8ss", "8ls", "8cs", "8bs", "8ws", "6ss", "6ls", "6cs",
+    "6bs", "6ws", // Bows.
+    "sbw", "hbw", "lbw", "cbw", "sbb", "lbb", "swb", "lwb", "8sb", "8hb", "8lb", "8cb", "8s8",
+    "8l8", "8sw", "8lw", "6sb", "6hb", "6lb", "6cb", "6s7", "6l7", "6sw", "6lw",
+    // Crossbows.
+    "lxb", "mxb", "hxb", "rxb", "8lx", "8mx", "8hx", "8rx", "6lx", "6mx", "6hx", "6rx",
+    // Throwing potions and quest weapons.
+    "gps", "ops", "gpm", "opm", "gpl", "opl", "d33", "g33", "leg", "hdm", "hfh", "hst", "msf",
+    "qf1", "qf2", // Assassin katars.
+    "ktr", "wrb", "axf", "ces", "clw", "btl", "skr", "9ar", "9wb", "9xf", "9cs", "9lw", "9tw",
+    "9qr", "7ar", "7wb", "7xf", "7cs", "7lw", "7tw", "7qr", // Sorceress orbs.
+    "ob1", "ob2", "ob3", "ob4", "ob5", "ob6", "ob7", "ob8", "ob9", "oba", "obb", "obc", "obd",
+    "obe", "obf", // Amazon bows, spears and javelins.
+    "am1", "am2", "am3", "am4", "am5", "am6", "am7", "am8", "am9", "ama", "amb", "amc", "amd",
+    "ame", "amf",
+];
+
+const STACKABLE_CODES: [&str; 41] = [
+    "tkf", "tax", "bkf", "bal", "9tk", "9ta", "9bk", "9b8", "7tk", "7ta", "7bk", "7b8", "jav",
+    "pil", "ssp", "glv", "tsp", "9ja", "9pi", "9s9", "9gl", "9ts", "7ja", "7pi", "7s7", "7gl",
+    "7ts", "gps", "ops", "gpm", "opm", "gpl", "opl", "am5", "ama", "amf", "tbk", "ibk", "key",
+    "aqv", "cqv",
+];
+
+const TOME_CODES: [&str; 2] = ["tbk", "ibk"];
+
+/// Classify an item code as armor, weapon, or anything else.
+pub(crate) fn base_kind(code: &str) -> BaseKind {
+    if ARMOR_CODES.contains(&code) {
+        BaseKind::Armor
+    } else if WEAPON_CODES.contains(&code) {
+        BaseKind::Weapon
+    } else {
+        BaseKind::Other
+    }
+}
+
+/// Whether extended items of this base type carry a quantity field.
+pub(crate) fn is_stackable(code: &str) -> bool {
+    STACKABLE_CODES.contains(&code)
+}
+
+/// Whether extended items of this base type carry the 5-bit tome field.
+pub(crate) fn is_tome(code: &str) -> bool {
+    TOME_CODES.contains(&code)
+}
@@ -0,0 +1,114 @@
+//! Huffman coding for D2R item type codes.
+//!
+//! D2R stores the 4-character item code (3 characters plus a trailing space)
+//! as Huffman-coded symbols instead of raw 8-bit characters.
+
+use crate::utils::{read_bits, write_bits, BytePosition};
+use crate::ParseHardError;
+
+/// Symbol table as `(symbol, code length, code bits in stream order)`.
+///
+/// Code bits are stored LSB-first, matching the order they are read from the stream.
+const HUFFMAN_TABLE: [(char, usize, u32); 37] = [
+    (' ', 2, 0b01),
+    ('0', 8, 0b1101_1111),
+    ('1', 7, 0b001_1111),
+    ('2', 6, 0b00_1100),
+    ('3', 7, 0b101_1011),
+    ('4', 8, 0b0101_1111),
+    ('5', 8, 0b0110_1000),
+    ('6', 7, 0b111_1011),
+    ('7', 5, 0b1_1110),
+    ('8', 6, 0b00_1000),
+    ('9', 5, 0b0_1110),
+    ('a', 5, 0b0_1111),
+    ('b', 4, 0b1010),
+    ('c', 5, 0b0_0010),
+    ('d', 6, 0b10_0011),
+    ('e', 6, 0b00_0011),
+    ('f', 6, 0b11_0010),
+    ('g', 5, 0b0_1011),
+    ('h', 5, 0b1_1000),
+    ('i', 7, 0b011_1111),
+    ('j', 9, 0b0_1110_1000),
+    ('k', 6, 0b01_0010),
+    ('l', 5, 0b1_0111),
+    ('m', 5, 0b1_0110),
+    ('n', 6, 0b10_1100),
+    ('o', 7, 0b111_1111),
+    ('p', 5, 0b1_0011),
+    ('q', 8, 0b1001_1011),
+    ('r', 5, 0b0_0111),
+    ('s', 4, 0b0100),
+    ('t', 5, 0b0_0110),
+    ('u', 5, 0b1_0000),
+    ('v', 7, 0b011_1011),
+    ('w', 5, 0b0_0000),
+    ('x', 5, 0b1_1100),
+    ('y', 7, 0b010_1000),
+    ('z', 8, 0b0001_1011),
+];
+
+const MAX_CODE_LENGTH: usize = 9;
+
+/// Number of symbols stored for every item code.
+pub(crate) const ITEM_CODE_SYMBOLS: usize = 4;
+
+fn read_symbol(
+    byte_slice: &[u8],
+    byte_position: &mut BytePosition,
+) -> Result<char, ParseHardError> {
+    let mut code_bits: u32 = 0;
+    for code_length in 1..=MAX_CODE_LENGTH {
+        code_bits |= read_bits(byte_slice, byte_position, 1)? << (code_length - 1);
+        if let Some((symbol, _, _)) = HUFFMAN_TABLE
+            .iter()
+            .find(|(_, length, bits)| *length == code_length && *bits == code_bits)
+        {
+            return Ok(*symbol);
+        }
+    }
+
+    Err(ParseHardError {
+        message: format!("Invalid Huffman-coded item code bits {code_bits:#011b}."),
+    })
+}
+
+/// Read a Huffman-coded item code and trim its trailing padding.
+pub(crate) fn read_item_code(
+    byte_slice: &[u8],
+    byte_position: &mut BytePosition,
+) -> Result<String, ParseHardError> {
+    let mut code = String::with_capacity(ITEM_CODE_SYMBOLS);
+    for _ in 0..ITEM_CODE_SYMBOLS {
+        code.push(read_symbol(byte_slice, byte_position)?);
+    }
+    Ok(code.trim_end_matches(' ').to_string())
+}
+
+/// Write an item code as Huffman-coded symbols, padding it to 4 symbols with spaces.
+pub(crate) fn write_item_code(
+    byte_vector: &mut Vec<u8>,
+    byte_position: &mut BytePosition,
+    code: &str,
+) -> Result<(), ParseHardError> {
+    let symbol_count = code.chars().count();
+    if symbol_count > ITEM_CODE_SYMBOLS {
+        return Err(ParseHardError {
+            message: format!("Item code '{code}' is longer than {ITEM_CODE_SYMBOLS} characters."),
+        });
+    }
+
+    let padding = std::iter::repeat_n(' ', ITEM_CODE_SYMBOLS - symbol_count);
+    for symbol in code.chars().chain(padding) {
+        let Some((_, code_length, code_bits)) =
+            HUFFMAN_TABLE.iter().find(|(candidate, _, _)| *candidate == symbol)
+        else {
+            return Err(ParseHardError {
+                message: format!("Item code '{code}' contains unsupported character '{symbol}'."),
+            });
+        };
+        write_bits(byte_vector, byte_position, *code_bits, *code_length)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,1001 @@
+//! Single-item model and bit-level codec.
+
+use serde::{Deserialize, Serialize};
+
+use crate::format::FormatId;
+use crate::utils::{read_bits, write_bits, BytePosition};
+use crate::{GameEdition, ParseHardError};
+
+use super::base::{base_kind, is_stackable, is_tome, BaseKind};
+use super::huffman::{read_item_code, write_item_code};
+use super::properties::{ItemStatCost, PropertyList};
+use super::{RawBits, ITEM_LIST_HEADER};
+
+const FLAG_IDENTIFIED: u32 = 1 << 4;
+const FLAG_SOCKETED: u32 = 1 << 11;
+const FLAG_NEW: u32 = 1 << 13;
+const FLAG_EAR: u32 = 1 << 16;
+const FLAG_STARTER: u32 = 1 << 17;
+const FLAG_SIMPLE: u32 = 1 << 21;
+const FLAG_ETHEREAL: u32 = 1 << 22;
+const FLAG_PERSONALIZED: u32 = 1 << 24;
+const FLAG_RUNEWORD: u32 = 1 << 26;
+
+const DEFENSE_BITS: usize = 11;
+const DEFENSE_SAVE_ADD: i32 = 10;
+const MAX_DURABILITY_BITS: usize = 8;
+const DURABILITY_BITS: usize = 9;
+const QUANTITY_BITS: usize = 9;
+const TOTAL_SOCKETS_BITS: usize = 4;
+const SET_LIST_MASK_BITS: usize = 5;
+const REALM_DATA_BITS: usize = 96;
+const NAME_CHARACTER_BITS: usize = 8;
+const NAME_MAX_CHARACTERS: usize = 16;
+const VERSION_BITS: usize = 3;
+const CLASSIC_VERSION_BITS: usize = 10;
+const CLASSIC_NAME_CHARACTER_BITS: usize = 7;
+const CLASSIC_ITEM_CODE_LENGTH: usize = 4;
+
+/// Raw 32-bit item flag field.
+///
+/// Structural bits (simple, ear, socketed, personalized, runeword) are kept in
+/// sync with the item model on encode.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct ItemFlags(u32);
+
+impl ItemFlags {
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    const fn has(self, flag: u32) -> bool {
+        self.0 & flag != 0
+    }
+
+    fn set(&mut self, flag: u32, value: bool) {
+        if value {
+            self.0 |= flag;
+        } else {
+            self.0 &= !flag;
+        }
+    }
+
+    pub const fn is_identified(self) -> bool {
+        self.has(FLAG_IDENTIFIED)
+    }
+
+    pub const fn is_socketed(self) -> bool {
+        self.has(FLAG_SOCKETED)
+    }
+
+    /// Picked up since the last save.
+    pub const fn is_new(self) -> bool {
+        self.has(FLAG_NEW)
+    }
+
+    pub const fn is_ear(self) -> bool {
+        self.has(FLAG_EAR)
+    }
+
+    /// Part of a character's starting equipment.
+    pub const fn is_starter(self) -> bool {
+        self.has(FLAG_STARTER)
+    }
+
+    /// Simple (compact) items have no extended data block.
+    pub const fn is_simple(self) -> bool {
+        self.has(FLAG_SIMPLE)
+    }
+
+    pub const fn is_ethereal(self) -> bool {
+        self.has(FLAG_ETHEREAL)
+    }
+
+    pub const fn is_personalized(self) -> bool {
+        self.has(FLAG_PERSONALIZED)
+    }
+
+    pub const fn is_runeword(self) -> bool {
+        self.has(FLAG_RUNEWORD)
+    }
+
+    pub fn set_identified(&mut self, identified: bool) {
+        self.set(FLAG_IDENTIFIED, identified);
+    }
+
+    pub fn set_new(&mut self, new: bool) {
+        self.set(FLAG_NEW, new);
+    }
+
+    pub fn set_ethereal(&mut self, ethereal: bool) {
+        self.set(FLAG_ETHEREAL, ethereal);
+    }
+}
+
+/// Where an item currently lives relative to its owner.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub enum ItemMode {
+    /// In a grid storage (inventory, cube, or stash).
+    #[default]
+    Stored,
+    Equipped,
+    Belt,
+    Ground,
+    Cursor,
+    Dropping,
+    /// Inserted into another item's socket.
+    Socketed,
+    Unknown(u8),
+}
+
+impl ItemMode {
+    pub const fn from_id(mode_id: u8) -> Self {
+        match mode_id {
+            0 => ItemMode::Stored,
+            1 => ItemMode::Equipped,
+            2 => ItemMode::Belt,
+            3 => ItemMode::Ground,
+            4 => ItemMode::Cursor,
+            5 => ItemMode::Dropping,
+            6 => ItemMode::Socketed,
+            _ => ItemMode::Unknown(mode_id),
+        }
+    }
+
+    pub const fn id(self) -> u8 {
+        match self {
+            ItemMode::Stored => 0,
+            ItemMode::Equipped => 1,
+            ItemMode::Belt => 2,
+            ItemMode::Ground => 3,
+            ItemMode::Cursor => 4,
+            ItemMode::Dropping => 5,
+            ItemMode::Socketed => 6,
+            ItemMode::Unknown(mode_id) => mode_id,
+        }
+    }
+}
+
+/// Grid storage for [`ItemMode::Stored`] items.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub enum ItemStorage {
+    /// Not in a grid storage (equipped, belt, socketed, ...).
+    #[default]
+    None,
+    Inventory,
+    Cube,
+    Stash,
+    Unknown(u8),
+}
+
+impl ItemStorage {
+    pub const fn from_id(storage_id: u8) -> Self {
+        match storage_id {
+            0 => ItemStorage::None,
+            1 => ItemStorage::Inventory,
+            4 => ItemStorage::Cube,
+            5 => ItemStorage::Stash,
+            _ => ItemStorage::Unknown(storage_id),
+        }
+    }
+
+    pub const fn id(self) -> u8 {
+        match self {
+            ItemStorage::None => 0,
+            ItemStorage::Inventory => 1,
+            ItemStorage::Cube => 4,
+            ItemStorage::Stash => 5,
+            ItemStorage::Unknown(storage_id) => storage_id,
+        }
+    }
+}
+
+/// Item quality tier stored in the extended block.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub enum ItemQuality {
+    Inferior,
+    #[default]
+    Normal,
+    Superior,
+    Magic,
+    Set,
+    Rare,
+    Unique,
+    Crafted,
+    Unknown(u8),
+}
+
+impl ItemQuality {
+    pub const fn from_id(quality_id: u8) -> Self {
+        match quality_id {
+            1 => ItemQuality::Inferior,
+            2 => ItemQuality::Normal,
+            3 => ItemQuality::Superior,
+            4 => ItemQuality::Magic,
+            5 => ItemQuality::Set,
+            6 => ItemQuality::Rare,
+            7 => ItemQuality::Unique,
+            8 => ItemQuality::Crafted,
+            _ => ItemQuality::Unknown(quality_id),
+        }
+    }
+
+    pub const fn id(self) -> u8 {
+        match self {
+            ItemQuality::Inferior => 1,
+            ItemQuality::Normal => 2,
+            ItemQuality::Superior => 3,
+            ItemQuality::Magic => 4,
+            ItemQuality::Set => 5,
+            ItemQuality::Rare => 6,
+            ItemQuality::Unique => 7,
+            ItemQuality::Crafted => 8,
+            ItemQuality::Unknown(quality_id) => quality_id,
+        }
+    }
+}
+
+/// Quality-specific naming data.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub enum QualityData {
+    /// Normal items carry no extra data.
+    #[default]
+    None,
+    /// Inferior/superior sub-type (3 bits).
+    LowOrHigh(u8),
+    /// Magic prefix and suffix ids (`0` means none).
+    Magic { prefix: u16, suffix: u16 },
+    /// Rare/crafted name ids and up to three prefixes and suffixes.
+    Rare { first_name: u8, second_name: u8, prefixes: [Option<u16>; 3], suffixes: [Option<u16>; 3] },
+    /// Set or unique row id.
+    SetOrUnique(u16),
+}
+
+/// Runeword header stored on runeword items.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct Runeword {
+    /// Runeword row id (12 bits).
+    pub id: u16,
+    /// Trailing 4-bit field, `5` on observed saves.
+    pub unknown: u8,
+}
+
+/// Durability fields for armor and weapon bases.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct Durability {
+    /// Maximum durability. `0` means indestructible and omits `current`.
+    pub max: u8,
+    /// Current durability (9-bit field).
+    pub current: u16,
+}
+
+/// Ear data stored instead of an item code.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Ear {
+    /// Class id of the ear's owner.
+    pub class_id: u8,
+    pub level: u8,
+    pub name: String,
+}
+
+/// Extended item block present on every non-simple item.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ExtendedItem {
+    /// Unique item instance id.
+    pub id: u32,
+    pub item_level: u8,
+    pub quality: ItemQuality,
+    /// Alternate inventory graphic index (rings, amulets, charms, jewels).
+    pub graphic: Option<u8>,
+    /// Class-specific automatic affix id.
+    pub class_affix: Option<u16>,
+    pub quality_data: QualityData,
+    pub runeword: Option<Runeword>,
+    pub personalized_name: Option<String>,
+    /// 5-bit field only present on tomes.
+    pub tome_data: Option<u8>,
+    /// Battle.net realm data (96 bits).
+    pub realm_data: Option<RawBits>,
+    /// Base defense for armor bases.
+    pub defense: Option<i32>,
+    /// Durability for armor and weapon bases.
+    pub durability: Option<Durability>,
+    /// Stack quantity for stackable bases.
+    pub quantity: Option<u16>,
+    /// Total socket count for socketed items.
+    pub total_sockets: Option<u8>,
+    /// Active set bonus lists (5-bit mask) for set items.
+    pub set_list_mask: Option<u8>,
+    /// Flag bit added after the fixed fields in the v105 layout. Meaning unknown.
+    #[serde(default)]
+    pub v105_flag: bool,
+    /// Magic properties.
+    pub properties: PropertyList,
+    /// Set bonus property lists, one per bit set in `set_list_mask`.
+    pub set_properties: Vec<PropertyList>,
+    /// Runeword properties.
+    pub runeword_properties: Option<PropertyList>,
+    /// Flag bit added after the property lists in the v105 layout. Meaning unknown.
+    #[serde(default)]
+    pub v105_trailing_flag: bool,
+}
+
+/// One item, including the items socketed into it.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Item {
+    pub flags: ItemFlags,
+    /// Item format version: a 3-bit field in D2R saves, a 10-bit decimal in v96 and older.
+    ///
+    /// The two spellings are converted on encode (`0b101` <-> `101`).
+    pub version: u8,
+    pub mode: ItemMode,
+    /// Body slot for equipped items (4 bits).
+    pub equip_slot: u8,
+    /// Grid column, or belt slot for belt items.
+    pub x: u8,
+    /// Grid row.
+    pub y: u8,
+    pub storage: ItemStorage,
+    /// Item type code (`"hp1"`, `"cm3"`, ...). Empty for ears.
+    pub code: String,
+    /// Ear data, present instead of `code` when this item is an ear.
+    pub ear: Option<Ear>,
+    /// Flag bit added after the socket count of simple items in the v105 layout.
+    #[serde(default)]
+    pub v105_flag: bool,
+    /// Extended block. `None` for simple items.
+    pub extended: Option<ExtendedItem>,
+    /// Items inserted into this item's sockets, in socket order.
+    pub socketed_items: Vec<Item>,
+}
+
+fn has_v105_fields(format: FormatId) -> bool {
+    format == FormatId::V105
+}
+
+/// Pre-D2R items start with their own `JM` marker and store codes and names as plain characters.
+fn has_classic_fields(format: FormatId) -> bool {
+    format.edition() == Some(GameEdition::Classic)
+}
+
+/// Width of the socketed item count. D2R simple items store it in a single bit; 1.10 items
+/// always use three.
+fn socketed_count_bits(format: FormatId, simple: bool) -> usize {
+    if simple && !has_classic_fields(format) {
+        1
+    } else {
+        3
+    }
+}
+
+/// Built-in stat widths for the item layout of `format`.
+pub(crate) fn default_stat_costs(format: FormatId) -> &'static ItemStatCost {
+    if has_classic_fields(format) {
+        ItemStatCost::classic()
+    } else {
+        ItemStatCost::d2r()
+    }
+}
+
+fn name_character_bits(format: FormatId) -> usize {
+    if has_classic_fields(format) {
+        CLASSIC_NAME_CHARACTER_BITS
+    } else {
+        NAME_CHARACTER_BITS
+    }
+}
+
+/// Convert an item version to the spelling used by `format`.
+///
+/// Classic saves write the version as a decimal number whose digits D2R keeps as bits.
+fn version_for_format(version: u8, format: FormatId) -> Result<u8, ParseHardError> {
+    let converted = if has_classic_fields(format) {
+        if version > 0b111 {
+            return Ok(version);
+        }
+        format!("{version:b}").parse::<u8>().ok()
+    } else {
+        if version <= 0b111 {
+            return Ok(version);
+        }
+        u8::from_str_radix(&version.to_string(), 2).ok().filter(|version| *version <= 0b111)
+    };
+    converted.ok_or_else(|| ParseHardError {
+        message: format!("Item version {version} cannot be written for {format:?}."),
+    })
+}
+
+fn read_classic_item_code(
+    byte_slice: &[u8],
+    byte_position: &mut BytePosition,
+) -> Result<String, ParseHardError> {
+    let mut code = String::with_capacity(CLASSIC_ITEM_CODE_LENGTH);
+    for _ in 0..CLASSIC_ITEM_CODE_LENGTH {
+        code.push(char::from(read_u8(byte_slice, byte_position, 8)?));
+    }
+    Ok(code.trim_end_matches(' ').to_string())
+}
+
+fn write_classic_item_code(
+    byte_vector: &mut Vec<u8>,
+    byte_position: &mut BytePosition,
+    code: &str,
+) -> Result<(), ParseHardError> {
+    if code.len() > CLASSIC_ITEM_CODE_LENGTH || !code.is_ascii() {
+        return Err(ParseHardError {
+            message: format!(
+                "Item code '{code}' must be at most {CLASSIC_ITEM_CODE_LENGTH} ASCII characters."
+            ),
+        });
+    }
+    for byte in format!("{code:<CLASSIC_ITEM_CODE_LENGTH$}").bytes() {
+        write_bits(byte_vector, byte_position, byte, 8)?;
+    }
+    Ok(())
+}
+
+fn read_u8(
+    byte_slice: &[u8],
+    byte_position: &mut BytePosition,
+    bits_count: usize,
+) -> Result<u8, ParseHardError> {
+    Ok(read_bits(byte_slice, byte_position, bits_count)? as u8)
+}
+
+fn read_u16(
+    byte_slice: &[u8],
+    byte_position: &mut BytePosition,
+    bits_count: usize,
+) -> Result<u16, ParseHardError> {
+    Ok(read_bits(byte_slice, byte_position, bits_count)? as u16)
+}
+
+fn read_flag(byte_slice: &[u8], byte_position: &mut BytePosition) -> Result<bool, ParseHardError> {
+    Ok(read_bits(byte_slice, byte_position, 1)? == 1)
+}
+
+fn read_name(
+    byte_slice: &[u8],
+    byte_position: &mut BytePosition,
+    character_bits: usize,
+) -> Result<String, ParseHardError> {
+    let mut name_bytes = Vec::with_capacity(NAME_MAX_CHARACTERS);
+    loop {
+        let character = read_u8(byte_slice, byte_position, character_bits)?;
+        if character == 0 {
+            break;
+        }
+        if name_bytes.len() == NAME_MAX_CHARACTERS {
+            return Err(ParseHardError {
+                message: format!("Item name exceeds {NAME_MAX_CHARACTERS} characters."),
+            });
+        }
+        name_bytes.push(character);
+    }
+    String::from_utf8(name_bytes).map_err(|error| ParseHardError {
+        message: format!("Item name is not valid UTF-8: {error}."),
+    })
+}
+
+fn write_name(
+    byte_vector: &mut Vec<u8>,
+    byte_position: &mut BytePosition,
+    name: &str,
+    character_bits: usize,
+) -> Result<(), ParseHardError> {
+    if name.len() > NAME_MAX_CHARACTERS
+        || name.bytes().any(|byte| byte == 0 || u32::from(byte) >= 1 << character_bits)
+    {
+        return Err(ParseHardError {
+            message: format!(
+                "Item name '{name}' must be at most {NAME_MAX_CHARACTERS} {character_bits}-bit characters without NUL."
+            ),
+        });
+    }
+    for byte in name.bytes() {
+        write_bits(byte_vector, byte_position, byte, character_bits)?;
+    }
+    write_bits(byte_vector, byte_position, 0u8, character_bits)
+}
+
+fn write_flag(
+    byte_vector: &mut Vec<u8>,
+    byte_position: &mut BytePosition,
+    flag: bool,
+) -> Result<(), ParseHardError> {
+    write_bits(byte_vector, byte_position, u8::from(flag), 1)
+}
+
+fn write_optional<T: Into<u32> + Copy>(
+    byte_vector: &mut Vec<u8>,
+    byte_position: &mut BytePosition,
+    value: Option<T>,
+    bits_count: usize,
+) -> Result<(), ParseHardError> {
+    write_flag(byte_vector, byte_position, value.is_some())?;
+    if let Some(value) = value {
+        write_bits(byte_vector, byte_position, value, bits_count)?;
+    }
+    Ok(())
+}
+
+fn align_to_byte(byte_position: &mut BytePosition) {
+    byte_position.current_byte = byte_position.next_byte_offset();
+    byte_position.current_bit = 0;
+}
+
+impl QualityData {
+    fn parse(
+        quality: ItemQuality,
+        byte_slice: &[u8],
+        byte_position: &mut BytePosition,
+    ) -> Result<QualityData, ParseHardError> {
+        Ok(match quality {
+            ItemQuality::Normal => QualityData::None,
+            ItemQuality::Inferior | ItemQuality::Superior => {
+                QualityData::LowOrHigh(read_u8(byte_slice, byte_position, 3)?)
+            }
+            ItemQuality::Magic => QualityData::Magic {
+                prefix: read_u16(byte_slice, byte_position, 11)?,
+                suffix: read_u16(byte_slice, byte_position, 11)?,
+            },
+            ItemQuality::Rare | ItemQuality::Crafted => {
+                let first_name = read_u8(byte_slice, byte_position, 8)?;
+                let second_name = read_u8(byte_slice, byte_position, 8)?;
+                let mut prefixes = [None; 3];
+                let mut suffixes = [None; 3];
+                for index in 0..3 {
+                    if read_flag(byte_slice, byte_position)? {
+                        prefixes[index] = Some(read_u16(byte_slice, byte_position, 11)?);
+                    }
+                    if read_flag(byte_slice, byte_position)? {
+                        suffixes[index] = Some(read_u16(byte_slice, byte_position, 11)?);
+                    }
+                }
+                QualityData::Rare { first_name, second_name, prefixes, suffixes }
+            }
+            ItemQuality::Set | ItemQuality::Unique => {
+                QualityData::SetOrUnique(read_u16(byte_slice, byte_position, 12)?)
+            }
+            ItemQuality::Unknown(quality_id) => {
+                return Err(ParseHardError {
+                    message: format!("Unknown item quality id {quality_id}."),
+                });
+            }
+        })
+    }
+
+    fn write(
+        &self,
+        byte_vector: &mut Vec<u8>,
+        byte_position: &mut BytePosition,
+    ) -> Result<(), ParseHardError> {
+        match self {
+            QualityData::None => {}
+            QualityData::LowOrHigh(sub_type) => {
+                write_bits(byte_vector, byte_position, *sub_type, 3)?;
+            }
+            QualityData::Magic { prefix, suffix } => {
+                write_bits(byte_vector, byte_position, *prefix, 11)?;
+                write_bits(byte_vector, byte_position, *suffix, 11)?;
+            }
+            QualityData::Rare { first_name, second_name, prefixes, suffixes } => {
+                write_bits(byte_vector, byte_position, *first_name, 8)?;
+                write_bits(byte_vector, byte_position, *second_name, 8)?;
+                for index in 0..3 {
+                    write_optional(byte_vector, byte_position, prefixes[index], 11)?;
+                    write_optional(byte_vector, byte_position, suffixes[index], 11)?;
+                }
+            }
+            QualityData::SetOrUnique(row_id) => {
+                write_bits(byte_vector, byte_position, *row_id, 12)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn matches_quality(&self, quality: ItemQuality) -> bool {
+        matches!(
+            (self, quality),
+            (QualityData::None, ItemQuality::Normal)
+                | (QualityData::LowOrHigh(_), ItemQuality::Inferior | ItemQuality::Superior)
+                | (QualityData::Magic { .. }, ItemQuality::Magic)
+                | (QualityData::Rare { .. }, ItemQuality::Rare | ItemQuality::Crafted)
+                | (QualityData::SetOrUnique(_), ItemQuality::Set | ItemQuality::Unique)
+        )
+    }
+}
+
+impl ExtendedItem {
+    fn parse(
+        flags: ItemFlags,
+        code: &str,
+        byte_slice: &[u8],
+        byte_position: &mut BytePosition,
+        format: FormatId,
+        stat_costs: &ItemStatCost,
+    ) -> Result<ExtendedItem, ParseHardError> {
+        let id = read_bits(byte_slice, byte_position, 32)?;
+        let item_level = read_u8(byte_slice, byte_position, 7)?;
+        let quality = ItemQuality::from_id(read_u8(byte_slice, byte_position, 4)?);
+        let graphic = if read_flag(byte_slice, byte_position)? {
+            Some(read_u8(byte_slice, byte_position, 3)?)
+        } else {
+            None
+        };
+        let class_affix = if read_flag(byte_slice, byte_position)? {
+            Some(read_u16(byte_slice, byte_position, 11)?)
+        } else {
+            None
+        };
+        let quality_data = QualityData::parse(quality, byte_slice, byte_position)?;
+        let runeword = if flags.is_runeword() {
+            Some(Runeword {
+                id: read_u16(byte_slice, byte_position, 12)?,
+                unknown: read_u8(byte_slice, byte_position, 4)?,
+            })
+        } else {
+            None
+        };
+        let personalized_name = if flags.is_personalized() {
+            Some(read_name(byte_slice, byte_position, name_character_bits(format))?)
+        } else {
+            None
+        };
+        let tome_data =
+            if is_tome(code) { Some(read_u8(byte_slice, byte_position, 5)?) } else { None };
+        let realm_data = if read_flag(byte_slice, byte_position)? {
+            Some(RawBits::read(byte_slice, byte_position, REALM_DATA_BITS)?)
+        } else {
+            None
+        };
+
+        let kind = base_kind(code);
+        let defense = if kind == BaseKind::Armor {
+            Some(read_bits(byte_slice, byte_position, DEFENSE_BITS)? as i32 - DEFENSE_SAVE_ADD)
+        } else {
+            None
+        };
+        let durability = if matches!(kind, BaseKind::Armor | BaseKind::Weapon) {
+            let max = read_u8(byte_slice, byte_position, MAX_DURABILITY_BITS)?;
+            let current =
+                if max > 0 { read_u16(byte_slice, byte_position, DURABILITY_BITS)? } else { 0 };
+            Some(Durability { max, current })
+        } else {
+            None
+        };
+        let quantity = if is_stackable(code) {
+            Some(read_u16(byte_slice, byte_position, QUANTITY_BITS)?)
+        } else {
+            None
+        };
+        let total_sockets = if flags.is_socketed() {
+            Some(read_u8(byte_slice, byte_position, TOTAL_SOCKETS_BITS)?)
+        } else {
+            None
+        };
+        let v105_flag = has_v105_fields(format) && read_flag(byte_slice, byte_position)?;
+        let set_list_mask = if quality == ItemQuality::Set {
+            Some(read_u8(byte_slice, byte_position, SET_LIST_MASK_BITS)?)
+        } else {
+            None
+        };
+
+        let properties = PropertyList::parse(byte_slice, byte_position, stat_costs)?;
+        let mut set_properties = Vec::new();
+        for list_index in 0..SET_LIST_MASK_BITS {
+            if set_list_mask.is_some_and(|mask| mask & (1 << list_index) != 0) {
+                set_properties.push(PropertyList::parse(byte_slice, byte_position, stat_costs)?);
+            }
+        }
+        let runeword_properties = if runeword.is_some() {
+            Some(PropertyList::parse(byte_slice, byte_position, stat_costs)?)
+        } else {
+            None
+        };
+        let v105_trailing_flag = has_v105_fields(format) && read_flag(byte_slice, byte_position)?;
+
+        Ok(ExtendedItem {
+            id,
+            item_level,
+            quality,
+            graphic,
+            class_affix,
+            quality_data,
+            runeword,
+            personalized_name,
+            tome_data,
+            realm_data,
+            defense,
+            durability,
+            quantity,
+            total_sockets,
+            set_list_mask,
+            v105_flag,
+            properties,
+            set_properties,
+            runeword_properties,
+            v105_trailing_flag,
+        })
+    }
+
+    fn write(
+        &self,
+        byte_vector: &mut Vec<u8>,
+        byte_position: &mut BytePosition,
+        format: FormatId,
+        stat_costs: &ItemStatCost,
+    ) -> Result<(), ParseHardError> {
+        if !self.quality_data.matches_quality(self.quality) {
+            return Err(ParseHardError {
+                message: format!(
+                    "Item quality {:?} does not match its quality data {:?}.",
+                    self.quality, self.quality_data
+                ),
+            });
+        }
+        let expected_set_lists = self.set_list_mask.map_or(0, |mask| mask.count_ones() as usize);
+        if self.set_properties.len() != expected_set_lists
+            || self.set_list_mask.is_some() != (self.quality == ItemQuality::Set)
+        {
+            return Err(ParseHardError {
+                message: format!(
+                    "Set item data is inconsistent: mask {:?} with {} set property lists.",
+                    self.set_list_mask,
+                    self.set_properties.len()
+                ),
+            });
+        }
+        if self.runeword.is_some() != self.runeword_properties.is_some() {
+            return Err(ParseHardError {
+                message: "Runeword header and runeword property list must be set together."
+                    .to_string(),
+            });
+        }
+
+        write_bits(byte_vector, byte_position, self.id, 32)?;
+        write_bits(byte_vector, byte_position, self.item_level, 7)?;
+        write_bits(byte_vector, byte_position, self.quality.id(), 4)?;
+        write_optional(byte_vector, byte_position, self.graphic, 3)?;
+        write_optional(byte_vector, byte_position, self.class_affix, 11)?;
+        self.quality_data.write(byte_vector, byte_position)?;
+        if let Some(runeword) = self.runeword {
+            write_bits(byte_vector, byte_position, runeword.id, 12)?;
+            write_bits(byte_vector, byte_position, runeword.unknown, 4)?;
+        }
+        if let Some(name) = &self.personalized_name {
+            write_name(byte_vector, byte_position, name, name_character_bits(format))?;
+        }
+        if let Some(tome_data) = self.tome_data {
+            write_bits(byte_vector, byte_position, tome_data, 5)?;
+        }
+        write_flag(byte_vector, byte_position, self.realm_data.is_some())?;
+        if let Some(realm_data) = &self.realm_data {
+            realm_data.write(byte_vector, byte_position)?;
+        }
+        if let Some(defense) = self.defense {
+            let stored_defense = u32::try_from(defense + DEFENSE_SAVE_ADD).map_err(|_| {
+                ParseHardError { message: format!("Item defense {defense} is out of range.") }
+            })?;
+            write_bits(byte_vector, byte_position, stored_defense, DEFENSE_BITS)?;
+        }
+        if let Some(durability) = self.durability {
+            write_bits(byte_vector, byte_position, durability.max, MAX_DURABILITY_BITS)?;
+            if durability.max > 0 {
+                write_bits(byte_vector, byte_position, durability.current, DURABILITY_BITS)?;
+            }
+        }
+        if let Some(quantity) = self.quantity {
+            write_bits(byte_vector, byte_position, quantity, QUANTITY_BITS)?;
+        }
+        if let Some(total_sockets) = self.total_sockets {
+            write_bits(byte_vector, byte_position, total_sockets, TOTAL_SOCKETS_BITS)?;
+        }
+        if has_v105_fields(format) {
+            write_flag(byte_vector, byte_position, self.v105_flag)?;
+        }
+        if let Some(set_list_mask) = self.set_list_mask {
+            write_bits(byte_vector, byte_position, set_list_mask, SET_LIST_MASK_BITS)?;
+        }
+
+        self.properties.write(byte_vector, byte_position, stat_costs)?;
+        for set_list in &self.set_properties {
+            set_list.write(byte_vector, byte_position, stat_costs)?;
+        }
+        if let Some(runeword_list) = &self.runeword_properties {
+            runeword_list.write(byte_vector, byte_position, stat_costs)?;
+        }
+        if has_v105_fields(format) {
+            write_flag(byte_vector, byte_position, self.v105_trailing_flag)?;
+        }
+        Ok(())
+    }
+}
+
+impl Item {
+    /// Parse one byte-aligned item and the items socketed into it.
+    ///
+    /// On success `byte_position` is left at the byte-aligned end of the last item read.
+    pub fn parse(
+        byte_slice: &[u8],
+        byte_position: &mut BytePosition,
+        format: FormatId,
+    ) -> Result<Item, ParseHardError> {
+        Item::parse_with(byte_slice, byte_position, format, default_stat_costs(format))
+    }
+
+    /// [`Item::parse`] with property widths from `stat_costs`, for example a mod's
+    /// [`crate::data::GameData::item_stat_cost`].
+    pub fn parse_with(
+        byte_slice: &[u8],
+        byte_position: &mut BytePosition,
+        format: FormatId,
+        stat_costs: &ItemStatCost,
+    ) -> Result<Item, ParseHardError> {
+        let classic = has_classic_fields(format);
+        let item_marker = u32::from(u16::from_le_bytes(ITEM_LIST_HEADER));
+        if classic && read_bits(byte_slice, byte_position, 16)? != item_marker {
+            return Err(ParseHardError {
+                message: "Item does not start with the 'JM' marker.".to_string(),
+            });
+        }
+        let flags = ItemFlags::from_bits(read_bits(byte_slice, byte_position, 32)?);
+        let version_bits = if classic { CLASSIC_VERSION_BITS } else { VERSION_BITS };
+        let version = read_bits(byte_slice, byte_position, version_bits)?;
+        let version = u8::try_from(version).map_err(|_| ParseHardError {
+            message: format!("Item version {version} is out of range."),
+        })?;
+        let mode = ItemMode::from_id(read_u8(byte_slice, byte_position, 3)?);
+        let equip_slot = read_u8(byte_slice, byte_position, 4)?;
+        let x = read_u8(byte_slice, byte_position, 4)?;
+        let y = read_u8(byte_slice, byte_position, 4)?;
+        let storage = ItemStorage::from_id(read_u8(byte_slice, byte_position, 3)?);
+
+        let mut item = Item {
+            flags,
+            version,
+            mode,
+            equip_slot,
+            x,
+            y,
+            storage,
+            code: String::new(),
+            ear: None,
+            v105_flag: false,
+            extended: None,
+            socketed_items: Vec::new(),
+        };
+
+        let mut socketed_count = 0;
+        if flags.is_ear() {
+            item.ear = Some(Ear {
+                class_id: read_u8(byte_slice, byte_position, 3)?,
+                level: read_u8(byte_slice, byte_position, 7)?,
+                name: read_name(byte_slice, byte_position, name_character_bits(format))?,
+            });
+        } else {
+            item.code = if classic {
+                read_classic_item_code(byte_slice, byte_position)?
+            } else {
+                read_item_code(byte_slice, byte_position)?
+            };
+            socketed_count =
+                read_u8(byte_slice, byte_position, socketed_count_bits(format, flags.is_simple()))?;
+        }
+
+        if flags.is_simple() {
+            item.v105_flag = has_v105_fields(format) && read_flag(byte_slice, byte_position)?;
+        } else {
+            item.extended = Some(ExtendedItem::parse(
+                flags,
+                &item.code,
+                byte_slice,
+                byte_position,
+                format,
+                stat_costs,
+            )?);
+        }
+        align_to_byte(byte_position);
+
+        for _ in 0..socketed_count {
+            item.socketed_items.push(Item::parse_with(
+                byte_slice,
+                byte_position,
+                format,
+                stat_costs,
+            )?);
+        }
+        Ok(item)
+    }
+
+    /// Append this item and its socketed items to `byte_vector`.
+    pub fn write(&self, byte_vector: &mut Vec<u8>, format: FormatId) -> Result<(), ParseHardError> {
+        self.write_with(byte_vector, format, default_stat_costs(format))
+    }
+
+    /// [`Item::write`] with property widths from `stat_costs`.
+    pub fn write_with(
+        &self,
+        byte_vector: &mut Vec<u8>,
+        format: FormatId,
+        stat_costs: &ItemStatCost,
+    ) -> Result<(), ParseHardError> {
+        let mut byte_position = BytePosition { current_byte: byte_vector.len(), current_bit: 0 };
+        let mut flags = self.flags;
+        flags.set(FLAG_SIMPLE, self.extended.is_none());
+        flags.set(FLAG_EAR, self.ear.is_some());
+        if let Some(extended) = &self.extended {
+            flags.set(FLAG_SOCKETED, extended.total_sockets.is_some());
+            flags.set(FLAG_PERSONALIZED, extended.personalized_name.is_some());
+            flags.set(FLAG_RUNEWORD, extended.runeword.is_some());
+        }
+
+        let socketed_count = self.socketed_items.len();
+        let socketed_count_bits = socketed_count_bits(format, self.extended.is_none());
+        if self.ear.is_some() && socketed_count > 0 || socketed_count >= 1 << socketed_count_bits {
+            return Err(ParseHardError {
+                message: format!(
+                    "Item '{}' cannot store {socketed_count} socketed items.",
+                    self.code
+                ),
+            });
+        }
+
+        let classic = has_classic_fields(format);
+        if classic {
+            byte_vector.extend_from_slice(&ITEM_LIST_HEADER);
+            byte_position.current_byte += ITEM_LIST_HEADER.len();
+        }
+        let version_bits = if classic { CLASSIC_VERSION_BITS } else { VERSION_BITS };
+        write_bits(byte_vector, &mut byte_position, flags.bits(), 32)?;
+        write_bits(
+            byte_vector,
+            &mut byte_position,
+            version_for_format(self.version, format)?,
+            version_bits,
+        )?;
+        write_bits(byte_vector, &mut byte_position, self.mode.id(), 3)?;
+        write_bits(byte_vector, &mut byte_position, self.equip_slot, 4)?;
+        write_bits(byte_vector, &mut byte_position, self.x, 4)?;
+        write_bits(byte_vector, &mut byte_position, self.y, 4)?;
+        write_bits(byte_vector, &mut byte_position, self.storage.id(), 3)?;
+
+        if let Some(ear) = &self.ear {
+            write_bits(byte_vector, &mut byte_position, ear.class_id, 3)?;
+            write_bits(byte_vector, &mut byte_position, ear.level, 7)?;
+            write_name(byte_vector, &mut byte_position, &ear.name, name_character_bits(format))?;
+        } else {
+            if classic {
+                write_classic_item_code(byte_vector, &mut byte_position, &self.code)?;
+            } else {
+                write_item_code(byte_vector, &mut byte_position, &self.code)?;
+            }
+            write_bits(
+                byte_vector,
+                &mut byte_position,
+                socketed_count as u32,
+                socketed_count_bits,
+            )?;
+        }
+
+        match &self.extended {
+            None => {
+                if has_v105_fields(format) {
+                    write_flag(byte_vector, &mut byte_position, self.v105_flag)?;
+                }
+            }
+            Some(extended) => {
+                extended.write(byte_vector, &mut byte_position, format, stat_costs)?
+            }
+        }
+
+        for socketed_item in &self.socketed_items {
+            socketed_item.write_with(byte_vector, format, stat_costs)?;
+        }
+        Ok(())
+    }
+}
@@ -1,92 +1,414 @@
-//! Items section placeholder support.
+//! Items section model.
 //!
-//! Item payload is preserved as raw bytes.
-//! If raw bytes are empty, encoding emits a known empty-inventory trailer for the target layout.
+//! Player, corpse, mercenary and iron golem items are decoded into typed [`Item`]
+//! values. Magic properties are decoded through an [`ItemStatCost`] table.
+//!
+//! If the section cannot be decoded, it is preserved as raw bytes and written back
+//! unchanged.
 
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, Bytes};
-
-const V99_EMPTY_ITEMS_CLASSIC: [u8; 4] = [0x4A, 0x4D, 0x00, 0x00];
-const V99_EMPTY_ITEMS_EXPANSION: [u8; 13] =
-    [0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x6B, 0x66, 0x00];
-const V99_EMPTY_ITEMS_EXPANSION_MERC: [u8; 17] = [
-    0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x4A, 0x4D, 0x00, 0x00, 0x6B, 0x66,
-    0x00,
-];
-
-const V105_EMPTY_ITEMS_CLASSIC: [u8; 4] = [0x4A, 0x4D, 0x00, 0x00];
-const V105_EMPTY_ITEMS_EXPANSION: [u8; 13] =
-    [0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x6B, 0x66, 0x00];
-const V105_EMPTY_ITEMS_EXPANSION_MERC: [u8; 17] = [
-    0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x4A, 0x4D, 0x00, 0x00, 0x6B, 0x66,
-    0x00,
-];
-const V105_EMPTY_ITEMS_ROTW: [u8; 19] = [
-    0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x6B, 0x66, 0x00, 0x01, 0x00, 0x6C,
-    0x66, 0x00, 0x00,
-];
-
-const V105_EMPTY_ITEMS_ROTW_MERC: [u8; 23] = [
-    0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x4A, 0x4D, 0x00, 0x00, 0x6B, 0x66,
-    0x00, 0x01, 0x00, 0x6C, 0x66, 0x00, 0x00,
-];
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum EmptyLayout {
-    /// Legacy D2R classic empty-item trailer.
-    LegacyClassic,
-    /// Legacy D2R expansion empty-item trailer.
-    LegacyExpansion,
-    /// V105 classic empty-item trailer.
-    V105Classic,
-    /// V105 expansion empty-item trailer.
-    V105Expansion,
-    /// V105 RotW empty-item trailer.
-    V105RotW,
-}
-
-/// Raw items-section payload placeholder.
+use serde_with::serde_as;
+
+use crate::data::GameData;
+use crate::format::FormatId;
+use crate::utils::{read_bits, u16_from, u32_from, u8_from, write_bits, BytePosition, HexBytes};
+use crate::{ExpansionType, ParseHardError};
+
+mod base;
+mod huffman;
+mod item;
+mod properties;
+#[cfg(test)]
+mod tests;
+
+pub(crate) use item::default_stat_costs;
+pub use item::{
+    Durability, Ear, ExtendedItem, Item, ItemFlags, ItemMode, ItemQuality, ItemStorage,
+    QualityData, Runeword,
+};
+pub use properties::{
+    ItemStatCost, Property, PropertyList, StatCost, PROPERTY_LIST_TERMINATOR, STAT_ID_BITS,
+};
+
+/// Item list header marker (`"JM"`).
+pub const ITEM_LIST_HEADER: [u8; 2] = [0x4A, 0x4D];
+const ITEM_LIST_HEADER_LENGTH: usize = 4;
+/// Mercenary subsection marker (`"jf"`), present in expansion saves.
+const MERCENARY_HEADER: [u8; 2] = [0x6A, 0x66];
+/// Iron golem subsection marker (`"kf"`), present in expansion saves.
+const IRON_GOLEM_HEADER: [u8; 2] = [0x6B, 0x66];
+const CORPSE_HEADER_LENGTH: usize = 12;
+/// RotW subsection written by the game when it holds no data.
+const EMPTY_ROTW_SECTION: [u8; 6] = [0x01, 0x00, 0x6C, 0x66, 0x00, 0x00];
+
+/// Bit-exact copy of a field this library does not interpret.
 #[serde_as]
 #[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
-pub struct Placeholder {
-    #[serde_as(as = "Bytes")]
-    data: Vec<u8>,
+pub struct RawBits {
+    #[serde_as(as = "HexBytes")]
+    bytes: Vec<u8>,
+    bit_length: usize,
+}
+
+impl RawBits {
+    /// Number of meaningful bits in this range.
+    pub fn bit_length(&self) -> usize {
+        self.bit_length
+    }
+
+    pub(crate) fn read(
+        byte_slice: &[u8],
+        byte_position: &mut BytePosition,
+        bit_length: usize,
+    ) -> Result<RawBits, ParseHardError> {
+        let mut bytes = Vec::with_capacity(bit_length.div_ceil(8));
+        let mut bits_left = bit_length;
+        while bits_left > 0 {
+            let chunk_bits = bits_left.min(8);
+            bytes.push(read_bits(byte_slice, byte_position, chunk_bits)? as u8);
+            bits_left -= chunk_bits;
+        }
+        Ok(RawBits { bytes, bit_length })
+    }
+
+    pub(crate) fn write(
+        &self,
+        byte_vector: &mut Vec<u8>,
+        byte_position: &mut BytePosition,
+    ) -> Result<(), ParseHardError> {
+        if self.bytes.len() != self.bit_length.div_ceil(8) {
+            return Err(ParseHardError {
+                message: format!(
+                    "Raw bit range holds {} bytes for {} bits.",
+                    self.bytes.len(),
+                    self.bit_length
+                ),
+            });
+        }
+        let mut bits_left = self.bit_length;
+        for byte in &self.bytes {
+            let chunk_bits = bits_left.min(8);
+            write_bits(byte_vector, byte_position, *byte, chunk_bits)?;
+            bits_left -= chunk_bits;
+        }
+        Ok(())
+    }
+}
+
+/// A `JM`-prefixed list of top-level items.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ItemList {
+    /// Top-level items. Socketed items are stored on their parent.
+    pub items: Vec<Item>,
+}
+
+impl ItemList {
+    /// Parse a `JM` item list and return it with the number of bytes consumed.
+    pub fn parse(byte_slice: &[u8], format: FormatId) -> Result<(ItemList, usize), ParseHardError> {
+        ItemList::parse_with(byte_slice, format, default_stat_costs(format))
+    }
+
+    /// [`ItemList::parse`] with property widths from `stat_costs`.
+    pub fn parse_with(
+        byte_slice: &[u8],
+        format: FormatId,
+        stat_costs: &ItemStatCost,
+    ) -> Result<(ItemList, usize), ParseHardError> {
+        if byte_slice.len() < ITEM_LIST_HEADER_LENGTH || byte_slice[0..2] != ITEM_LIST_HEADER {
+            return Err(ParseHardError {
+                message: "Item list does not start with the 'JM' header.".to_string(),
+            });
+        }
+        let item_count = u16::from_le_bytes([byte_slice[2], byte_slice[3]]);
+
+        let mut byte_position =
+            BytePosition { current_byte: ITEM_LIST_HEADER_LENGTH, current_bit: 0 };
+        let mut items = Vec::with_capacity(usize::from(item_count));
+        for item_index in 0..item_count {
+            let item = Item::parse_with(byte_slice, &mut byte_position, format, stat_costs)
+                .map_err(|error| ParseHardError {
+                    message: format!(
+                        "Item {item_index} at byte {}: {error}",
+                        byte_position.current_byte
+                    ),
+                })?;
+            items.push(item);
+        }
+        Ok((ItemList { items }, byte_position.next_byte_offset()))
+    }
+
+    /// Serialize this list, including its `JM` header.
+    pub fn to_bytes(&self, format: FormatId) -> Result<Vec<u8>, ParseHardError> {
+        self.to_bytes_with(format, default_stat_costs(format))
+    }
+
+    /// [`ItemList::to_bytes`] with property widths from `stat_costs`.
+    pub fn to_bytes_with(
+        &self,
+        format: FormatId,
+        stat_costs: &ItemStatCost,
+    ) -> Result<Vec<u8>, ParseHardError> {
+        let item_count = u16::try_from(self.items.len()).map_err(|_| ParseHardError {
+            message: format!(
+                "Item list holds {} items, more than a save can store.",
+                self.items.len()
+            ),
+        })?;
+        let mut byte_vector = Vec::with_capacity(ITEM_LIST_HEADER_LENGTH);
+        byte_vector.extend_from_slice(&ITEM_LIST_HEADER);
+        byte_vector.extend_from_slice(&item_count.to_le_bytes());
+        for item in &self.items {
+            item.write_with(&mut byte_vector, format, stat_costs)?;
+        }
+        Ok(byte_vector)
+    }
+}
+
+/// A dead player's corpse and the items left on it.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Corpse {
+    /// Leading 4-byte field, meaning unknown.
+    pub unknown: u32,
+    pub x: u32,
+    pub y: u32,
+    pub items: ItemList,
+}
+
+/// Items section.
+///
+/// Subsections follow the save layout: player items, corpses, then (expansion only)
+/// mercenary items and the iron golem, then (RotW only) an extra subsection.
+#[serde_as]
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Items {
+    /// Items carried by the player (inventory, equipment, belt, stash, cube).
+    pub player: ItemList,
+    pub corpses: Vec<Corpse>,
+    /// Items equipped by the mercenary. Only written when a mercenary is hired.
+    pub mercenary: ItemList,
+    /// Item the iron golem was made from, if the player has one.
+    pub iron_golem: Option<Item>,
+    /// Raw RotW subsection bytes. Empty means the game's empty subsection is written.
+    #[serde_as(as = "HexBytes")]
+    #[serde(default)]
+    rotw_section: Vec<u8>,
+    /// Whole-section bytes kept when the section could not be decoded.
+    #[serde_as(as = "HexBytes")]
     #[serde(default)]
-    original_mercenary_hired: bool,
+    raw: Vec<u8>,
 }
 
-/// Store item bytes without decoding.
-pub fn parse(byte_vector: &[u8], mercenary_hired: bool) -> Placeholder {
-    Placeholder { data: byte_vector.to_vec(), original_mercenary_hired: mercenary_hired }
+impl Items {
+    /// Whether this section was preserved as raw bytes because it could not be decoded.
+    pub fn is_raw(&self) -> bool {
+        !self.raw.is_empty()
+    }
+
+    pub(crate) fn rotw_section(&self) -> &[u8] {
+        &self.rotw_section
+    }
+
+    pub(crate) fn raw(&self) -> &[u8] {
+        &self.raw
+    }
+}
+
+fn unexpected_bytes(offset: usize, expected: &str) -> ParseHardError {
+    ParseHardError { message: format!("Expected {expected} at items section byte {offset}.") }
+}
+
+/// Property widths for items of `format`: the built-in table, with the stats of
+/// `game_data` replacing or adding to it.
+pub(crate) fn stat_costs_for(format: FormatId, game_data: Option<&GameData>) -> ItemStatCost {
+    let defaults = default_stat_costs(format);
+    match game_data {
+        Some(game_data) => {
+            ItemStatCost::new(defaults.iter().chain(game_data.item_stat_cost().iter()).cloned())
+        }
+        None => defaults.clone(),
+    }
+}
+
+/// Parse one item list and check that it re-encodes to its source bytes.
+pub(crate) fn parse_verified_list(
+    byte_slice: &[u8],
+    offset: usize,
+    format: FormatId,
+    stat_costs: &ItemStatCost,
+    list_name: &str,
+) -> Result<(ItemList, usize), ParseHardError> {
+    let list_bytes = &byte_slice[offset..];
+    let (item_list, consumed) =
+        ItemList::parse_with(list_bytes, format, stat_costs).map_err(|error| ParseHardError {
+            message: format!("{list_name} item list at byte {offset}: {error}"),
+        })?;
+    if item_list.to_bytes_with(format, stat_costs)? != list_bytes[..consumed] {
+        return Err(ParseHardError {
+            message: format!(
+                "Decoded {list_name} item list does not re-encode to its source bytes."
+            ),
+        });
+    }
+    Ok((item_list, offset + consumed))
 }
 
-impl Placeholder {
-    pub(crate) fn mercenary_hire_state_changed(&self, mercenary_hired: bool) -> bool {
-        self.original_mercenary_hired != mercenary_hired
+fn parse_corpses(
+    byte_slice: &[u8],
+    mut offset: usize,
+    format: FormatId,
+    stat_costs: &ItemStatCost,
+) -> Result<(Vec<Corpse>, usize), ParseHardError> {
+    if !byte_slice[offset..].starts_with(&ITEM_LIST_HEADER)
+        || byte_slice.len() < offset + ITEM_LIST_HEADER_LENGTH
+    {
+        return Err(unexpected_bytes(offset, "corpse 'JM' header"));
     }
+    let corpse_count = u16_from(&byte_slice[offset + 2..offset + 4], "corpse count")?;
+    offset += ITEM_LIST_HEADER_LENGTH;
+
+    let mut corpses = Vec::with_capacity(usize::from(corpse_count));
+    for _ in 0..corpse_count {
+        let header = byte_slice
+            .get(offset..offset + CORPSE_HEADER_LENGTH)
+            .ok_or_else(|| unexpected_bytes(offset, "12-byte corpse header"))?;
+        let unknown = u32_from(&header[0..4], "corpse unknown")?;
+        let x = u32_from(&header[4..8], "corpse x")?;
+        let y = u32_from(&header[8..12], "corpse y")?;
+        let (items, next_offset) = parse_verified_list(
+            byte_slice,
+            offset + CORPSE_HEADER_LENGTH,
+            format,
+            stat_costs,
+            "Corpse",
+        )?;
+        corpses.push(Corpse { unknown, x, y, items });
+        offset = next_offset;
+    }
+    Ok((corpses, offset))
+}
+
+/// Decode the items section.
+///
+/// Every decoded item list is re-encoded and compared with its source bytes, so a
+/// successful parse is guaranteed to write back byte-identically.
+pub fn parse(byte_slice: &[u8], format: FormatId) -> Result<Items, ParseHardError> {
+    parse_with(byte_slice, format, default_stat_costs(format))
+}
+
+/// [`parse`] with property widths from `stat_costs`, for example a mod's
+/// [`GameData::item_stat_cost`].
+pub fn parse_with(
+    byte_slice: &[u8],
+    format: FormatId,
+    stat_costs: &ItemStatCost,
+) -> Result<Items, ParseHardError> {
+    let (player, offset) = parse_verified_list(byte_slice, 0, format, stat_costs, "Player")?;
+    let (corpses, mut offset) = parse_corpses(byte_slice, offset, format, stat_costs)?;
+    let mut items = Items { player, corpses, ..Items::default() };
+    if offset == byte_slice.len() {
+        return Ok(items);
+    }
+
+    if !byte_slice[offset..].starts_with(&MERCENARY_HEADER) {
+        return Err(unexpected_bytes(offset, "mercenary 'jf' header"));
+    }
+    offset += MERCENARY_HEADER.len();
+    if byte_slice[offset..].starts_with(&ITEM_LIST_HEADER) {
+        (items.mercenary, offset) =
+            parse_verified_list(byte_slice, offset, format, stat_costs, "Mercenary")?;
+    }
+
+    if !byte_slice[offset..].starts_with(&IRON_GOLEM_HEADER) {
+        return Err(unexpected_bytes(offset, "iron golem 'kf' header"));
+    }
+    offset += IRON_GOLEM_HEADER.len();
+    let has_iron_golem = u8_from(&byte_slice[offset..], "iron golem flag")?;
+    offset += 1;
+    if has_iron_golem != 0 {
+        let mut byte_position = BytePosition { current_byte: offset, current_bit: 0 };
+        let golem_item = Item::parse_with(byte_slice, &mut byte_position, format, stat_costs)?;
+        let next_offset = byte_position.next_byte_offset();
+        let mut golem_bytes = Vec::new();
+        golem_item.write_with(&mut golem_bytes, format, stat_costs)?;
+        if golem_bytes != byte_slice[offset..next_offset] {
+            return Err(ParseHardError {
+                message: "Decoded iron golem item does not re-encode to its source bytes."
+                    .to_string(),
+            });
+        }
+        items.iron_golem = Some(golem_item);
+        offset = next_offset;
+    }
+
+    items.rotw_section = byte_slice[offset..].to_vec();
+    Ok(items)
+}
+
+/// Store item bytes without decoding.
+pub fn parse_raw(byte_slice: &[u8]) -> Items {
+    Items { raw: byte_slice.to_vec(), ..Items::default() }
 }
 
 /// Generate item bytes.
 ///
-/// If `placeholder` contains raw bytes, they are returned unchanged.
-/// Otherwise, a known empty-item layout trailer is emitted.
+/// Raw (undecoded) sections are returned unchanged. Otherwise every subsection is
+/// encoded for `format`. Mercenary and iron golem subsections are only written for
+/// expansion modes, and the mercenary item list only when a mercenary is hired.
 pub fn generate(
-    placeholder: &Placeholder,
-    empty_layout: EmptyLayout,
+    items: &Items,
+    format: FormatId,
+    expansion_type: ExpansionType,
     mercenary_hired: bool,
-) -> Vec<u8> {
-    if !placeholder.data.is_empty() {
-        return placeholder.data.clone();
+) -> Result<Vec<u8>, ParseHardError> {
+    generate_with(items, format, expansion_type, mercenary_hired, default_stat_costs(format))
+}
+
+/// [`generate`] with property widths from `stat_costs`.
+pub fn generate_with(
+    items: &Items,
+    format: FormatId,
+    expansion_type: ExpansionType,
+    mercenary_hired: bool,
+    stat_costs: &ItemStatCost,
+) -> Result<Vec<u8>, ParseHardError> {
+    if items.is_raw() {
+        return Ok(items.raw.clone());
+    }
+
+    let mut byte_vector = items.player.to_bytes_with(format, stat_costs)?;
+
+    let corpse_count = u16::try_from(items.corpses.len()).map_err(|_| ParseHardError {
+        message: format!("Cannot store {} corpses.", items.corpses.len()),
+    })?;
+    byte_vector.extend_from_slice(&ITEM_LIST_HEADER);
+    byte_vector.extend_from_slice(&corpse_count.to_le_bytes());
+    for corpse in &items.corpses {
+        byte_vector.extend_from_slice(&corpse.unknown.to_le_bytes());
+        byte_vector.extend_from_slice(&corpse.x.to_le_bytes());
+        byte_vector.extend_from_slice(&corpse.y.to_le_bytes());
+        byte_vector.append(&mut corpse.items.to_bytes_with(format, stat_costs)?);
+    }
+
+    if expansion_type == ExpansionType::Classic {
+        return Ok(byte_vector);
+    }
+
+    byte_vector.extend_from_slice(&MERCENARY_HEADER);
+    if mercenary_hired {
+        byte_vector.append(&mut items.mercenary.to_bytes_with(format, stat_costs)?);
+    }
+    byte_vector.extend_from_slice(&IRON_GOLEM_HEADER);
+    byte_vector.push(u8::from(items.iron_golem.is_some()));
+    if let Some(golem_item) = &items.iron_golem {
+        golem_item.write_with(&mut byte_vector, format, stat_costs)?;
     }
 
-    match (empty_layout, mercenary_hired) {
-        (EmptyLayout::LegacyClassic, _) => V99_EMPTY_ITEMS_CLASSIC.to_vec(),
-        (EmptyLayout::LegacyExpansion, false) => V99_EMPTY_ITEMS_EXPANSION.to_vec(),
-        (EmptyLayout::LegacyExpansion, true) => V99_EMPTY_ITEMS_EXPANSION_MERC.to_vec(),
-        (EmptyLayout::V105Classic, _) => V105_EMPTY_ITEMS_CLASSIC.to_vec(),
-        (EmptyLayout::V105Expansion, false) => V105_EMPTY_ITEMS_EXPANSION.to_vec(),
-        (EmptyLayout::V105Expansion, true) => V105_EMPTY_ITEMS_EXPANSION_MERC.to_vec(),
-        (EmptyLayout::V105RotW, false) => V105_EMPTY_ITEMS_ROTW.to_vec(),
-        (EmptyLayout::V105RotW, true) => V105_EMPTY_ITEMS_ROTW_MERC.to_vec(),
+    if expansion_type == ExpansionType::RotW {
+        if items.rotw_section.is_empty() {
+            byte_vector.extend_from_slice(&EMPTY_ROTW_SECTION);
+        } else {
+            byte_vector.extend_from_slice(&items.rotw_section);
+        }
     }
+    Ok(byte_vector)
 }
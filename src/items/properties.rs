@@ -0,0 +1,540 @@
+//! Item property lists and the stat-cost table that drives them.
+//!
+//! Property lists are a sequence of 9-bit stat ids, each followed by the
+//! stat's parameter and value fields, terminated by `0x1FF`. Field widths come
+//! from the `Save Param Bits`, `Save Bits` and `Save Add` columns of
+//! `ItemStatCost.txt`; [`ItemStatCost::d2r`] holds the default D2R values and
+//! [`ItemStatCost::classic`] the 1.10–1.14d ones.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{read_bits, write_bits, BytePosition};
+use crate::{GameEdition, ParseHardError};
+
+/// Bit width of a property stat id.
+pub const STAT_ID_BITS: usize = 9;
+/// Stat id that terminates a property list.
+pub const PROPERTY_LIST_TERMINATOR: u16 = 0x1FF;
+
+/// `(stat id, name, save bits, save param bits, save add)` for every stat that can be saved on an item.
+const D2R_ITEM_STAT_COSTS: [(u16, &str, usize, usize, i32); 239] = [
+    (0, "strength", 8, 0, 32),
+    (1, "energy", 7, 0, 32),
+    (2, "dexterity", 7, 0, 32),
+    (3, "vitality", 7, 0, 32),
+    (7, "maxhp", 9, 0, 32),
+    (9, "maxmana", 8, 0, 32),
+    (11, "maxstamina", 8, 0, 32),
+    (16, "item_armor_percent", 9, 0, 0),
+    (17, "item_maxdamage_percent", 9, 0, 0),
+    (18, "item_mindamage_percent", 9, 0, 0),
+    (19, "tohit", 10, 0, 0),
+    (20, "toblock", 6, 0, 0),
+    (21, "mindamage", 6, 0, 0),
+    (22, "maxdamage", 7, 0, 0),
+    (23, "secondary_mindamage", 6, 0, 0),
+    (24, "secondary_maxdamage", 7, 0, 0),
+    (25, "damagepercent", 8, 0, 0),
+    (26, "manarecovery", 8, 0, 0),
+    (27, "manarecoverybonus", 8, 0, 0),
+    (28, "staminarecoverybonus", 8, 0, 0),
+    (31, "armorclass", 11, 0, 10),
+    (32, "armorclass_vs_missile", 9, 0, 0),
+    (33, "armorclass_vs_hth", 8, 0, 0),
+    (34, "normal_damage_reduction", 6, 0, 0),
+    (35, "magic_damage_reduction", 6, 0, 0),
+    (36, "damageresist", 9, 0, 200),
+    (37, "magicresist", 9, 0, 200),
+    (38, "maxmagicresist", 5, 0, 0),
+    (39, "fireresist", 9, 0, 200),
+    (40, "maxfireresist", 5, 0, 0),
+    (41, "lightresist", 9, 0, 200),
+    (42, "maxlightresist", 5, 0, 0),
+    (43, "coldresist", 9, 0, 200),
+    (44, "maxcoldresist", 5, 0, 0),
+    (45, "poisonresist", 9, 0, 200),
+    (46, "maxpoisonresist", 5, 0, 0),
+    (48, "firemindam", 8, 0, 0),
+    (49, "firemaxdam", 9, 0, 0),
+    (50, "lightmindam", 6, 0, 0),
+    (51, "lightmaxdam", 10, 0, 0),
+    (52, "magicmindam", 8, 0, 0),
+    (53, "magicmaxdam", 9, 0, 0),
+    (54, "coldmindam", 8, 0, 0),
+    (55, "coldmaxdam", 9, 0, 0),
+    (56, "coldlength", 8, 0, 0),
+    (57, "poisonmindam", 10, 0, 0),
+    (58, "poisonmaxdam", 10, 0, 0),
+    (59, "poisonlength", 9, 0, 0),
+    (60, "lifedrainmindam", 7, 0, 0),
+    (62, "manadrainmindam", 7, 0, 0),
+    (67, "velocitypercent", 7, 0, 30),
+    (68, "attackrate", 7, 0, 30),
+    (71, "value", 8, 0, 100),
+    (72, "durability", 9, 0, 0),
+    (73, "maxdurability", 8, 0, 0),
+    (74, "hpregen", 6, 0, 30),
+    (75, "item_maxdurability_percent", 7, 0, 20),
+    (76, "item_maxhp_percent", 6, 0, 10),
+    (77, "item_maxmana_percent", 6, 0, 10),
+    (78, "item_attackertakesdamage", 7, 0, 0),
+    (79, "item_goldbonus", 9, 0, 100),
+    (80, "item_magicbonus", 8, 0, 100),
+    (81, "item_knockback", 7, 0, 0),
+    (82, "item_timeduration", 9, 0, 20),
+    (83, "item_addclassskills", 3, 3, 0),
+    (85, "item_addexperience", 9, 0, 50),
+    (86, "item_healafterkill", 7, 0, 0),
+    (87, "item_reducedprices", 7, 0, 0),
+    (88, "item_doubleherbduration", 1, 0, 0),
+    (89, "item_lightradius", 4, 0, 4),
+    (90, "item_lightcolor", 24, 0, 0),
+    (91, "item_req_percent", 8, 0, 100),
+    (92, "item_levelreq", 7, 0, 0),
+    (93, "item_fasterattackrate", 7, 0, 20),
+    (94, "item_levelreqpct", 7, 0, 64),
+    (96, "item_fastermovevelocity", 7, 0, 20),
+    (97, "item_nonclassskill", 6, 9, 0),
+    (98, "state", 1, 8, 0),
+    (99, "item_fastergethitrate", 7, 0, 20),
+    (102, "item_fasterblockrate", 7, 0, 20),
+    (105, "item_fastercastrate", 7, 0, 20),
+    (107, "item_singleskill", 3, 9, 0),
+    (108, "item_restinpeace", 1, 0, 0),
+    (109, "curse_resistance", 9, 0, 0),
+    (110, "item_poisonlengthresist", 8, 0, 20),
+    (111, "item_normaldamage", 9, 0, 20),
+    (112, "item_howl", 7, 0, 0),
+    (113, "item_stupidity", 7, 0, 0),
+    (114, "item_damagetomana", 6, 0, 0),
+    (115, "item_ignoretargetac", 1, 0, 0),
+    (116, "item_fractionaltargetac", 7, 0, 0),
+    (117, "item_preventheal", 7, 0, 0),
+    (118, "item_halffreezeduration", 1, 0, 0),
+    (119, "item_tohit_percent", 9, 0, 20),
+    (120, "item_damagetargetac", 7, 0, 128),
+    (121, "item_demondamage_percent", 9, 0, 20),
+    (122, "item_undeaddamage_percent", 9, 0, 20),
+    (123, "item_demon_tohit", 10, 0, 128),
+    (124, "item_undead_tohit", 10, 0, 128),
+    (125, "item_throwable", 1, 0, 0),
+    (126, "item_elemskill", 3, 3, 0),
+    (127, "item_allskills", 3, 0, 0),
+    (128, "item_attackertakeslightdamage", 5, 0, 0),
+    (134, "item_freeze", 5, 0, 0),
+    (135, "item_openwounds", 7, 0, 0),
+    (136, "item_crushingblow", 7, 0, 0),
+    (137, "item_kickdamage", 7, 0, 0),
+    (138, "item_manaafterkill", 7, 0, 0),
+    (139, "item_healafterdemonkill", 7, 0, 0),
+    (140, "item_extrablood", 7, 0, 0),
+    (141, "item_deadlystrike", 7, 0, 0),
+    (142, "item_absorbfire_percent", 7, 0, 0),
+    (143, "item_absorbfire", 7, 0, 0),
+    (144, "item_absorblight_percent", 7, 0, 0),
+    (145, "item_absorblight", 7, 0, 0),
+    (146, "item_absorbmagic_percent", 7, 0, 0),
+    (147, "item_absorbmagic", 7, 0, 0),
+    (148, "item_absorbcold_percent", 7, 0, 0),
+    (149, "item_absorbcold", 7, 0, 0),
+    (150, "item_slow", 7, 0, 0),
+    (151, "item_aura", 5, 9, 0),
+    (152, "item_indesctructible", 1, 0, 0),
+    (153, "item_cannotbefrozen", 1, 0, 0),
+    (154, "item_staminadrainpct", 7, 0, 20),
+    (155, "item_reanimate", 7, 10, 0),
+    (156, "item_pierce", 7, 0, 0),
+    (157, "item_magicarrow", 7, 0, 0),
+    (158, "item_explosivearrow", 7, 0, 0),
+    (159, "item_throw_mindamage", 6, 0, 0),
+    (160, "item_throw_maxdamage", 7, 0, 0),
+    (179, "attack_vs_montype", 9, 10, 0),
+    (180, "damage_vs_montype", 9, 10, 0),
+    (187, "item_pierce_cold_immunity", 10, 0, 0),
+    (188, "item_addskill_tab", 3, 16, 0),
+    (189, "item_pierce_fire_immunity", 10, 0, 0),
+    (190, "item_pierce_light_immunity", 10, 0, 0),
+    (191, "item_pierce_poison_immunity", 10, 0, 0),
+    (192, "item_pierce_damage_immunity", 10, 0, 0),
+    (193, "item_pierce_magic_immunity", 10, 0, 0),
+    (194, "item_numsockets", 4, 0, 0),
+    (195, "item_skillonattack", 7, 16, 0),
+    (196, "item_skillonkill", 7, 16, 0),
+    (197, "item_skillondeath", 7, 16, 0),
+    (198, "item_skillonhit", 7, 16, 0),
+    (199, "item_skillonlevelup", 7, 16, 0),
+    (201, "item_skillongethit", 7, 16, 0),
+    (204, "item_charged_skill", 16, 16, 0),
+    (214, "item_armor_perlevel", 6, 0, 0),
+    (215, "item_armorpercent_perlevel", 6, 0, 0),
+    (216, "item_hp_perlevel", 6, 0, 0),
+    (217, "item_mana_perlevel", 6, 0, 0),
+    (218, "item_maxdamage_perlevel", 6, 0, 0),
+    (219, "item_maxdamage_percent_perlevel", 6, 0, 0),
+    (220, "item_strength_perlevel", 6, 0, 0),
+    (221, "item_dexterity_perlevel", 6, 0, 0),
+    (222, "item_energy_perlevel", 6, 0, 0),
+    (223, "item_vitality_perlevel", 6, 0, 0),
+    (224, "item_tohit_perlevel", 6, 0, 0),
+    (225, "item_tohitpercent_perlevel", 6, 0, 0),
+    (226, "item_cold_damagemax_perlevel", 6, 0, 0),
+    (227, "item_fire_damagemax_perlevel", 6, 0, 0),
+    (228, "item_ltng_damagemax_perlevel", 6, 0, 0),
+    (229, "item_pois_damagemax_perlevel", 6, 0, 0),
+    (230, "item_resist_cold_perlevel", 6, 0, 0),
+    (231, "item_resist_fire_perlevel", 6, 0, 0),
+    (232, "item_resist_ltng_perlevel", 6, 0, 0),
+    (233, "item_resist_pois_perlevel", 6, 0, 0),
+    (234, "item_absorb_cold_perlevel", 6, 0, 0),
+    (235, "item_absorb_fire_perlevel", 6, 0, 0),
+    (236, "item_absorb_ltng_perlevel", 6, 0, 0),
+    (237, "item_absorb_pois_perlevel", 6, 0, 0),
+    (238, "item_thorns_perlevel", 5, 0, 0),
+    (239, "item_find_gold_perlevel", 6, 0, 0),
+    (240, "item_find_magic_perlevel", 6, 0, 0),
+    (241, "item_regenstamina_perlevel", 6, 0, 0),
+    (242, "item_stamina_perlevel", 6, 0, 0),
+    (243, "item_damage_demon_perlevel", 6, 0, 0),
+    (244, "item_damage_undead_perlevel", 6, 0, 0),
+    (245, "item_tohit_demon_perlevel", 6, 0, 0),
+    (246, "item_tohit_undead_perlevel", 6, 0, 0),
+    (247, "item_crushingblow_perlevel", 6, 0, 0),
+    (248, "item_openwounds_perlevel", 6, 0, 0),
+    (249, "item_kick_damage_perlevel", 6, 0, 0),
+    (250, "item_deadlystrike_perlevel", 6, 0, 0),
+    (252, "item_replenish_durability", 6, 0, 0),
+    (253, "item_replenish_quantity", 6, 0, 0),
+    (254, "item_extra_stack", 8, 0, 0),
+    (268, "item_armor_bytime", 22, 0, 0),
+    (269, "item_armorpercent_bytime", 22, 0, 0),
+    (270, "item_hp_bytime", 22, 0, 0),
+    (271, "item_mana_bytime", 22, 0, 0),
+    (272, "item_maxdamage_bytime", 22, 0, 0),
+    (273, "item_maxdamage_percent_bytime", 22, 0, 0),
+    (274, "item_strength_bytime", 22, 0, 0),
+    (275, "item_dexterity_bytime", 22, 0, 0),
+    (276, "item_energy_bytime", 22, 0, 0),
+    (277, "item_vitality_bytime", 22, 0, 0),
+    (278, "item_tohit_bytime", 22, 0, 0),
+    (279, "item_tohitpercent_bytime", 22, 0, 0),
+    (280, "item_cold_damagemax_bytime", 22, 0, 0),
+    (281, "item_fire_damagemax_bytime", 22, 0, 0),
+    (282, "item_ltng_damagemax_bytime", 22, 0, 0),
+    (283, "item_pois_damagemax_bytime", 22, 0, 0),
+    (284, "item_resist_cold_bytime", 22, 0, 0),
+    (285, "item_resist_fire_bytime", 22, 0, 0),
+    (286, "item_resist_ltng_bytime", 22, 0, 0),
+    (287, "item_resist_pois_bytime", 22, 0, 0),
+    (288, "item_absorb_cold_bytime", 22, 0, 0),
+    (289, "item_absorb_fire_bytime", 22, 0, 0),
+    (290, "item_absorb_ltng_bytime", 22, 0, 0),
+    (291, "item_absorb_pois_bytime", 22, 0, 0),
+    (292, "item_find_gold_bytime", 22, 0, 0),
+    (293, "item_find_magic_bytime", 22, 0, 0),
+    (294, "item_regenstamina_bytime", 22, 0, 0),
+    (295, "item_stamina_bytime", 22, 0, 0),
+    (296, "item_damage_demon_bytime", 22, 0, 0),
+    (297, "item_damage_undead_bytime", 22, 0, 0),
+    (298, "item_tohit_demon_bytime", 22, 0, 0),
+    (299, "item_tohit_undead_bytime", 22, 0, 0),
+    (300, "item_crushingblow_bytime", 22, 0, 0),
+    (301, "item_openwounds_bytime", 22, 0, 0),
+    (302, "item_kick_damage_bytime", 22, 0, 0),
+    (303, "item_deadlystrike_bytime", 22, 0, 0),
+    (305, "item_pierce_cold", 8, 0, 0),
+    (306, "item_pierce_fire", 8, 0, 0),
+    (307, "item_pierce_ltng", 8, 0, 0),
+    (308, "item_pierce_pois", 8, 0, 0),
+    (324, "item_extra_charges", 6, 0, 0),
+    (329, "passive_fire_mastery", 9, 0, 50),
+    (330, "passive_ltng_mastery", 9, 0, 50),
+    (331, "passive_cold_mastery", 9, 0, 50),
+    (332, "passive_pois_mastery", 9, 0, 50),
+    (333, "passive_fire_pierce", 8, 0, 0),
+    (334, "passive_ltng_pierce", 8, 0, 0),
+    (335, "passive_cold_pierce", 8, 0, 0),
+    (336, "passive_pois_pierce", 8, 0, 0),
+    (356, "quest_item_difficulty", 2, 0, 0),
+    (357, "passive_mag_mastery", 9, 0, 50),
+    (358, "passive_mag_pierce", 8, 0, 0),
+];
+
+/// Stats that store the following stat ids' values under their own id.
+const D2R_GROUPED_STATS: [(u16, &[u16]); 6] =
+    [(17, &[18]), (48, &[49]), (50, &[51]), (52, &[53]), (54, &[55, 56]), (57, &[58, 59])];
+
+/// `(stat id, save bits, save add)` of stats saved with other widths in 1.10–1.14d.
+const CLASSIC_STAT_COST_CHANGES: [(u16, usize, i32); 6] =
+    [(36, 8, 0), (37, 8, 0), (39, 8, 50), (41, 8, 50), (43, 8, 50), (45, 8, 50)];
+
+/// Stats added by D2R (the sunder charms' immunity pierce) that 1.10–1.14d cannot save.
+const D2R_ONLY_STATS: [u16; 6] = [187, 189, 190, 191, 192, 193];
+
+/// Save-format metadata for one item stat.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct StatCost {
+    pub id: u16,
+    /// Stat key (`Stat` column), for example `"item_fastercastrate"`.
+    pub name: String,
+    /// Width of the stored value (`Save Bits`).
+    pub value_bits: usize,
+    /// Width of the stored parameter (`Save Param Bits`).
+    pub param_bits: usize,
+    /// Offset added to the value before it is stored (`Save Add`).
+    pub save_add: i32,
+    /// Stats whose values follow this one under the same id, for example max damage after min damage.
+    #[serde(default)]
+    pub grouped_stats: Vec<u16>,
+}
+
+impl StatCost {
+    /// Number of values stored under this stat id, including grouped stats.
+    pub fn value_count(&self) -> usize {
+        1 + self.grouped_stats.len()
+    }
+}
+
+/// Table of item stat save widths, keyed by stat id.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ItemStatCost {
+    stats: BTreeMap<u16, StatCost>,
+}
+
+impl ItemStatCost {
+    /// Build a table from stat entries. Later entries replace earlier ones with the same id.
+    pub fn new(stats: impl IntoIterator<Item = StatCost>) -> ItemStatCost {
+        ItemStatCost { stats: stats.into_iter().map(|stat| (stat.id, stat)).collect() }
+    }
+
+    /// Default D2R table.
+    pub fn d2r() -> &'static ItemStatCost {
+        static D2R: OnceLock<ItemStatCost> = OnceLock::new();
+        D2R.get_or_init(|| {
+            ItemStatCost::new(D2R_ITEM_STAT_COSTS.iter().map(
+                |&(id, name, value_bits, param_bits, save_add)| {
+                    StatCost {
+                        id,
+                        name: name.to_string(),
+                        value_bits,
+                        param_bits,
+                        save_add,
+                        grouped_stats: D2R_GROUPED_STATS
+                            .iter()
+                            .find(|(group_id, _)| *group_id == id)
+                            .map_or_else(Vec::new, |(_, grouped)| grouped.to_vec()),
+                    }
+                },
+            ))
+        })
+    }
+
+    /// Default 1.10–1.14d table, for [`GameEdition::Classic`] saves.
+    ///
+    /// Resistances are stored in 8 bits instead of 9, with a smaller `Save Add`, and the
+    /// D2R sunder stats are missing.
+    pub fn classic() -> &'static ItemStatCost {
+        static CLASSIC: OnceLock<ItemStatCost> = OnceLock::new();
+        CLASSIC.get_or_init(|| {
+            ItemStatCost::new(
+                ItemStatCost::d2r().iter().filter(|stat| !D2R_ONLY_STATS.contains(&stat.id)).map(
+                    |stat| {
+                        let mut stat = stat.clone();
+                        if let Some(&(_, value_bits, save_add)) =
+                            CLASSIC_STAT_COST_CHANGES.iter().find(|(id, ..)| *id == stat.id)
+                        {
+                            stat.value_bits = value_bits;
+                            stat.save_add = save_add;
+                        }
+                        stat
+                    },
+                ),
+            )
+        })
+    }
+
+    /// Default table for the item layout of `edition`.
+    pub fn for_edition(edition: GameEdition) -> &'static ItemStatCost {
+        match edition {
+            GameEdition::Classic => ItemStatCost::classic(),
+            GameEdition::D2RLegacy | GameEdition::RotW => ItemStatCost::d2r(),
+        }
+    }
+
+    pub fn get(&self, stat_id: u16) -> Option<&StatCost> {
+        self.stats.get(&stat_id)
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&StatCost> {
+        self.stats.values().find(|stat| stat.name == name)
+    }
+
+    /// Iterate stats in id order.
+    pub fn iter(&self) -> impl Iterator<Item = &StatCost> {
+        self.stats.values()
+    }
+
+    fn require(&self, stat_id: u16) -> Result<&StatCost, ParseHardError> {
+        self.get(stat_id).ok_or_else(|| ParseHardError {
+            message: format!("Unknown item stat id {stat_id} in property list."),
+        })
+    }
+}
+
+/// One property: a stat id, its parameter, and its value(s).
+///
+/// `values` holds one entry per stat stored under this id: the stat itself, then its
+/// grouped stats (for example min and max fire damage, or cold damage and duration).
+/// Values are game-visible, with `Save Add` already removed.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Property {
+    pub stat_id: u16,
+    /// Stat parameter, such as a skill id. `0` for stats without one.
+    pub param: u32,
+    pub values: Vec<i32>,
+}
+
+impl Property {
+    /// Single-value property without a parameter.
+    pub fn new(stat_id: u16, value: i32) -> Property {
+        Property { stat_id, param: 0, values: vec![value] }
+    }
+
+    /// First stored value.
+    pub fn value(&self) -> i32 {
+        self.values.first().copied().unwrap_or_default()
+    }
+
+    /// Stat name from `table`, if known.
+    pub fn name<'a>(&self, table: &'a ItemStatCost) -> Option<&'a str> {
+        table.get(self.stat_id).map(|stat| stat.name.as_str())
+    }
+}
+
+/// One `0x1FF`-terminated property list.
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PropertyList {
+    pub properties: Vec<Property>,
+}
+
+fn read_stat_value(
+    byte_slice: &[u8],
+    byte_position: &mut BytePosition,
+    stat: &StatCost,
+) -> Result<i32, ParseHardError> {
+    let stored = read_bits(byte_slice, byte_position, stat.value_bits)?;
+    Ok((i64::from(stored) - i64::from(stat.save_add)) as i32)
+}
+
+fn write_stat_value(
+    byte_vector: &mut Vec<u8>,
+    byte_position: &mut BytePosition,
+    stat: &StatCost,
+    value: i32,
+) -> Result<(), ParseHardError> {
+    let stored =
+        u32::try_from(i64::from(value) + i64::from(stat.save_add)).map_err(|_| ParseHardError {
+            message: format!(
+                "Value {value} for stat {} ({}) is below its minimum {}.",
+                stat.id, stat.name, -stat.save_add
+            ),
+        })?;
+    write_bits(byte_vector, byte_position, stored, stat.value_bits).map_err(|error| {
+        ParseHardError {
+            message: format!("Value {value} for stat {} ({}): {error}", stat.id, stat.name),
+        }
+    })
+}
+
+impl PropertyList {
+    /// Read one property list, including its terminator.
+    pub fn parse(
+        byte_slice: &[u8],
+        byte_position: &mut BytePosition,
+        table: &ItemStatCost,
+    ) -> Result<PropertyList, ParseHardError> {
+        let mut properties = Vec::new();
+        loop {
+            let stat_id = read_bits(byte_slice, byte_position, STAT_ID_BITS)? as u16;
+            if stat_id == PROPERTY_LIST_TERMINATOR {
+                break;
+            }
+
+            let stat = table.require(stat_id)?;
+            let param = read_bits(byte_slice, byte_position, stat.param_bits)?;
+            let mut values = vec![read_stat_value(byte_slice, byte_position, stat)?];
+            for grouped_id in &stat.grouped_stats {
+                let grouped_stat = table.require(*grouped_id)?;
+                values.push(read_stat_value(byte_slice, byte_position, grouped_stat)?);
+            }
+            properties.push(Property { stat_id, param, values });
+        }
+        Ok(PropertyList { properties })
+    }
+
+    /// Write this list, including its terminator.
+    pub fn write(
+        &self,
+        byte_vector: &mut Vec<u8>,
+        byte_position: &mut BytePosition,
+        table: &ItemStatCost,
+    ) -> Result<(), ParseHardError> {
+        for property in &self.properties {
+            let stat = table.require(property.stat_id)?;
+            if property.values.len() != stat.value_count() {
+                return Err(ParseHardError {
+                    message: format!(
+                        "Stat {} ({}) stores {} values, found {}.",
+                        stat.id,
+                        stat.name,
+                        stat.value_count(),
+                        property.values.len()
+                    ),
+                });
+            }
+
+            write_bits(byte_vector, byte_position, property.stat_id, STAT_ID_BITS)?;
+            write_bits(byte_vector, byte_position, property.param, stat.param_bits)?;
+            write_stat_value(byte_vector, byte_position, stat, property.values[0])?;
+            for (grouped_id, value) in stat.grouped_stats.iter().zip(&property.values[1..]) {
+                write_stat_value(byte_vector, byte_position, table.require(*grouped_id)?, *value)?;
+            }
+        }
+        write_bits(byte_vector, byte_position, PROPERTY_LIST_TERMINATOR, STAT_ID_BITS)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Property> {
+        self.properties.iter()
+    }
+
+    /// First property with this stat id.
+    pub fn get(&self, stat_id: u16) -> Option<&Property> {
+        self.properties.iter().find(|property| property.stat_id == stat_id)
+    }
+
+    /// Property with this stat id and parameter.
+    pub fn get_with_param(&self, stat_id: u16, param: u32) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|property| property.stat_id == stat_id && property.param == param)
+    }
+
+    /// Replace the property with the same stat id and parameter, or append it.
+    pub fn set(&mut self, property: Property) {
+        match self.properties.iter_mut().find(|existing| {
+            existing.stat_id == property.stat_id && existing.param == property.param
+        }) {
+            Some(existing) => *existing = property,
+            None => self.properties.push(property),
+        }
+    }
+
+    /// Remove every property with this stat id. Returns whether any were removed.
+    pub fn remove(&mut self, stat_id: u16) -> bool {
+        let original_length = self.properties.len();
+        self.properties.retain(|property| property.stat_id != stat_id);
+        self.properties.len() != original_length
+    }
+}
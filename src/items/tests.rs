@@ -0,0 +1,355 @@
+use super::huffman::{read_item_code, write_item_code};
+use super::*;
+use crate::utils::BytePosition;
+use crate::{Save, Strictness};
+
+const FIXTURES: [(&str, &[u8]); 7] = [
+    ("Joe", include_bytes!("../../assets/test/Joe.d2s")),
+    ("Ayame", include_bytes!("../../assets/test/Ayame.d2s")),
+    ("Test", include_bytes!("../../assets/test/Test.d2s")),
+    ("Warlock_v105", include_bytes!("../../assets/test/Warlock_v105.d2s")),
+    ("barbclassic_v105", include_bytes!("../../assets/test/barbclassic_v105.d2s")),
+    ("barbexp_v105", include_bytes!("../../assets/test/barbexp_v105.d2s")),
+    ("barbrotw_v105", include_bytes!("../../assets/test/barbrotw_v105.d2s")),
+];
+
+fn parse_fixture(name: &str) -> Save {
+    let (_, bytes) = FIXTURES.iter().find(|(fixture, _)| *fixture == name).unwrap();
+    Save::parse(bytes, Strictness::Strict).unwrap().save
+}
+
+#[test]
+fn item_code_huffman_roundtrip() {
+    for code in ["hp1", "cm3", "7gd", "xyz", "jew", "r33", "box"] {
+        let mut byte_vector = Vec::new();
+        write_item_code(&mut byte_vector, &mut BytePosition::default(), code).unwrap();
+        let decoded = read_item_code(&byte_vector, &mut BytePosition::default()).unwrap();
+        assert_eq!(decoded, code);
+    }
+}
+
+#[test]
+fn item_code_rejects_unsupported_characters() {
+    let mut byte_vector = Vec::new();
+    assert!(write_item_code(&mut byte_vector, &mut BytePosition::default(), "HP1").is_err());
+    assert!(write_item_code(&mut byte_vector, &mut BytePosition::default(), "abcde").is_err());
+}
+
+#[test]
+fn raw_bits_roundtrip_unaligned() {
+    let source = [0b1011_0110, 0b0101_1101, 0b0000_0011];
+    let mut read_position = BytePosition { current_byte: 0, current_bit: 3 };
+    let raw = RawBits::read(&source, &mut read_position, 13).unwrap();
+    assert_eq!(raw.bit_length(), 13);
+    assert_eq!(read_position, BytePosition { current_byte: 2, current_bit: 0 });
+
+    let mut byte_vector = vec![0b0000_0110];
+    let mut write_position = BytePosition { current_byte: 0, current_bit: 3 };
+    raw.write(&mut byte_vector, &mut write_position).unwrap();
+    assert_eq!(byte_vector, source[..2]);
+}
+
+#[test]
+fn joe_player_items_decode() {
+    let save = parse_fixture("Joe");
+    let items = &save.items.player.items;
+    assert_eq!(items.len(), 8);
+
+    let potions: Vec<&Item> = items.iter().filter(|item| item.code == "hp1").collect();
+    assert_eq!(potions.len(), 4);
+    for (slot, potion) in potions.iter().enumerate() {
+        assert_eq!(potion.mode, ItemMode::Belt);
+        assert_eq!(usize::from(potion.x), slot);
+        assert!(potion.flags.is_simple());
+        assert!(potion.extended.is_none());
+    }
+
+    let axe = items.iter().find(|item| item.code == "hax").unwrap();
+    assert_eq!(axe.mode, ItemMode::Equipped);
+    assert!(axe.flags.is_starter());
+    let axe_data = axe.extended.as_ref().unwrap();
+    assert_eq!(axe_data.quality, ItemQuality::Normal);
+    assert!(axe_data.durability.is_some_and(|durability| durability.max > 0));
+    assert_eq!(axe_data.defense, None);
+
+    let buckler = items.iter().find(|item| item.code == "buc").unwrap();
+    assert_eq!(buckler.extended.as_ref().unwrap().defense, Some(4));
+}
+
+#[test]
+fn ayame_socketed_items_decode() {
+    let save = parse_fixture("Ayame");
+    let items = &save.items.player.items;
+    assert_eq!(items.len(), 82);
+
+    let runeword_item = items.iter().find(|item| item.flags.is_runeword()).unwrap();
+    let runeword_data = runeword_item.extended.as_ref().unwrap();
+    assert!(runeword_data.runeword.is_some());
+    assert!(runeword_data.runeword_properties.is_some());
+    assert_eq!(
+        usize::from(runeword_data.total_sockets.unwrap()),
+        runeword_item.socketed_items.len()
+    );
+    for socketed_item in &runeword_item.socketed_items {
+        assert_eq!(socketed_item.mode, ItemMode::Socketed);
+    }
+}
+
+#[test]
+fn fixture_item_sections_roundtrip_byte_identical() {
+    for (name, bytes) in FIXTURES {
+        let save = Save::parse(bytes, Strictness::Strict).unwrap().save;
+        assert!(!save.items.is_raw(), "{name}: items fell back to raw bytes");
+
+        let generated = generate(
+            &save.items,
+            save.format(),
+            save.expansion_type(),
+            save.character.mercenary.is_hired(),
+        )
+        .unwrap();
+        assert!(bytes.ends_with(&generated), "{name}: item section bytes differ");
+        assert_eq!(&generated[..2], &ITEM_LIST_HEADER, "{name}: missing item list header");
+    }
+}
+
+#[test]
+fn edited_item_reencodes() {
+    let mut save = parse_fixture("Joe");
+    let buckler = save.items.player.items.iter_mut().find(|item| item.code == "buc").unwrap();
+    buckler.extended.as_mut().unwrap().defense = Some(12);
+    buckler.flags.set_ethereal(true);
+
+    let encoded = save.encode_for(save.format(), crate::CompatibilityChecks::Enforce).unwrap();
+    let reparsed = Save::parse(&encoded, Strictness::Strict).unwrap().save;
+    let buckler = reparsed.items.player.items.iter().find(|item| item.code == "buc").unwrap();
+    assert_eq!(buckler.extended.as_ref().unwrap().defense, Some(12));
+    assert!(buckler.flags.is_ethereal());
+    assert_eq!(reparsed.items, save.items);
+}
+
+#[test]
+fn undecodable_items_are_preserved_raw() {
+    let bytes = [0x4A, 0x4D, 0x01, 0x00, 0xFF];
+    assert!(parse(&bytes, FormatId::V99).is_err());
+
+    let items = parse_raw(&bytes);
+    assert!(items.is_raw());
+    assert_eq!(generate(&items, FormatId::V99, ExpansionType::Expansion, true).unwrap(), bytes);
+}
+
+#[test]
+fn ayame_subsections_decode() {
+    let save = parse_fixture("Ayame");
+    assert!(save.items.corpses.is_empty());
+    assert_eq!(save.items.mercenary.items.len(), 3);
+    assert!(save.items.mercenary.items.iter().all(|item| item.mode == ItemMode::Equipped));
+    assert!(save.items.iron_golem.is_none());
+}
+
+#[test]
+fn corpse_and_iron_golem_roundtrip() {
+    let mut save = parse_fixture("Ayame");
+    let mut corpse_items = ItemList::default();
+    corpse_items.items.push(save.items.player.items[0].clone());
+    save.items.corpses.push(Corpse { unknown: 0, x: 5012, y: 5740, items: corpse_items });
+    save.items.iron_golem = Some(save.items.mercenary.items[0].clone());
+
+    for format in [FormatId::V99, FormatId::V105] {
+        let bytes = generate(&save.items, format, ExpansionType::Expansion, true).unwrap();
+        assert_eq!(parse(&bytes, format).unwrap(), save.items);
+    }
+}
+
+#[test]
+fn mercenary_items_follow_hire_state() {
+    let save = parse_fixture("Ayame");
+    let hired = generate(&save.items, FormatId::V99, ExpansionType::Expansion, true).unwrap();
+    let fired = generate(&save.items, FormatId::V99, ExpansionType::Expansion, false).unwrap();
+
+    let reparsed = parse(&fired, FormatId::V99).unwrap();
+    assert!(reparsed.mercenary.items.is_empty());
+    assert_eq!(reparsed.player, save.items.player);
+    assert!(hired.len() > fired.len());
+    assert!(fired.ends_with(&[0x6A, 0x66, 0x6B, 0x66, 0x00]));
+}
+
+#[test]
+fn empty_items_emit_rotw_subsection() {
+    let items = Items::default();
+    assert_eq!(
+        generate(&items, FormatId::V105, ExpansionType::RotW, true).unwrap(),
+        [
+            0x4A, 0x4D, 0x00, 0x00, 0x4A, 0x4D, 0x00, 0x00, 0x6A, 0x66, 0x4A, 0x4D, 0x00, 0x00,
+            0x6B, 0x66, 0x00, 0x01, 0x00, 0x6C, 0x66, 0x00, 0x00,
+        ]
+    );
+}
+
+#[test]
+fn d2r_stat_cost_table_lookup() {
+    let table = ItemStatCost::d2r();
+    let fcr = table.by_name("item_fastercastrate").unwrap();
+    assert_eq!((fcr.id, fcr.value_bits, fcr.param_bits, fcr.save_add), (105, 7, 0, 20));
+    assert_eq!(table.get(57).unwrap().grouped_stats, vec![58, 59]);
+    assert_eq!(table.get(280).unwrap().value_bits, 22);
+    assert!(table.get(511).is_none());
+}
+
+#[test]
+fn ayame_properties_decode() {
+    let save = parse_fixture("Ayame");
+    let table = ItemStatCost::d2r();
+    let raven_frost = save
+        .items
+        .player
+        .items
+        .iter()
+        .filter_map(|item| item.extended.as_ref())
+        .find(|extended| extended.properties.get(153).is_some())
+        .unwrap();
+
+    let dexterity = raven_frost.properties.get(2).unwrap();
+    assert_eq!(dexterity.name(table), Some("dexterity"));
+    assert_eq!(dexterity.value(), 20);
+    assert_eq!(raven_frost.properties.get(9).unwrap().value(), 40);
+    assert_eq!(raven_frost.properties.get(54).unwrap().values, vec![15, 45, 100]);
+}
+
+#[test]
+fn edited_properties_reencode() {
+    let mut save = parse_fixture("Ayame");
+    let amulet = save
+        .items
+        .player
+        .items
+        .iter_mut()
+        .find(|item| item.code == "amu")
+        .and_then(|item| item.extended.as_mut())
+        .unwrap();
+    amulet.properties.set(Property::new(105, 30));
+    amulet.properties.set(Property::new(127, 2));
+    assert!(amulet.properties.remove(93));
+
+    let encoded = save.encode_for(save.format(), crate::CompatibilityChecks::Enforce).unwrap();
+    let reparsed = Save::parse(&encoded, Strictness::Strict).unwrap().save;
+    assert_eq!(reparsed.items, save.items);
+    let amulet = reparsed.items.player.items.iter().find(|item| item.code == "amu").unwrap();
+    let properties = &amulet.extended.as_ref().unwrap().properties;
+    assert_eq!(properties.get(105).unwrap().value(), 30);
+    assert_eq!(properties.get(127).unwrap().value(), 2);
+}
+
+#[test]
+fn property_list_write_rejects_invalid_values() {
+    let table = ItemStatCost::d2r();
+    let write = |property: Property| {
+        let list = PropertyList { properties: vec![property] };
+        list.write(&mut Vec::new(), &mut BytePosition::default(), table)
+    };
+
+    assert!(write(Property::new(105, 107)).is_ok());
+    assert!(write(Property::new(105, 108)).is_err());
+    assert!(write(Property::new(105, -21)).is_err());
+    assert!(write(Property::new(48, 10)).is_err());
+    assert!(write(Property::new(500, 1)).is_err());
+    assert!(write(Property { stat_id: 48, param: 0, values: vec![10, 20] }).is_ok());
+}
+
+#[test]
+fn classic_stat_cost_table_uses_110_widths() {
+    let classic = ItemStatCost::for_edition(crate::GameEdition::Classic);
+    let fire_resist = classic.by_name("fireresist").unwrap();
+    assert_eq!((fire_resist.value_bits, fire_resist.save_add), (8, 50));
+    assert_eq!(ItemStatCost::d2r().get(39).unwrap().value_bits, 9);
+    assert!(classic.get(187).is_none());
+    assert_eq!(classic.get(105), ItemStatCost::d2r().get(105));
+
+    // Stat id 39, then 30% stored as 80 in 8 bits, then the terminator.
+    let list = PropertyList { properties: vec![Property::new(39, 30)] };
+    let mut bytes = Vec::new();
+    list.write(&mut bytes, &mut BytePosition::default(), classic).unwrap();
+    let mut position = BytePosition::default();
+    assert_eq!(read_bits(&bytes, &mut position, STAT_ID_BITS).unwrap(), 39);
+    assert_eq!(read_bits(&bytes, &mut position, 8).unwrap(), 80);
+    assert_eq!(
+        read_bits(&bytes, &mut position, STAT_ID_BITS).unwrap(),
+        u32::from(PROPERTY_LIST_TERMINATOR)
+    );
+    assert!(list.write(&mut Vec::new(), &mut BytePosition::default(), ItemStatCost::d2r()).is_ok());
+    let too_high = PropertyList { properties: vec![Property::new(39, 206)] };
+    assert!(too_high.write(&mut Vec::new(), &mut BytePosition::default(), classic).is_err());
+}
+
+#[test]
+fn items_with_resistances_convert_to_v96() {
+    let mut save = parse_fixture("Ayame");
+    let has_resistances = |save: &Save| {
+        save.items.player.items.iter().filter_map(|item| item.extended.as_ref()).any(|extended| {
+            [39, 41, 43, 45].iter().any(|stat_id| extended.properties.get(*stat_id).is_some())
+        })
+    };
+    assert!(has_resistances(&save));
+
+    // 1.10 resistances cannot go below -50, and the sunder charm stats do not exist.
+    let error = save.encode_for(FormatId::V96, crate::CompatibilityChecks::Ignore).unwrap_err();
+    assert!(error.message.contains("lightresist"), "{error}");
+    let classic = ItemStatCost::classic();
+    for item in &mut save.items.player.items {
+        if let Some(extended) = item.extended.as_mut() {
+            extended.properties.properties.retain(|property| {
+                classic.get(property.stat_id).is_some() && property.value() >= -50
+            });
+        }
+    }
+
+    let v96 = save.encode_for(FormatId::V96, crate::CompatibilityChecks::Ignore);
+    let v96 = Save::parse(&v96.unwrap(), Strictness::Strict).unwrap().save;
+    assert_eq!(v96.format(), FormatId::V96);
+    assert!(has_resistances(&v96));
+    let properties = |save: &Save| -> Vec<PropertyList> {
+        save.items
+            .player
+            .items
+            .iter()
+            .filter_map(|item| item.extended.as_ref())
+            .map(|extended| extended.properties.clone())
+            .collect()
+    };
+    assert_eq!(properties(&v96), properties(&save));
+}
+
+#[test]
+fn classic_simple_items_store_three_socket_count_bits() {
+    let save = parse_fixture("Ayame");
+    let mut simple =
+        save.items.player.items.iter().find(|item| item.extended.is_none()).unwrap().clone();
+    let gems: Vec<Item> = save
+        .items
+        .player
+        .items
+        .iter()
+        .filter(|item| item.extended.is_none())
+        .take(2)
+        .cloned()
+        .collect();
+    simple.socketed_items = gems;
+
+    // D2R simple items have a 1-bit socket count.
+    assert!(simple.write(&mut Vec::new(), FormatId::V99).is_err());
+
+    let mut bytes = Vec::new();
+    simple.write(&mut bytes, FormatId::V96).unwrap();
+    // 1.10: "JM", 32 flag bits, 10 version bits, location, a 32-bit code ending at bit 108,
+    // then the socket count in bits 108 to 110.
+    let mut socket_count_position = BytePosition { current_byte: 13, current_bit: 4 };
+    assert_eq!(read_bits(&bytes, &mut socket_count_position, 3).unwrap(), 2);
+    assert_eq!(bytes[14..16], ITEM_LIST_HEADER);
+
+    let parsed = Item::parse(&bytes, &mut BytePosition::default(), FormatId::V96).unwrap();
+    let codes: Vec<&str> = parsed.socketed_items.iter().map(|item| item.code.as_str()).collect();
+    assert_eq!(codes, ["r11", "mp5"]);
+    let mut rewritten = Vec::new();
+    parsed.write(&mut rewritten, FormatId::V96).unwrap();
+    assert_eq!(rewritten, bytes);
+}
@@ -15,6 +15,7 @@
 //! so saves can round-trip through the library.
 //!
 //! Supported top-level save layouts:
+//! - [`format::FormatId::V96`] (Diablo II 1.10–1.14d)
 //! - [`format::FormatId::V99`]
 //! - [`format::FormatId::V105`]
 //!
@@ -48,7 +49,7 @@ use std::fmt;
 
 use attributes::Attributes;
 use character::Character;
-use npcs::Placeholder as NPCs;
+use npcs::Npcs;
 use quests::Quests;
 use skills::SkillPoints;
 use waypoints::Waypoints;
@@ -57,16 +58,31 @@ use waypoints::Waypoints;
 pub mod attributes;
 /// Character section model and per-format codecs.
 pub mod character;
+/// Class conversion across skills, bindings, appearance and base stats.
+pub mod class_change;
+/// Game data tables loaded from mod `.txt` files.
+pub mod data;
+/// Field-level comparison between two saves.
+pub mod diff;
+/// Versioned JSON document format for full saves.
+#[cfg(feature = "json")]
+pub mod document;
 /// Save-layout detection and top-level encode/decode glue.
 pub mod format;
-/// Item section placeholder/raw-preserving support.
+/// Item section model and bit-level item codec.
 pub mod items;
-/// NPC section placeholder/raw-preserving support.
+/// NPC introduction/congratulation section model.
 pub mod npcs;
+/// Act and difficulty completion across quests, waypoints and progression.
+pub mod progression;
 /// Quest section model.
 pub mod quests;
+/// Skill and stat resets and derived life, mana and stamina.
+pub mod respec;
 /// Skill section model and default D2R name mapping.
 pub mod skills;
+/// Shared stash (`.d2i`) model and codec.
+pub mod stash;
 /// Internal byte utilities shared across sections.
 pub mod utils;
 /// Backend-owned save validation rules and issue model.
@@ -106,6 +122,8 @@ impl ExpansionType {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameEdition {
+    /// Original Diablo II and Lord of Destruction, up to 1.14d.
+    Classic,
     D2RLegacy,
     RotW,
 }
@@ -113,6 +131,7 @@ pub enum GameEdition {
 impl GameEdition {
     pub fn label(self) -> &'static str {
         match self {
+            GameEdition::Classic => "Diablo II",
             GameEdition::D2RLegacy => "D2R Legacy",
             GameEdition::RotW => "RotW",
         }
@@ -175,9 +194,9 @@ impl std::error::Error for ParseHardError {}
 pub struct ParsedSave {
     /// Parsed save model (possibly partial in lax mode).
     pub save: Save,
-    /// Format detected from header version bytes (`V99`, `V105`, or `Unknown(n)`).
+    /// Format detected from header version bytes (`V96`, `V99`, `V105`, ...).
     pub detected_format: FormatId,
-    /// Known layout actually used for decode (`V99` or `V105`).
+    /// Known layout actually used for decode (`V96`, `V99` or `V105`).
     pub decoded_layout: FormatId,
     /// Optional inferred edition hint for unknown/unsupported versions.
     pub edition_hint: Option<GameEdition>,
@@ -232,6 +251,82 @@ pub enum CompatibilityChecks {
     Ignore,
 }
 
+/// Controls what happens to unmodeled character bytes during encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RawRegions {
+    /// Keep the parsed character bytes when the target edition matches the source edition.
+    #[default]
+    Preserve,
+    /// Rebuild the character section from modeled fields, zeroing unmodeled bytes.
+    Regenerate,
+}
+
+/// Settings for [`Save::encode_for`].
+///
+/// Defaults match a plain `encode_for(format, CompatibilityChecks::Enforce)` call.
+/// A [`CompatibilityChecks`] value converts into the matching options.
+///
+/// ```rust
+/// use halbu::attributes::AttributeOmission;
+/// use halbu::{CompatibilityChecks, EncodeOptions};
+///
+/// let options = EncodeOptions::new()
+///     .compatibility_checks(CompatibilityChecks::Enforce)
+///     .refresh_last_played(true)
+///     .block_on_validation(true)
+///     .attribute_omission(AttributeOmission::OmitZeros);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct EncodeOptions {
+    compatibility_checks: CompatibilityChecks,
+    refresh_last_played: bool,
+    block_on_validation: bool,
+    attribute_omission: attributes::AttributeOmission,
+    raw_regions: RawRegions,
+}
+
+impl EncodeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether blocking compatibility issues stop the encode.
+    pub fn compatibility_checks(mut self, compatibility_checks: CompatibilityChecks) -> Self {
+        self.compatibility_checks = compatibility_checks;
+        self
+    }
+
+    /// Write the current time as the character's last played timestamp.
+    pub fn refresh_last_played(mut self, refresh_last_played: bool) -> Self {
+        self.refresh_last_played = refresh_last_played;
+        self
+    }
+
+    /// Run [`Save::validate`] first and fail on blocking validation issues.
+    pub fn block_on_validation(mut self, block_on_validation: bool) -> Self {
+        self.block_on_validation = block_on_validation;
+        self
+    }
+
+    /// Choose which attribute stats are written (see [`attributes::AttributeOmission`]).
+    pub fn attribute_omission(mut self, attribute_omission: attributes::AttributeOmission) -> Self {
+        self.attribute_omission = attribute_omission;
+        self
+    }
+
+    /// Choose whether unmodeled character bytes are kept or regenerated.
+    pub fn raw_regions(mut self, raw_regions: RawRegions) -> Self {
+        self.raw_regions = raw_regions;
+        self
+    }
+}
+
+impl From<CompatibilityChecks> for EncodeOptions {
+    fn from(compatibility_checks: CompatibilityChecks) -> Self {
+        Self::new().compatibility_checks(compatibility_checks)
+    }
+}
+
 /// Stable identifier for a save compatibility rule.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -241,7 +336,8 @@ pub enum CompatibilityCode {
     RotWExpansionRequiresRotWEdition,
     ExpansionClassRequiresExpansionMode,
     UnknownClassRequiresKnownTarget,
-    MercenaryHireStateToggleUnsupported,
+    ClassicNameRequiresShortAscii,
+    RawItemsRequireSourceEdition,
 }
 
 /// Compatibility finding for a specific target format.
@@ -273,14 +369,14 @@ pub struct Save {
     pub quests: Quests,
     /// Waypoints section.
     pub waypoints: Waypoints,
-    /// NPC section (placeholder model).
-    pub npcs: npcs::Placeholder,
+    /// NPC section.
+    pub npcs: Npcs,
     /// Attributes section.
     pub attributes: Attributes,
     /// Skills section.
     pub skills: SkillPoints,
-    /// Items section (placeholder model).
-    pub items: items::Placeholder,
+    /// Items section.
+    pub items: items::Items,
     /// Auxiliary metadata kept by this library.
     #[serde(default)]
     meta: SaveMeta,
@@ -297,10 +393,10 @@ impl Default for Save {
             character,
             quests: Quests::default(),
             waypoints: Waypoints::default(),
-            npcs: NPCs::default(),
+            npcs: Npcs::default(),
             attributes: Attributes::new_save_defaults(),
             skills: SkillPoints::default(),
-            items: items::Placeholder::default(),
+            items: items::Items::default(),
             meta: SaveMeta { format: FormatId::V99 },
         }
     }
@@ -322,7 +418,7 @@ impl fmt::Display for Save {
 impl Save {
     fn apply_expansion_type_for_format(&mut self, format: FormatId, expansion_type: ExpansionType) {
         self.expansion_type = expansion_type;
-        if format == FormatId::V99 {
+        if matches!(format.edition(), Some(GameEdition::Classic | GameEdition::D2RLegacy)) {
             self.character
                 .set_legacy_expansion_flag(!matches!(expansion_type, ExpansionType::Classic));
         } else if matches!(format, FormatId::V105 | FormatId::Unknown(_)) {
@@ -333,7 +429,7 @@ impl Save {
 
     /// Build a new blank save for a target format/class.
     ///
-    /// `V99` defaults to `Expansion`; `V105` and unknown formats default to `RotW`.
+    /// `V96` and `V99` default to `Expansion`; `V105` and unknown formats default to `RotW`.
     /// Call `set_expansion_type` afterward if you need `Classic`.
     pub fn new(format: FormatId, class: Class) -> Save {
         let mut character = Character::default_class(class);
@@ -341,7 +437,7 @@ impl Save {
         character.raw_section = Vec::new();
         let expansion_type = match format.edition() {
             Some(GameEdition::RotW) => ExpansionType::RotW,
-            Some(GameEdition::D2RLegacy) | None => ExpansionType::Expansion,
+            Some(GameEdition::Classic | GameEdition::D2RLegacy) | None => ExpansionType::Expansion,
         };
         character.set_legacy_expansion_flag(!matches!(expansion_type, ExpansionType::Classic));
 
@@ -351,14 +447,26 @@ impl Save {
             character,
             quests: Quests::default(),
             waypoints: Waypoints::default(),
-            npcs: NPCs::default(),
+            npcs: Npcs::default(),
             attributes: Attributes::new_save_defaults(),
             skills: SkillPoints::default(),
-            items: items::Placeholder::default(),
+            items: items::Items::default(),
             meta: SaveMeta { format },
         }
     }
 
+    /// [`Save::new`] with the class's base stats, life, mana and stamina from
+    /// [`data::ClassData::d2r`] instead of the generic defaults.
+    ///
+    /// Classes without a built-in table row keep the generic defaults.
+    pub fn new_with_class_stats(format: FormatId, class: Class) -> Save {
+        let mut save = Save::new(format, class);
+        if let Some(class_data) = data::ClassData::d2r(class) {
+            respec::respec_stats(&mut save, &class_data);
+        }
+        save
+    }
+
     pub fn format(&self) -> FormatId {
         self.meta.format
     }
@@ -384,6 +492,21 @@ impl Save {
         self.attributes.set_level(level);
     }
 
+    /// Set the level everywhere and move experience to that level's threshold.
+    ///
+    /// Levels outside `1..=99` leave experience unchanged.
+    pub fn set_level_with_experience(&mut self, level: u8) {
+        self.set_level(level);
+        if let Some(experience) = character::experience::experience_for_level(level) {
+            self.attributes.experience.value = experience;
+        }
+    }
+
+    /// Level reached with the stored experience, from the D2R experience table.
+    pub fn level_from_experience(&self) -> u8 {
+        character::experience::level_from_experience(self.attributes.experience.value)
+    }
+
     pub fn expansion_type(&self) -> ExpansionType {
         self.expansion_type
     }
@@ -406,6 +529,16 @@ impl Save {
         format::decode(byte_slice, strictness)
     }
 
+    /// Parse a save with attribute and item property widths from `game_data`, keeping
+    /// modded stats in [`attributes::Attributes::extra`].
+    pub fn parse_with(
+        byte_slice: &[u8],
+        strictness: Strictness,
+        game_data: &data::GameData,
+    ) -> Result<ParsedSave, ParseHardError> {
+        format::decode_with(byte_slice, strictness, Some(game_data))
+    }
+
     /// Summarize only top-level header + character fields.
     pub fn summarize(
         byte_slice: &[u8],
@@ -414,13 +547,50 @@ impl Save {
         format::summarize(byte_slice, strictness)
     }
 
-    /// Encode to a specific output format with an explicit compatibility policy.
+    /// Encode to a specific output format.
+    ///
+    /// `options` is either an [`EncodeOptions`] or a bare [`CompatibilityChecks`] policy.
     pub fn encode_for(
         &self,
         format: FormatId,
-        compatibility_checks: CompatibilityChecks,
+        options: impl Into<EncodeOptions>,
     ) -> Result<Vec<u8>, EncodeError> {
-        format::encode(self, format, compatibility_checks)
+        format::encode(self, format, options.into())
+    }
+
+    /// [`Save::encode_for`] with item property widths from `game_data`, for saves read
+    /// with [`Save::parse_with`].
+    pub fn encode_for_with(
+        &self,
+        game_data: &data::GameData,
+        format: FormatId,
+        options: impl Into<EncodeOptions>,
+    ) -> Result<Vec<u8>, EncodeError> {
+        format::encode_with(self, format, options.into(), Some(game_data))
+    }
+
+    /// Parse `bytes`, re-encode them to the same format and report every field whose bytes changed.
+    ///
+    /// An exact report proves the save survives a parse/encode cycle unchanged.
+    pub fn verify_roundtrip(bytes: &[u8]) -> Result<format::RoundtripReport, ParseHardError> {
+        format::verify_roundtrip(bytes)
+    }
+
+    /// List field-level changes from `self` to `other`.
+    pub fn diff(&self, other: &Save) -> diff::SaveDiff {
+        diff::diff(self, other)
+    }
+
+    /// Export the save as a versioned JSON document (see [`document`]).
+    #[cfg(feature = "json")]
+    pub fn to_json_document(&self) -> Result<String, EncodeError> {
+        document::to_json_document(self)
+    }
+
+    /// Import a save from a JSON document produced by [`Save::to_json_document`].
+    #[cfg(feature = "json")]
+    pub fn from_json_document(json: &str) -> Result<Save, ParseHardError> {
+        document::from_json_document(json)
     }
 
     /// Return compatibility findings for encoding this save to `target`.
@@ -437,6 +607,191 @@ impl Save {
     pub fn validate(&self) -> validation::ValidationReport {
         validation::build_validation_report(self)
     }
+
+    /// Validate the current save against mod tables instead of the built-in D2R constants.
+    pub fn validate_with(&self, game_data: &data::GameData) -> validation::ValidationReport {
+        validation::build_validation_report_with(self, Some(game_data))
+    }
+
+    /// Complete `act` in `difficulty` and every act before it, earlier difficulties included.
+    ///
+    /// Quests, waypoints and `character.progression` are updated together; see
+    /// [`progression`]. The current difficulty and act are not changed.
+    pub fn complete_act(
+        &mut self,
+        difficulty: Difficulty,
+        act: Act,
+    ) -> Result<(), progression::ProgressionError> {
+        progression::complete_act(self, difficulty, act)
+    }
+
+    /// Complete every act of `difficulty`, unlocking the next one.
+    pub fn complete_difficulty(&mut self, difficulty: Difficulty) {
+        progression::complete_difficulty(self, difficulty);
+    }
+
+    /// Clear quests and waypoints from `act` in `difficulty` onward and lower
+    /// `character.progression` to match.
+    ///
+    /// A current difficulty and act past that point are moved back to it.
+    pub fn reset_progress_from(
+        &mut self,
+        difficulty: Difficulty,
+        act: Act,
+    ) -> Result<(), progression::ProgressionError> {
+        progression::reset_progress_from(self, difficulty, act)
+    }
+
+    /// Mark `quest` completed with its reward collected, adding the reward's skill points,
+    /// stat points or maximum life. Does nothing if the reward is already granted.
+    ///
+    /// Prison of Ice's resistances come from the quest flag alone. See [`quests::QuestId::reward`].
+    pub fn grant_quest_reward(&mut self, difficulty: Difficulty, quest: quests::QuestId) {
+        respec::grant_quest_reward(self, difficulty, quest);
+    }
+
+    /// Undo [`Save::grant_quest_reward`], leaving the quest completed with its reward pending.
+    ///
+    /// Fails without changes when the reward's points are no longer unspent.
+    pub fn revoke_quest_reward(
+        &mut self,
+        difficulty: Difficulty,
+        quest: quests::QuestId,
+    ) -> Result<(), respec::QuestRewardError> {
+        respec::revoke_quest_reward(self, difficulty, quest)
+    }
+
+    /// Change the character's class, resetting what belonged to the previous class.
+    ///
+    /// Fails without changes when the class is not allowed by the save's format and
+    /// expansion type (see [`CompatibilityCode`]) or has no built-in class table row.
+    /// See [`class_change`] for what is reset.
+    pub fn change_class(
+        &mut self,
+        class: Class,
+        policy: class_change::ClassChangePolicy,
+    ) -> Result<class_change::ClassChangeReport, class_change::ClassChangeError> {
+        class_change::change_class(self, None, class, policy)
+    }
+
+    /// [`Save::change_class`] with the class and skill tables from `game_data`.
+    pub fn change_class_with(
+        &mut self,
+        game_data: &data::GameData,
+        class: Class,
+        policy: class_change::ClassChangePolicy,
+    ) -> Result<class_change::ClassChangeReport, class_change::ClassChangeError> {
+        class_change::change_class(self, Some(game_data), class, policy)
+    }
+
+    /// Bind a skill to a hotkey or mouse button, resolving class skills with the default
+    /// D2R skill ids.
+    ///
+    /// Fails without changes when the skill is in the class tree but has no points. Skills
+    /// outside the tree, such as Attack or item skills, are always accepted, and so is any
+    /// skill for a class with no known skill table.
+    pub fn bind_skill(
+        &mut self,
+        slot: character::SkillBindingSlot,
+        binding: character::SkillBinding,
+    ) -> Result<(), character::SkillBindingError> {
+        let skill_index = binding.class_slot_d2r(self.character.class);
+        self.bind_skill_at(slot, binding, skill_index)
+    }
+
+    /// [`Save::bind_skill`] with the skill table from `game_data`.
+    pub fn bind_skill_with(
+        &mut self,
+        game_data: &data::GameData,
+        slot: character::SkillBindingSlot,
+        binding: character::SkillBinding,
+    ) -> Result<(), character::SkillBindingError> {
+        let skill_index = binding.class_slot(game_data, self.character.class);
+        self.bind_skill_at(slot, binding, skill_index)
+    }
+
+    fn bind_skill_at(
+        &mut self,
+        slot: character::SkillBindingSlot,
+        binding: character::SkillBinding,
+        skill_index: Result<Option<usize>, skills::NamedSkillError>,
+    ) -> Result<(), character::SkillBindingError> {
+        let skill_index = match skill_index {
+            // Without a skill table there is no tree to check the binding against.
+            Err(skills::NamedSkillError::UnsupportedClass(_)) => None,
+            skill_index => skill_index?,
+        };
+        if let (character::SkillBinding::Skill(skill_id), Some(skill_index)) =
+            (binding, skill_index)
+        {
+            if self.skills.points.get(skill_index).is_none_or(|points| *points == 0) {
+                return Err(character::SkillBindingError::NoSkillPoints {
+                    slot,
+                    skill_id,
+                    skill_index,
+                });
+            }
+        }
+        self.character.set_binding(slot, binding)
+    }
+
+    /// Clear all skills and refund every point earned from levels and quest rewards
+    /// into `newskills`, as Akara's respec does. See [`respec`] for the rewards counted.
+    pub fn respec_skills(&mut self) -> Result<(), respec::RespecError> {
+        let class_data = respec::class_data(None, self.character.class)?;
+        respec::respec_skills(self, &class_data);
+        Ok(())
+    }
+
+    /// [`Save::respec_skills`] with the class table from `game_data`.
+    pub fn respec_skills_with(
+        &mut self,
+        game_data: &data::GameData,
+    ) -> Result<(), respec::RespecError> {
+        let class_data = respec::class_data(Some(game_data), self.character.class)?;
+        respec::respec_skills(self, &class_data);
+        Ok(())
+    }
+
+    /// Reset strength, dexterity, vitality and energy to the class base values and refund
+    /// every point earned from levels and Lam Esen's Tome into `statpts`.
+    ///
+    /// Maximum life, mana and stamina are recomputed and the current values refilled.
+    pub fn respec_stats(&mut self) -> Result<(), respec::RespecError> {
+        let class_data = respec::class_data(None, self.character.class)?;
+        respec::respec_stats(self, &class_data);
+        Ok(())
+    }
+
+    /// [`Save::respec_stats`] with the class table from `game_data`.
+    pub fn respec_stats_with(
+        &mut self,
+        game_data: &data::GameData,
+    ) -> Result<(), respec::RespecError> {
+        let class_data = respec::class_data(Some(game_data), self.character.class)?;
+        respec::respec_stats(self, &class_data);
+        Ok(())
+    }
+
+    /// Set maximum life, mana and stamina from the class table, level and base stats.
+    ///
+    /// Values are stored in Q8, without item bonuses. Current values above the new
+    /// maximums are lowered to them.
+    pub fn recompute_vitals(&mut self) -> Result<(), respec::RespecError> {
+        let class_data = respec::class_data(None, self.character.class)?;
+        respec::set_max_vitals(self, &class_data);
+        Ok(())
+    }
+
+    /// [`Save::recompute_vitals`] with the class table from `game_data`.
+    pub fn recompute_vitals_with(
+        &mut self,
+        game_data: &data::GameData,
+    ) -> Result<(), respec::RespecError> {
+        let class_data = respec::class_data(Some(game_data), self.character.class)?;
+        respec::set_max_vitals(self, &class_data);
+        Ok(())
+    }
 }
 
 /// Save encoding error.
@@ -645,6 +1000,39 @@ mod tests {
         assert_eq!(save.attributes.level(), 75);
     }
 
+    #[test]
+    fn bind_skill_rejects_class_skills_without_points() {
+        use character::{SkillBinding, SkillBindingError, SkillBindingSlot};
+
+        let mut save = Save::new(FormatId::V99, Class::Sorceress);
+        let blizzard = SkillBinding::Skill(59);
+        assert_eq!(
+            save.bind_skill(SkillBindingSlot::Hotkey(0), blizzard),
+            Err(SkillBindingError::NoSkillPoints {
+                slot: SkillBindingSlot::Hotkey(0),
+                skill_id: 59,
+                skill_index: 23
+            })
+        );
+        assert_eq!(save.character.assigned_skills[0], character::UNASSIGNED_SKILL);
+
+        save.skills.set(23, 1);
+        save.bind_skill(SkillBindingSlot::Hotkey(0), blizzard).unwrap();
+        // Battle Orders from an item and Attack are outside the tree.
+        save.bind_skill(SkillBindingSlot::Hotkey(1), SkillBinding::Skill(149)).unwrap();
+        save.bind_skill(SkillBindingSlot::LeftMouse, SkillBinding::Skill(0)).unwrap();
+        assert_eq!(&save.character.assigned_skills[..2], [59, 149]);
+
+        // The Warlock and unknown classes have no built-in skill ids, so nothing is checked.
+        save.character.class = Class::Warlock;
+        save.bind_skill(SkillBindingSlot::RightMouse, SkillBinding::Skill(395)).unwrap();
+        save.bind_skill(SkillBindingSlot::Hotkey(2), blizzard).unwrap();
+        save.character.class = Class::Unknown(0xFF);
+        save.bind_skill(SkillBindingSlot::Hotkey(3), blizzard).unwrap();
+        save.bind_skill(SkillBindingSlot::Hotkey(4), SkillBinding::Skill(0)).unwrap();
+        assert_eq!(&save.character.assigned_skills[2..5], [59, 59, 0]);
+    }
+
     #[test]
     fn new_save_sets_default_expansion_type_by_edition() {
         let v99 = Save::new(FormatId::V99, Class::Amazon);
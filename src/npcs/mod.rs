@@ -1,58 +1,347 @@
-//! NPC section placeholder support.
+//! NPC introduction/congratulation section.
 //!
-//! NPC bytes are preserved as a fixed-size raw payload with header validation.
+//! The section stores two 64-bit fields per difficulty: which NPCs already greeted the
+//! character ("introduced") and which NPCs congratulated the character after a quest
+//! ("congratulated"). Bits that are not mapped to a known [`Npc`] are preserved as-is.
 
-use crate::ParseHardError;
+use std::fmt;
+use std::ops::Range;
+
+use bit::BitIndex;
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, Bytes};
+use serde_with::serde_as;
+
+use crate::utils::HexU64;
+use crate::{Act, Difficulty, ParseHardError};
 
+#[cfg(test)]
+mod tests;
+
+const SECTION_LENGTH: usize = 52;
 const SECTION_HEADER: [u8; 4] = [0x01, 0x77, 0x34, 0x00];
 
+enum Section {
+    Header,
+    IntroducedNormal,
+    IntroducedNightmare,
+    IntroducedHell,
+    CongratulatedNormal,
+    CongratulatedNightmare,
+    CongratulatedHell,
+}
+
+impl Section {
+    const fn range(self) -> Range<usize> {
+        match self {
+            Section::Header => 0..4,
+            Section::IntroducedNormal => 4..12,
+            Section::IntroducedNightmare => 12..20,
+            Section::IntroducedHell => 20..28,
+            Section::CongratulatedNormal => 28..36,
+            Section::CongratulatedNightmare => 36..44,
+            Section::CongratulatedHell => 44..52,
+        }
+    }
+}
+
+/// Named byte spans of the NPC section, relative to its start.
+pub(crate) fn field_spans() -> Vec<(Range<usize>, String)> {
+    [
+        (Section::Header, "header"),
+        (Section::IntroducedNormal, "introduced.normal"),
+        (Section::IntroducedNightmare, "introduced.nightmare"),
+        (Section::IntroducedHell, "introduced.hell"),
+        (Section::CongratulatedNormal, "congratulated.normal"),
+        (Section::CongratulatedNightmare, "congratulated.nightmare"),
+        (Section::CongratulatedHell, "congratulated.hell"),
+    ]
+    .into_iter()
+    .map(|(section, name)| (section.range(), name.to_string()))
+    .collect()
+}
+
+/// Town NPC tracked by the introduction/congratulation bitfields.
+///
+/// The discriminant is the bit index inside each 64-bit difficulty field.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, Serialize, Deserialize)]
+pub enum Npc {
+    WarrivAct2 = 0,
+    Charsi = 2,
+    WarrivAct1 = 3,
+    Kashya = 4,
+    Akara = 5,
+    Gheed = 6,
+    Greiz = 8,
+    Jerhyn = 9,
+    MeshifAct2 = 10,
+    Geglash = 11,
+    Lysander = 12,
+    Fara = 13,
+    Drogan = 14,
+    Alkor = 16,
+    Hratli = 17,
+    Ashera = 18,
+    CainAct3 = 21,
+    Elzix = 23,
+    Malah = 24,
+    Anya = 25,
+    Natalya = 27,
+    MeshifAct3 = 28,
+    Ormus = 31,
+    CainAct5 = 37,
+    QualKehk = 38,
+    Nihlathak = 39,
+}
+
+impl Npc {
+    /// Every NPC with a known bit, in bit order.
+    pub const ALL: [Npc; 26] = [
+        Npc::WarrivAct2,
+        Npc::Charsi,
+        Npc::WarrivAct1,
+        Npc::Kashya,
+        Npc::Akara,
+        Npc::Gheed,
+        Npc::Greiz,
+        Npc::Jerhyn,
+        Npc::MeshifAct2,
+        Npc::Geglash,
+        Npc::Lysander,
+        Npc::Fara,
+        Npc::Drogan,
+        Npc::Alkor,
+        Npc::Hratli,
+        Npc::Ashera,
+        Npc::CainAct3,
+        Npc::Elzix,
+        Npc::Malah,
+        Npc::Anya,
+        Npc::Natalya,
+        Npc::MeshifAct3,
+        Npc::Ormus,
+        Npc::CainAct5,
+        Npc::QualKehk,
+        Npc::Nihlathak,
+    ];
+
+    /// Bit index inside a difficulty field.
+    pub const fn bit(self) -> usize {
+        self as usize
+    }
+
+    /// Display name of the NPC.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Npc::WarrivAct2 | Npc::WarrivAct1 => "Warriv",
+            Npc::Charsi => "Charsi",
+            Npc::Kashya => "Kashya",
+            Npc::Akara => "Akara",
+            Npc::Gheed => "Gheed",
+            Npc::Greiz => "Greiz",
+            Npc::Jerhyn => "Jerhyn",
+            Npc::MeshifAct2 | Npc::MeshifAct3 => "Meshif",
+            Npc::Geglash => "Geglash",
+            Npc::Lysander => "Lysander",
+            Npc::Fara => "Fara",
+            Npc::Drogan => "Drogan",
+            Npc::Alkor => "Alkor",
+            Npc::Hratli => "Hratli",
+            Npc::Ashera => "Ashera",
+            Npc::CainAct3 | Npc::CainAct5 => "Deckard Cain",
+            Npc::Elzix => "Elzix",
+            Npc::Malah => "Malah",
+            Npc::Anya => "Anya",
+            Npc::Natalya => "Natalya",
+            Npc::Ormus => "Ormus",
+            Npc::QualKehk => "Qual-Kehk",
+            Npc::Nihlathak => "Nihlathak",
+        }
+    }
+
+    /// Act whose town the NPC is met in.
+    pub const fn act(self) -> Act {
+        match self {
+            Npc::WarrivAct1 | Npc::Charsi | Npc::Kashya | Npc::Akara | Npc::Gheed => Act::Act1,
+            Npc::WarrivAct2
+            | Npc::Greiz
+            | Npc::Jerhyn
+            | Npc::MeshifAct2
+            | Npc::Geglash
+            | Npc::Lysander
+            | Npc::Fara
+            | Npc::Drogan
+            | Npc::Elzix => Act::Act2,
+            Npc::Alkor
+            | Npc::Hratli
+            | Npc::Ashera
+            | Npc::CainAct3
+            | Npc::MeshifAct3
+            | Npc::Natalya
+            | Npc::Ormus => Act::Act3,
+            Npc::Malah | Npc::Anya | Npc::CainAct5 | Npc::QualKehk | Npc::Nihlathak => Act::Act5,
+        }
+    }
+}
+
+impl fmt::Display for Npc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name(), self.act())
+    }
+}
+
+/// Introduction and congratulation bitfields for one difficulty.
+///
+/// Both fields serialize as hex strings in human-readable formats.
 #[serde_as]
-#[derive(PartialEq, Eq, Debug, Copy, Clone, Serialize, Deserialize)]
-pub struct Placeholder {
-    #[serde_as(as = "Bytes")]
-    data: [u8; 52],
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub struct DifficultyNpcs {
+    /// Raw introduction bits, one per [`Npc::bit`].
+    #[serde_as(as = "HexU64")]
+    pub introduced: u64,
+    /// Raw congratulation bits, one per [`Npc::bit`].
+    #[serde_as(as = "HexU64")]
+    pub congratulated: u64,
 }
 
-impl Default for Placeholder {
-    fn default() -> Self {
-        let mut placeholder = Placeholder { data: [0x00; 52] };
-        placeholder.data[0..4].copy_from_slice(&SECTION_HEADER);
-        placeholder
+impl DifficultyNpcs {
+    /// Whether the NPC already introduced itself.
+    pub fn is_introduced(&self, npc: Npc) -> bool {
+        self.introduced.bit(npc.bit())
+    }
+
+    pub fn set_introduced(&mut self, npc: Npc, value: bool) {
+        self.introduced.set_bit(npc.bit(), value);
+    }
+
+    /// Whether the NPC already congratulated the character.
+    pub fn is_congratulated(&self, npc: Npc) -> bool {
+        self.congratulated.bit(npc.bit())
+    }
+
+    pub fn set_congratulated(&mut self, npc: Npc, value: bool) {
+        self.congratulated.set_bit(npc.bit(), value);
+    }
+
+    /// Set introduction and congratulation state for every known NPC of one act.
+    ///
+    /// Unmapped bits are left untouched.
+    pub fn set_act(&mut self, act: Act, value: bool) {
+        for npc in Npc::ALL.into_iter().filter(|npc| npc.act() == act) {
+            self.set_introduced(npc, value);
+            self.set_congratulated(npc, value);
+        }
+    }
+
+    /// Set introduction and congratulation state for every known NPC.
+    ///
+    /// Unmapped bits are left untouched.
+    pub fn set_all(&mut self, value: bool) {
+        for npc in Npc::ALL {
+            self.set_introduced(npc, value);
+            self.set_congratulated(npc, value);
+        }
     }
 }
 
-impl Placeholder {
+/// NPC section model.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub struct Npcs {
+    pub normal: DifficultyNpcs,
+    pub nightmare: DifficultyNpcs,
+    pub hell: DifficultyNpcs,
+}
+
+impl Npcs {
+    /// Borrow the NPC state of one difficulty.
+    pub fn difficulty(&self, difficulty: Difficulty) -> &DifficultyNpcs {
+        match difficulty {
+            Difficulty::Normal => &self.normal,
+            Difficulty::Nightmare => &self.nightmare,
+            Difficulty::Hell => &self.hell,
+        }
+    }
+
+    /// Mutably borrow the NPC state of one difficulty.
+    pub fn difficulty_mut(&mut self, difficulty: Difficulty) -> &mut DifficultyNpcs {
+        match difficulty {
+            Difficulty::Normal => &mut self.normal,
+            Difficulty::Nightmare => &mut self.nightmare,
+            Difficulty::Hell => &mut self.hell,
+        }
+    }
+
+    pub fn is_introduced(&self, difficulty: Difficulty, npc: Npc) -> bool {
+        self.difficulty(difficulty).is_introduced(npc)
+    }
+
+    pub fn set_introduced(&mut self, difficulty: Difficulty, npc: Npc, value: bool) {
+        self.difficulty_mut(difficulty).set_introduced(npc, value);
+    }
+
+    pub fn is_congratulated(&self, difficulty: Difficulty, npc: Npc) -> bool {
+        self.difficulty(difficulty).is_congratulated(npc)
+    }
+
+    pub fn set_congratulated(&mut self, difficulty: Difficulty, npc: Npc, value: bool) {
+        self.difficulty_mut(difficulty).set_congratulated(npc, value);
+    }
+
     /// Parse and validate NPC section bytes.
-    pub fn parse(bytes: &[u8]) -> Result<Placeholder, ParseHardError> {
-        if bytes.len() < 52 {
+    pub fn parse(bytes: &[u8]) -> Result<Npcs, ParseHardError> {
+        if bytes.len() < SECTION_LENGTH {
             return Err(ParseHardError {
                 message: format!(
-                    "NPC section is truncated: expected 52 bytes, found {}.",
+                    "NPC section is truncated: expected {SECTION_LENGTH} bytes, found {}.",
                     bytes.len()
                 ),
             });
         }
 
-        if bytes[0..4] != SECTION_HEADER {
+        if bytes[Section::Header.range()] != SECTION_HEADER {
             return Err(ParseHardError {
                 message: format!(
                     "Found wrong header for NPC section, expected {SECTION_HEADER:X?} but found {:X?}.",
-                    &bytes[0..4]
+                    &bytes[Section::Header.range()]
                 ),
             });
         }
-        let mut placeholder: Placeholder = Placeholder { data: [0x00; 52] };
-        placeholder.data.copy_from_slice(&bytes[0..52]);
 
-        Ok(placeholder)
+        let read = |section: Section| {
+            let mut field = [0u8; 8];
+            field.copy_from_slice(&bytes[section.range()]);
+            u64::from_le_bytes(field)
+        };
+
+        Ok(Npcs {
+            normal: DifficultyNpcs {
+                introduced: read(Section::IntroducedNormal),
+                congratulated: read(Section::CongratulatedNormal),
+            },
+            nightmare: DifficultyNpcs {
+                introduced: read(Section::IntroducedNightmare),
+                congratulated: read(Section::CongratulatedNightmare),
+            },
+            hell: DifficultyNpcs {
+                introduced: read(Section::IntroducedHell),
+                congratulated: read(Section::CongratulatedHell),
+            },
+        })
     }
 
-    /// Serialize the NPC placeholder bytes back to the save.
-    pub fn to_bytes(&self) -> [u8; 52] {
-        let mut bytes: [u8; 52] = [0x00; 52];
-        bytes.copy_from_slice(&self.data[0..52]);
+    /// Serialize the NPC section back to the save.
+    pub fn to_bytes(&self) -> [u8; SECTION_LENGTH] {
+        let mut bytes = [0x00; SECTION_LENGTH];
+        bytes[Section::Header.range()].copy_from_slice(&SECTION_HEADER);
+        for (section, value) in [
+            (Section::IntroducedNormal, self.normal.introduced),
+            (Section::IntroducedNightmare, self.nightmare.introduced),
+            (Section::IntroducedHell, self.hell.introduced),
+            (Section::CongratulatedNormal, self.normal.congratulated),
+            (Section::CongratulatedNightmare, self.nightmare.congratulated),
+            (Section::CongratulatedHell, self.hell.congratulated),
+        ] {
+            bytes[section.range()].copy_from_slice(&value.to_le_bytes());
+        }
 
         bytes
     }
@@ -0,0 +1,56 @@
+use crate::npcs::*;
+use crate::{Act, Difficulty};
+
+const AYAME_NPC_BYTES: [u8; 52] = [
+    0x01, 0x77, 0x34, 0x00, 0x2E, 0xAE, 0xA5, 0xD9, 0x03, 0x00, 0x00, 0x00, 0x2C, 0xAE, 0xA4, 0xC9,
+    0x02, 0x00, 0x00, 0x00, 0x2E, 0xA6, 0xA4, 0xC9, 0x02, 0x00, 0x00, 0x00, 0xFE, 0x7F, 0xE7, 0x19,
+    0x00, 0x00, 0x00, 0x00, 0xFE, 0xFF, 0xE7, 0x19, 0x00, 0x00, 0x00, 0x00, 0xA0, 0x7B, 0xE7, 0x19,
+    0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn parse_npcs_reads_expected_flags() {
+    let npcs = Npcs::parse(&AYAME_NPC_BYTES).expect("NPC payload should parse in test fixture.");
+
+    assert_eq!(npcs.normal.introduced, 0x03_D9A5_AE2E);
+    assert!(npcs.is_introduced(Difficulty::Normal, Npc::Akara));
+    assert!(npcs.is_introduced(Difficulty::Normal, Npc::Charsi));
+    assert!(!npcs.is_introduced(Difficulty::Normal, Npc::Kashya));
+    assert!(!npcs.is_introduced(Difficulty::Nightmare, Npc::WarrivAct2));
+    assert!(npcs.is_congratulated(Difficulty::Hell, Npc::Malah));
+    assert!(!npcs.is_congratulated(Difficulty::Hell, Npc::Charsi));
+}
+
+#[test]
+fn npcs_round_trip_preserves_unmapped_bits() {
+    let npcs = Npcs::parse(&AYAME_NPC_BYTES).expect("NPC payload should parse in test fixture.");
+    assert_eq!(npcs.to_bytes(), AYAME_NPC_BYTES);
+
+    let mut reset = npcs;
+    reset.normal.set_all(false);
+    assert!(Npc::ALL.iter().all(|npc| !reset.is_introduced(Difficulty::Normal, *npc)));
+    assert_eq!(reset.normal.introduced, 0x03_4000_8002);
+    assert_eq!(reset.nightmare, npcs.nightmare);
+}
+
+#[test]
+fn set_act_only_touches_that_act() {
+    let mut npcs = Npcs::default();
+    npcs.difficulty_mut(Difficulty::Hell).set_act(Act::Act5, true);
+
+    assert!(npcs.is_introduced(Difficulty::Hell, Npc::QualKehk));
+    assert!(npcs.is_congratulated(Difficulty::Hell, Npc::CainAct5));
+    assert!(!npcs.is_introduced(Difficulty::Hell, Npc::CainAct3));
+    assert_eq!(npcs.normal, DifficultyNpcs::default());
+
+    let reparsed = Npcs::parse(&npcs.to_bytes()).unwrap();
+    assert_eq!(reparsed, npcs);
+}
+
+#[test]
+fn parse_npcs_rejects_wrong_header() {
+    let mut bytes = AYAME_NPC_BYTES;
+    bytes[1] = 0x00;
+    assert!(Npcs::parse(&bytes).is_err());
+    assert!(Npcs::parse(&AYAME_NPC_BYTES[..40]).is_err());
+}
@@ -0,0 +1,240 @@
+//! Act and difficulty completion kept consistent across quests, waypoints and progression.
+//!
+//! Completing an act marks its quests, prologue and completion slot as done, acquires all
+//! of its waypoints plus the next act's town, and raises `character.progression` to the
+//! number of acts completed in order. The title follows from progression. Completing the
+//! last act of a difficulty (Act V, or Act IV for classic) unlocks the next difficulty.
+//!
+//! Quests that already count as completed keep their flags. Act V's completion slot only
+//! gains `CompletedBefore`, so the Akara reset flags stored there are left alone.
+//!
+//! Quest rewards are applied as quests are completed and taken back as they are cleared,
+//! as with [`crate::Save::grant_quest_reward`]. Points already spent are not refunded by a
+//! reset, so unspent points stop at zero.
+
+use std::fmt;
+
+use crate::quests::{DifficultyQuests, Quest, QuestFlag, QuestId, QuestState};
+use crate::respec;
+use crate::waypoints::DifficultyWaypoints;
+use crate::{Act, Difficulty, ExpansionType, Save};
+
+#[cfg(test)]
+mod tests;
+
+const DIFFICULTIES: [Difficulty; 3] = [Difficulty::Normal, Difficulty::Nightmare, Difficulty::Hell];
+const ACTS: [Act; 5] = [Act::Act1, Act::Act2, Act::Act3, Act::Act4, Act::Act5];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressionError {
+    /// The act does not exist for the save's expansion type.
+    ActUnavailable { act: Act, expansion_type: ExpansionType },
+}
+
+impl fmt::Display for ProgressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ActUnavailable { act, expansion_type } => {
+                write!(f, "{act} is not available in {} saves", expansion_type.label())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProgressionError {}
+
+fn act_count(expansion_type: ExpansionType) -> usize {
+    if expansion_type == ExpansionType::Classic {
+        4
+    } else {
+        5
+    }
+}
+
+fn difficulty_index(difficulty: Difficulty) -> usize {
+    match difficulty {
+        Difficulty::Normal => 0,
+        Difficulty::Nightmare => 1,
+        Difficulty::Hell => 2,
+    }
+}
+
+/// Every (difficulty, act) pair of the save's expansion type, in play order.
+fn positions(expansion_type: ExpansionType) -> Vec<(Difficulty, Act)> {
+    DIFFICULTIES
+        .into_iter()
+        .flat_map(|difficulty| {
+            ACTS[..act_count(expansion_type)].iter().map(move |act| (difficulty, *act))
+        })
+        .collect()
+}
+
+fn position_index(expansion_type: ExpansionType, difficulty: Difficulty, act: Act) -> usize {
+    difficulty_index(difficulty) * act_count(expansion_type) + usize::from(u8::from(act))
+}
+
+fn check_act(save: &Save, act: Act) -> Result<(), ProgressionError> {
+    let expansion_type = save.expansion_type();
+    if usize::from(u8::from(act)) < act_count(expansion_type) {
+        Ok(())
+    } else {
+        Err(ProgressionError::ActUnavailable { act, expansion_type })
+    }
+}
+
+fn difficulty_waypoints_mut(save: &mut Save, difficulty: Difficulty) -> &mut DifficultyWaypoints {
+    match difficulty {
+        Difficulty::Normal => &mut save.waypoints.normal,
+        Difficulty::Nightmare => &mut save.waypoints.nightmare,
+        Difficulty::Hell => &mut save.waypoints.hell,
+    }
+}
+
+/// Prologue and completion slots of one act.
+fn act_frame_mut(quests: &mut DifficultyQuests, act: Act) -> (&mut Quest, &mut Quest) {
+    match act {
+        Act::Act1 => (&mut quests.act1.prologue, &mut quests.act1.completion),
+        Act::Act2 => (&mut quests.act2.prologue, &mut quests.act2.completion),
+        Act::Act3 => (&mut quests.act3.prologue, &mut quests.act3.completion),
+        Act::Act4 => (&mut quests.act4.prologue, &mut quests.act4.completion),
+        Act::Act5 => (&mut quests.act5.prologue, &mut quests.act5.completion),
+    }
+}
+
+fn set_act_waypoints(waypoints: &mut DifficultyWaypoints, act: Act, acquired: bool) {
+    match act {
+        Act::Act1 => waypoints.act1.set_all(acquired),
+        Act::Act2 => waypoints.act2.set_all(acquired),
+        Act::Act3 => waypoints.act3.set_all(acquired),
+        Act::Act4 => waypoints.act4.set_all(acquired),
+        Act::Act5 => waypoints.act5.set_all(acquired),
+    }
+}
+
+fn set_town_waypoint(waypoints: &mut DifficultyWaypoints, act: Act, acquired: bool) {
+    let result = match act {
+        Act::Act1 => waypoints.act1.set_by_index(0, acquired),
+        Act::Act2 => waypoints.act2.set_by_index(0, acquired),
+        Act::Act3 => waypoints.act3.set_by_index(0, acquired),
+        Act::Act4 => waypoints.act4.set_by_index(0, acquired),
+        Act::Act5 => waypoints.act5.set_by_index(0, acquired),
+    };
+    result.expect("every act has a town waypoint at index 0");
+}
+
+fn quest_completed(quest: &Quest) -> bool {
+    quest.state.contains(&QuestFlag::RewardGranted)
+        || quest.state.contains(&QuestFlag::CompletedBefore)
+}
+
+/// Whether the act's completion slot is set, using the markers validation checks.
+fn act_completed(save: &Save, difficulty: Difficulty, act: Act) -> bool {
+    let quests = save.quests.difficulty(difficulty);
+    let completion = match act {
+        Act::Act1 => &quests.act1.completion,
+        Act::Act2 => &quests.act2.completion,
+        Act::Act3 => &quests.act3.completion,
+        Act::Act4 => &quests.act4.completion,
+        Act::Act5 => &quests.act5.completion,
+    };
+    quest_completed(completion)
+}
+
+/// Number of acts completed in play order, the canonical `character.progression`.
+pub(crate) fn completed_progression(save: &Save) -> u8 {
+    positions(save.expansion_type())
+        .into_iter()
+        .take_while(|(difficulty, act)| act_completed(save, *difficulty, *act))
+        .count() as u8
+}
+
+fn complete_single_act(save: &mut Save, difficulty: Difficulty, act: Act) {
+    let last_act = act_count(save.expansion_type()) == usize::from(u8::from(act)) + 1;
+    for quest in QuestId::for_act(act) {
+        if !quest_completed(save.quests.get(difficulty, quest)) {
+            respec::grant_quest_reward(save, difficulty, quest);
+        }
+    }
+    let (prologue, completion) = act_frame_mut(save.quests.difficulty_mut(difficulty), act);
+    // Act V has no prologue reward; real saves leave the slot empty.
+    if act != Act::Act5 && prologue.state.is_empty() {
+        prologue.state.insert(QuestFlag::RewardGranted);
+    }
+    if act == Act::Act5 {
+        completion.state.insert(QuestFlag::CompletedBefore);
+    } else {
+        completion.state.insert(QuestFlag::RewardGranted);
+    }
+
+    let waypoints = difficulty_waypoints_mut(save, difficulty);
+    set_act_waypoints(waypoints, act, true);
+    if !last_act {
+        set_town_waypoint(waypoints, ACTS[usize::from(u8::from(act)) + 1], true);
+    }
+}
+
+/// Complete every act up to and including `act` in `difficulty`, earlier difficulties
+/// included.
+pub(crate) fn complete_act(
+    save: &mut Save,
+    difficulty: Difficulty,
+    act: Act,
+) -> Result<(), ProgressionError> {
+    check_act(save, act)?;
+    let expansion_type = save.expansion_type();
+    let end = position_index(expansion_type, difficulty, act);
+    for (difficulty, act) in positions(expansion_type).into_iter().take(end + 1) {
+        complete_single_act(save, difficulty, act);
+    }
+    save.character.progression = save.character.progression.max(completed_progression(save));
+    Ok(())
+}
+
+/// Complete every act of `difficulty` and of the difficulties before it.
+pub(crate) fn complete_difficulty(save: &mut Save, difficulty: Difficulty) {
+    let last_act = ACTS[act_count(save.expansion_type()) - 1];
+    complete_act(save, difficulty, last_act).expect("the last act exists for every expansion type");
+}
+
+/// Clear quests and waypoints from `act` in `difficulty` onward and move the current
+/// difficulty and act back to that point if they were past it.
+pub(crate) fn reset_progress_from(
+    save: &mut Save,
+    difficulty: Difficulty,
+    act: Act,
+) -> Result<(), ProgressionError> {
+    check_act(save, act)?;
+    let expansion_type = save.expansion_type();
+    let start = position_index(expansion_type, difficulty, act);
+    // The previous act's completion still grants this act's town.
+    let keep_start_town = act == Act::Act1
+        || start.checked_sub(1).is_some_and(|previous| {
+            let (difficulty, act) = positions(expansion_type)[previous];
+            act_completed(save, difficulty, act)
+        });
+
+    for (index, (difficulty, act)) in positions(expansion_type).into_iter().enumerate().skip(start)
+    {
+        for quest in QuestId::for_act(act) {
+            respec::set_quest_state(save, difficulty, quest, QuestState::NotStarted);
+        }
+        let (prologue, completion) = act_frame_mut(save.quests.difficulty_mut(difficulty), act);
+        *prologue = Quest::default();
+        *completion = Quest::default();
+
+        let waypoints = difficulty_waypoints_mut(save, difficulty);
+        set_act_waypoints(waypoints, act, false);
+        // Rogue Encampment is always available.
+        if act == Act::Act1 || (index == start && keep_start_town) {
+            set_town_waypoint(waypoints, act, true);
+        }
+    }
+
+    let character = &save.character;
+    if position_index(expansion_type, character.difficulty, character.act) > start {
+        save.character.difficulty = difficulty;
+        save.character.act = act;
+    }
+    save.character.progression = save.character.progression.min(completed_progression(save));
+    Ok(())
+}
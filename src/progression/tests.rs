@@ -0,0 +1,83 @@
+use super::*;
+use crate::format::FormatId;
+use crate::quests::Quests;
+use crate::waypoints::{Waypoint, Waypoints};
+use crate::Class;
+
+fn hell_ready(expansion_type: ExpansionType) -> Save {
+    let mut save = Save::new(FormatId::V99, Class::Sorceress);
+    save.set_expansion_type(expansion_type);
+    save.complete_difficulty(Difficulty::Nightmare);
+    save.character.difficulty = Difficulty::Hell;
+    save
+}
+
+#[test]
+fn complete_difficulty_makes_hell_selectable_without_issues() {
+    let save = hell_ready(ExpansionType::Expansion);
+
+    assert!(save.validate().issues.is_empty(), "{:?}", save.validate().issues);
+    assert_eq!(save.character.progression, 10);
+    assert_eq!(save.title_d2r(), Some("Champion"));
+    // Den of Evil, Radament and Izual, then Lam Esen, in two difficulties.
+    assert_eq!((save.attributes.newskills.value, save.attributes.statpts.value), (8, 10));
+    assert!(save.quests.nightmare.act5.completion.state.contains(&QuestFlag::CompletedBefore));
+    assert!(save.waypoints.nightmare.act5.get(Waypoint::WorldstoneKeep).unwrap());
+    assert!(save.quests.hell.act1.q1.state.is_empty());
+
+    let classic = hell_ready(ExpansionType::Classic);
+    assert!(classic.validate().issues.is_empty(), "{:?}", classic.validate().issues);
+    assert_eq!(classic.character.progression, 8);
+    assert!(classic.quests.nightmare.act5.completion.state.is_empty());
+}
+
+#[test]
+fn complete_act_fills_earlier_acts_and_opens_the_next_town() {
+    let mut save = Save::new(FormatId::V99, Class::Sorceress);
+    save.complete_act(Difficulty::Normal, Act::Act3).unwrap();
+
+    assert_eq!(save.character.progression, 3);
+    assert!(save.quests.normal.act1.q1.state.contains(&QuestFlag::RewardGranted));
+    assert!(save.waypoints.normal.act2.get(Waypoint::LostCity).unwrap());
+    assert!(save.waypoints.normal.act4.get(Waypoint::PandemoniumFortress).unwrap());
+    assert!(!save.waypoints.normal.act4.get(Waypoint::CityOfTheDamned).unwrap());
+
+    save.character.act = Act::Act4;
+    assert!(save.validate().issues.is_empty());
+
+    // Flags of a quest that is already done are kept.
+    save.quests.normal.act1.q2 = Quest::from(0x101d);
+    save.complete_act(Difficulty::Normal, Act::Act4).unwrap();
+    assert_eq!(save.quests.normal.act1.q2.value(), 0x101d);
+
+    save.set_expansion_type(ExpansionType::Classic);
+    assert_eq!(
+        save.complete_act(Difficulty::Normal, Act::Act5),
+        Err(ProgressionError::ActUnavailable {
+            act: Act::Act5,
+            expansion_type: ExpansionType::Classic
+        })
+    );
+}
+
+#[test]
+fn reset_progress_from_rolls_back_selection_and_progression() {
+    let mut save = hell_ready(ExpansionType::Expansion);
+    save.reset_progress_from(Difficulty::Nightmare, Act::Act3).unwrap();
+
+    assert_eq!((save.character.difficulty, save.character.act), (Difficulty::Nightmare, Act::Act3));
+    assert_eq!(save.character.progression, 7);
+    assert_eq!((save.attributes.newskills.value, save.attributes.statpts.value), (6, 5));
+    assert!(save.validate().issues.is_empty(), "{:?}", save.validate().issues);
+    assert!(save.quests.nightmare.act3.q1.state.is_empty());
+    assert!(save.quests.nightmare.act2.completion.state.contains(&QuestFlag::RewardGranted));
+    assert!(save.waypoints.nightmare.act3.get(Waypoint::KurastDocks).unwrap());
+    assert!(!save.waypoints.nightmare.act3.get(Waypoint::SpiderForest).unwrap());
+    assert!(!save.waypoints.nightmare.act5.get(Waypoint::Harrogath).unwrap());
+    assert!(save.waypoints.hell.act1.get(Waypoint::RogueEncampment).unwrap());
+
+    save.reset_progress_from(Difficulty::Normal, Act::Act1).unwrap();
+    assert_eq!(save.quests, Quests::default());
+    assert_eq!(save.waypoints, Waypoints::default());
+    assert_eq!(save.character.progression, 0);
+}
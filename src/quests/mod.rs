@@ -4,9 +4,16 @@ use std::ops::Range;
 use std::str;
 
 use bit::BitIndex;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-use crate::ParseHardError;
+use crate::{Act, Difficulty, ParseHardError};
+
+mod quest_id;
+
+pub use quest_id::{QuestId, QuestReward, QuestState};
+
+#[cfg(test)]
+mod tests;
 
 const SECTION_LENGTH: usize = 298;
 const SECTION_HEADER: [u8; 10] = [0x57, 0x6F, 0x6F, 0x21, 0x06, 0x00, 0x00, 0x00, 0x2A, 0x01]; // Woo! + header
@@ -39,7 +46,26 @@ impl Section {
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Copy, Clone, Serialize, Deserialize, Hash)]
+/// Named byte spans of the quests section, relative to its start.
+pub(crate) fn field_spans() -> Vec<(Range<usize>, String)> {
+    let mut spans = vec![(Section::Header.range(), "header".to_string())];
+    let difficulties =
+        [("normal", Section::Normal), ("nightmare", Section::Nightmare), ("hell", Section::Hell)];
+    for (difficulty_name, difficulty_section) in difficulties {
+        let mut offset = difficulty_section.range().start;
+        for (act, quest_name, _) in DifficultyQuests::default().labeled() {
+            let act_number = u8::from(act) + 1;
+            spans.push((
+                offset..offset + 2,
+                format!("{difficulty_name}.act{act_number}.{quest_name}"),
+            ));
+            offset += 2;
+        }
+    }
+    spans
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone, Serialize, Deserialize, Hash)]
 pub enum QuestFlag {
     RewardGranted = 0,
     RewardPending = 1,
@@ -59,7 +85,7 @@ pub enum QuestFlag {
     CompletedBefore = 15,
 }
 
-const ALL_QUEST_FLAGS: [QuestFlag; 16] = [
+pub(crate) const ALL_QUEST_FLAGS: [QuestFlag; 16] = [
     QuestFlag::RewardGranted,
     QuestFlag::RewardPending,
     QuestFlag::Started,
@@ -81,9 +107,20 @@ const ALL_QUEST_FLAGS: [QuestFlag; 16] = [
 /// Representation of the state of a quest. Stores a collection of `QuestFlag` values in a HashSet to indicate which flags are active.
 #[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Quest {
+    /// Serialized in bit order so output does not depend on hash iteration order.
+    #[serde(serialize_with = "serialize_sorted_flags")]
     pub state: HashSet<QuestFlag>,
 }
 
+fn serialize_sorted_flags<S: Serializer>(
+    state: &HashSet<QuestFlag>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut flags: Vec<&QuestFlag> = state.iter().collect();
+    flags.sort();
+    serializer.collect_seq(flags)
+}
+
 impl From<u16> for Quest {
     fn from(value: u16) -> Self {
         let mut quest = Quest::default();
@@ -449,6 +486,56 @@ pub struct DifficultyQuests {
 
 // section length = 298bytes -> header 10 bytes -> 288bytes for all quests -> 96 * 3 difficulties
 impl DifficultyQuests {
+    /// Every quest slot of this difficulty in save order, keyed by act and field name.
+    pub(crate) fn labeled(&self) -> Vec<(Act, &'static str, &Quest)> {
+        let (act1, act2, act3, act4, act5) =
+            (&self.act1, &self.act2, &self.act3, &self.act4, &self.act5);
+        vec![
+            (Act::Act1, "prologue", &act1.prologue),
+            (Act::Act1, "q1", &act1.q1),
+            (Act::Act1, "q2", &act1.q2),
+            (Act::Act1, "q3", &act1.q3),
+            (Act::Act1, "q4", &act1.q4),
+            (Act::Act1, "q5", &act1.q5),
+            (Act::Act1, "q6", &act1.q6),
+            (Act::Act1, "completion", &act1.completion),
+            (Act::Act2, "prologue", &act2.prologue),
+            (Act::Act2, "q1", &act2.q1),
+            (Act::Act2, "q2", &act2.q2),
+            (Act::Act2, "q3", &act2.q3),
+            (Act::Act2, "q4", &act2.q4),
+            (Act::Act2, "q5", &act2.q5),
+            (Act::Act2, "q6", &act2.q6),
+            (Act::Act2, "completion", &act2.completion),
+            (Act::Act3, "prologue", &act3.prologue),
+            (Act::Act3, "q1", &act3.q1),
+            (Act::Act3, "q2", &act3.q2),
+            (Act::Act3, "q3", &act3.q3),
+            (Act::Act3, "q4", &act3.q4),
+            (Act::Act3, "q5", &act3.q5),
+            (Act::Act3, "q6", &act3.q6),
+            (Act::Act3, "completion", &act3.completion),
+            (Act::Act4, "prologue", &act4.prologue),
+            (Act::Act4, "q1", &act4.q1),
+            (Act::Act4, "q2", &act4.q2),
+            (Act::Act4, "q3", &act4.q3),
+            (Act::Act4, "completion", &act4.completion),
+            (Act::Act4, "unused_1", &act4.unused_1),
+            (Act::Act4, "unused_2", &act4.unused_2),
+            (Act::Act4, "unused_3", &act4.unused_3),
+            (Act::Act5, "prologue", &act5.prologue),
+            (Act::Act5, "unused_1", &act5.unused_1),
+            (Act::Act5, "unused_2", &act5.unused_2),
+            (Act::Act5, "q1", &act5.q1),
+            (Act::Act5, "q2", &act5.q2),
+            (Act::Act5, "q3", &act5.q3),
+            (Act::Act5, "q4", &act5.q4),
+            (Act::Act5, "q5", &act5.q5),
+            (Act::Act5, "q6", &act5.q6),
+            (Act::Act5, "completion", &act5.completion),
+        ]
+    }
+
     pub fn to_bytes(&self) -> [u8; 96] {
         let mut byte_vector: [u8; 96] = [0; 96];
         byte_vector[Section::Act1.range()].copy_from_slice(&self.act1.to_bytes());
@@ -490,6 +577,40 @@ impl fmt::Display for Quests {
 }
 
 impl Quests {
+    pub fn difficulty(&self, difficulty: Difficulty) -> &DifficultyQuests {
+        match difficulty {
+            Difficulty::Normal => &self.normal,
+            Difficulty::Nightmare => &self.nightmare,
+            Difficulty::Hell => &self.hell,
+        }
+    }
+
+    pub fn difficulty_mut(&mut self, difficulty: Difficulty) -> &mut DifficultyQuests {
+        match difficulty {
+            Difficulty::Normal => &mut self.normal,
+            Difficulty::Nightmare => &mut self.nightmare,
+            Difficulty::Hell => &mut self.hell,
+        }
+    }
+
+    pub fn get(&self, difficulty: Difficulty, quest: QuestId) -> &Quest {
+        quest.slot(self.difficulty(difficulty))
+    }
+
+    pub fn get_mut(&mut self, difficulty: Difficulty, quest: QuestId) -> &mut Quest {
+        quest.slot_mut(self.difficulty_mut(difficulty))
+    }
+
+    /// Quest log stage of `quest`; see [`QuestId::state_of`].
+    pub fn state(&self, difficulty: Difficulty, quest: QuestId) -> QuestState {
+        quest.state_of(self.get(difficulty, quest))
+    }
+
+    /// Replace the flags of `quest` with its canonical set for `state`.
+    pub fn set_state(&mut self, difficulty: Difficulty, quest: QuestId, state: QuestState) {
+        self.get_mut(difficulty, quest).state = quest.flags(state).iter().copied().collect();
+    }
+
     pub fn to_bytes(&self) -> [u8; 298] {
         let mut byte_vector: [u8; 298] = [0; 298];
         byte_vector[Section::Header.range()].copy_from_slice(&SECTION_HEADER);
@@ -525,6 +646,3 @@ impl Quests {
         })
     }
 }
-
-#[cfg(test)]
-mod tests {}
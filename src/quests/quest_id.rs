@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+
+use super::{DifficultyQuests, Quest, QuestFlag};
+use crate::Act;
+
+/// Story quest, in the order the save stores them.
+///
+/// Save order differs from the in-game quest log in Acts I, III and IV.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone, Hash, Serialize, Deserialize)]
+pub enum QuestId {
+    DenOfEvil,
+    SistersBurialGrounds,
+    ToolsOfTheTrade,
+    TheSearchForCain,
+    TheForgottenTower,
+    SistersToTheSlaughter,
+    RadamentsLair,
+    TheHoradricStaff,
+    TaintedSun,
+    ArcaneSanctuary,
+    TheSummoner,
+    TheSevenTombs,
+    LamEsensTome,
+    KhalimsWill,
+    BladeOfTheOldReligion,
+    TheGoldenBird,
+    TheBlackenedTemple,
+    TheGuardian,
+    TheFallenAngel,
+    TerrorsEnd,
+    HellsForge,
+    SiegeOnHarrogath,
+    RescueOnMountArreat,
+    PrisonOfIce,
+    BetrayalOfHarrogath,
+    RiteOfPassage,
+    EveOfDestruction,
+}
+
+/// Stage of a story quest, as shown in the quest log.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, Serialize, Deserialize)]
+pub enum QuestState {
+    NotStarted,
+    InProgress,
+    /// Quest goal done but the reward has not been collected yet.
+    CompletedRewardPending,
+    CompletedRewardCollected,
+}
+
+/// Permanent character bonus given by a quest reward.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, Serialize, Deserialize)]
+pub enum QuestReward {
+    SkillPoints(u32),
+    StatPoints(u32),
+    /// Maximum life, in whole points.
+    MaxLife(u32),
+    /// All resistances, in percent. The game applies it from the quest flag, so no
+    /// attribute stores it.
+    AllResistances(u32),
+}
+
+const NOT_STARTED: &[QuestFlag] = &[];
+const IN_PROGRESS: &[QuestFlag] = &[QuestFlag::Started];
+const REWARD_PENDING: &[QuestFlag] = &[QuestFlag::RewardPending, QuestFlag::Started];
+const REWARD_COLLECTED: &[QuestFlag] =
+    &[QuestFlag::RewardGranted, QuestFlag::UpdateQuestLog, QuestFlag::PrimaryGoalDone];
+/// Den cleared, not yet back to Akara.
+const DEN_OF_EVIL_REWARD_PENDING: &[QuestFlag] =
+    &[QuestFlag::Started, QuestFlag::LeaveTown, QuestFlag::EnterArea];
+
+impl QuestId {
+    pub const ALL: [Self; 27] = [
+        Self::DenOfEvil,
+        Self::SistersBurialGrounds,
+        Self::ToolsOfTheTrade,
+        Self::TheSearchForCain,
+        Self::TheForgottenTower,
+        Self::SistersToTheSlaughter,
+        Self::RadamentsLair,
+        Self::TheHoradricStaff,
+        Self::TaintedSun,
+        Self::ArcaneSanctuary,
+        Self::TheSummoner,
+        Self::TheSevenTombs,
+        Self::LamEsensTome,
+        Self::KhalimsWill,
+        Self::BladeOfTheOldReligion,
+        Self::TheGoldenBird,
+        Self::TheBlackenedTemple,
+        Self::TheGuardian,
+        Self::TheFallenAngel,
+        Self::TerrorsEnd,
+        Self::HellsForge,
+        Self::SiegeOnHarrogath,
+        Self::RescueOnMountArreat,
+        Self::PrisonOfIce,
+        Self::BetrayalOfHarrogath,
+        Self::RiteOfPassage,
+        Self::EveOfDestruction,
+    ];
+
+    pub const fn act(self) -> Act {
+        match self as usize {
+            0..=5 => Act::Act1,
+            6..=11 => Act::Act2,
+            12..=17 => Act::Act3,
+            18..=20 => Act::Act4,
+            _ => Act::Act5,
+        }
+    }
+
+    /// Story quests of `act` in save order.
+    pub fn for_act(act: Act) -> impl Iterator<Item = QuestId> {
+        Self::ALL.into_iter().filter(move |quest| quest.act() == act)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DenOfEvil => "Den of Evil",
+            Self::SistersBurialGrounds => "Sisters' Burial Grounds",
+            Self::ToolsOfTheTrade => "Tools of the Trade",
+            Self::TheSearchForCain => "The Search for Cain",
+            Self::TheForgottenTower => "The Forgotten Tower",
+            Self::SistersToTheSlaughter => "Sisters to the Slaughter",
+            Self::RadamentsLair => "Radament's Lair",
+            Self::TheHoradricStaff => "The Horadric Staff",
+            Self::TaintedSun => "Tainted Sun",
+            Self::ArcaneSanctuary => "Arcane Sanctuary",
+            Self::TheSummoner => "The Summoner",
+            Self::TheSevenTombs => "The Seven Tombs",
+            Self::LamEsensTome => "Lam Esen's Tome",
+            Self::KhalimsWill => "Khalim's Will",
+            Self::BladeOfTheOldReligion => "Blade of the Old Religion",
+            Self::TheGoldenBird => "The Golden Bird",
+            Self::TheBlackenedTemple => "The Blackened Temple",
+            Self::TheGuardian => "The Guardian",
+            Self::TheFallenAngel => "The Fallen Angel",
+            Self::TerrorsEnd => "Terror's End",
+            Self::HellsForge => "Hell's Forge",
+            Self::SiegeOnHarrogath => "Siege on Harrogath",
+            Self::RescueOnMountArreat => "Rescue on Mount Arreat",
+            Self::PrisonOfIce => "Prison of Ice",
+            Self::BetrayalOfHarrogath => "Betrayal of Harrogath",
+            Self::RiteOfPassage => "Rite of Passage",
+            Self::EveOfDestruction => "Eve of Destruction",
+        }
+    }
+
+    /// Character bonus this quest grants once per difficulty, if any.
+    ///
+    /// Rewards that are items, imbues, sockets or personalizations are not listed.
+    pub const fn reward(self) -> Option<QuestReward> {
+        match self {
+            Self::DenOfEvil | Self::RadamentsLair => Some(QuestReward::SkillPoints(1)),
+            Self::TheFallenAngel => Some(QuestReward::SkillPoints(2)),
+            Self::LamEsensTome => Some(QuestReward::StatPoints(5)),
+            Self::TheGoldenBird => Some(QuestReward::MaxLife(20)),
+            Self::PrisonOfIce => Some(QuestReward::AllResistances(10)),
+            _ => None,
+        }
+    }
+
+    /// Flags the game writes for this quest at `state`.
+    pub fn flags(self, state: QuestState) -> &'static [QuestFlag] {
+        match (self, state) {
+            (_, QuestState::NotStarted) => NOT_STARTED,
+            (_, QuestState::InProgress) => IN_PROGRESS,
+            (Self::DenOfEvil, QuestState::CompletedRewardPending) => DEN_OF_EVIL_REWARD_PENDING,
+            (_, QuestState::CompletedRewardPending) => REWARD_PENDING,
+            (_, QuestState::CompletedRewardCollected) => REWARD_COLLECTED,
+        }
+    }
+
+    /// Quest log stage of `quest`, read as this quest.
+    ///
+    /// Canonical flag sets map to their state. Otherwise `RewardGranted` or a completion
+    /// flag means collected, `RewardPending` means pending, and any other flag means in
+    /// progress.
+    pub fn state_of(self, quest: &Quest) -> QuestState {
+        let canonical = [
+            QuestState::NotStarted,
+            QuestState::InProgress,
+            QuestState::CompletedRewardPending,
+            QuestState::CompletedRewardCollected,
+        ]
+        .into_iter()
+        .find(|state| {
+            let flags = self.flags(*state);
+            flags.len() == quest.state.len() && flags.iter().all(|flag| quest.state.contains(flag))
+        });
+        if let Some(state) = canonical {
+            return state;
+        }
+
+        let has = |flag| quest.state.contains(&flag);
+        if has(QuestFlag::RewardGranted) {
+            QuestState::CompletedRewardCollected
+        } else if has(QuestFlag::RewardPending) {
+            QuestState::CompletedRewardPending
+        } else if has(QuestFlag::CompletedBefore) || has(QuestFlag::CompletedNow) {
+            QuestState::CompletedRewardCollected
+        } else {
+            QuestState::InProgress
+        }
+    }
+
+    pub(crate) fn slot(self, quests: &DifficultyQuests) -> &Quest {
+        let (act1, act2, act3, act4, act5) =
+            (&quests.act1, &quests.act2, &quests.act3, &quests.act4, &quests.act5);
+        match self {
+            Self::DenOfEvil => &act1.q1,
+            Self::SistersBurialGrounds => &act1.q2,
+            Self::ToolsOfTheTrade => &act1.q3,
+            Self::TheSearchForCain => &act1.q4,
+            Self::TheForgottenTower => &act1.q5,
+            Self::SistersToTheSlaughter => &act1.q6,
+            Self::RadamentsLair => &act2.q1,
+            Self::TheHoradricStaff => &act2.q2,
+            Self::TaintedSun => &act2.q3,
+            Self::ArcaneSanctuary => &act2.q4,
+            Self::TheSummoner => &act2.q5,
+            Self::TheSevenTombs => &act2.q6,
+            Self::LamEsensTome => &act3.q1,
+            Self::KhalimsWill => &act3.q2,
+            Self::BladeOfTheOldReligion => &act3.q3,
+            Self::TheGoldenBird => &act3.q4,
+            Self::TheBlackenedTemple => &act3.q5,
+            Self::TheGuardian => &act3.q6,
+            Self::TheFallenAngel => &act4.q1,
+            Self::TerrorsEnd => &act4.q2,
+            Self::HellsForge => &act4.q3,
+            Self::SiegeOnHarrogath => &act5.q1,
+            Self::RescueOnMountArreat => &act5.q2,
+            Self::PrisonOfIce => &act5.q3,
+            Self::BetrayalOfHarrogath => &act5.q4,
+            Self::RiteOfPassage => &act5.q5,
+            Self::EveOfDestruction => &act5.q6,
+        }
+    }
+
+    pub(crate) fn slot_mut(self, quests: &mut DifficultyQuests) -> &mut Quest {
+        let (act1, act2, act3, act4, act5) = (
+            &mut quests.act1,
+            &mut quests.act2,
+            &mut quests.act3,
+            &mut quests.act4,
+            &mut quests.act5,
+        );
+        match self {
+            Self::DenOfEvil => &mut act1.q1,
+            Self::SistersBurialGrounds => &mut act1.q2,
+            Self::ToolsOfTheTrade => &mut act1.q3,
+            Self::TheSearchForCain => &mut act1.q4,
+            Self::TheForgottenTower => &mut act1.q5,
+            Self::SistersToTheSlaughter => &mut act1.q6,
+            Self::RadamentsLair => &mut act2.q1,
+            Self::TheHoradricStaff => &mut act2.q2,
+            Self::TaintedSun => &mut act2.q3,
+            Self::ArcaneSanctuary => &mut act2.q4,
+            Self::TheSummoner => &mut act2.q5,
+            Self::TheSevenTombs => &mut act2.q6,
+            Self::LamEsensTome => &mut act3.q1,
+            Self::KhalimsWill => &mut act3.q2,
+            Self::BladeOfTheOldReligion => &mut act3.q3,
+            Self::TheGoldenBird => &mut act3.q4,
+            Self::TheBlackenedTemple => &mut act3.q5,
+            Self::TheGuardian => &mut act3.q6,
+            Self::TheFallenAngel => &mut act4.q1,
+            Self::TerrorsEnd => &mut act4.q2,
+            Self::HellsForge => &mut act4.q3,
+            Self::SiegeOnHarrogath => &mut act5.q1,
+            Self::RescueOnMountArreat => &mut act5.q2,
+            Self::PrisonOfIce => &mut act5.q3,
+            Self::BetrayalOfHarrogath => &mut act5.q4,
+            Self::RiteOfPassage => &mut act5.q5,
+            Self::EveOfDestruction => &mut act5.q6,
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Skill and stat resets, matching Akara's respec.
+//!
+//! Refunded points are rebuilt from the character level, the class table and the quest
+//! rewards recorded in [`crate::quests::Quests`]. Points currently spent are ignored. Den of
+//! Evil and Radament's Lair grant one skill point and The Fallen Angel (Izual) grants two, in
+//! each difficulty. Lam Esen's Tome grants five stat points. Prison of Ice (Anya) grants
+//! resistances and The Golden Bird grants life, so neither adds points. See
+//! [`crate::quests::QuestId::reward`].
+//!
+//! The respec itself does not touch quest state, including the Act V reset flag.
+//! [`crate::Save::grant_quest_reward`] and [`crate::Save::revoke_quest_reward`] change a
+//! quest's reward and the attributes it affects together.
+//!
+//! The same class table gives maximum life, mana and stamina; see [`crate::Save::recompute_vitals`].
+
+use std::fmt;
+
+use crate::data::{ClassData, GameData};
+use crate::quests::{Quest, QuestFlag, QuestId, QuestReward, QuestState, Quests};
+use crate::{Class, Difficulty, Save};
+
+#[cfg(test)]
+mod tests;
+
+/// Q8 units per quarter point, the unit of the `charstats.txt` gain columns.
+const Q8_PER_QUARTER_POINT: u32 = 64;
+const Q8_PER_POINT: u32 = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RespecError {
+    /// No class table row for the class, so base stats and gains are unknown.
+    NoClassData(Class),
+}
+
+impl fmt::Display for RespecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoClassData(class) => write!(f, "No class base stats for class {class}"),
+        }
+    }
+}
+
+impl std::error::Error for RespecError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestRewardError {
+    /// The reward's points are no longer unspent, so they cannot be taken back.
+    PointsAlreadySpent { quest: QuestId, points: u32, unspent: u32 },
+}
+
+impl fmt::Display for QuestRewardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PointsAlreadySpent { quest, points, unspent } => write!(
+                f,
+                "Cannot revoke {}: it granted {points} points but only {unspent} are unspent",
+                quest.name()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QuestRewardError {}
+
+fn reward_granted(quest: &Quest) -> bool {
+    quest.state.contains(&QuestFlag::RewardGranted)
+}
+
+/// Skill points, stat points and maximum life from the rewards of quests matching `counted`.
+fn reward_totals(quests: &Quests, counted: impl Fn(QuestId, &Quest) -> bool) -> [u32; 3] {
+    let mut totals = [0; 3];
+    for difficulty in [Difficulty::Normal, Difficulty::Nightmare, Difficulty::Hell] {
+        for quest in QuestId::ALL {
+            if !counted(quest, quests.get(difficulty, quest)) {
+                continue;
+            }
+            match quest.reward() {
+                Some(QuestReward::SkillPoints(points)) => totals[0] += points,
+                Some(QuestReward::StatPoints(points)) => totals[1] += points,
+                Some(QuestReward::MaxLife(life)) => totals[2] += life,
+                Some(QuestReward::AllResistances(_)) | None => {}
+            }
+        }
+    }
+    totals
+}
+
+/// Skill points granted by the quest rewards recorded in `quests`.
+pub(crate) fn quest_skill_points(quests: &Quests) -> u32 {
+    reward_totals(quests, |_, quest| reward_granted(quest))[0]
+}
+
+/// Stat points granted by the quest rewards recorded in `quests`.
+pub(crate) fn quest_stat_points(quests: &Quests) -> u32 {
+    reward_totals(quests, |_, quest| reward_granted(quest))[1]
+}
+
+/// Maximum life granted by the quest rewards recorded in `quests`.
+pub(crate) fn quest_life_bonus(quests: &Quests) -> u32 {
+    reward_totals(quests, |_, quest| reward_granted(quest))[2]
+}
+
+/// Skill and stat points of rewards still marked pending.
+///
+/// Real saves keep some collected rewards in this state, so these points may or may not
+/// have been given.
+pub(crate) fn pending_reward_points(quests: &Quests) -> [u32; 2] {
+    let [skill_points, stat_points, _] = reward_totals(quests, |id, quest| {
+        !reward_granted(quest) && id.state_of(quest) == QuestState::CompletedRewardPending
+    });
+    [skill_points, stat_points]
+}
+
+fn add_reward(save: &mut Save, reward: QuestReward) {
+    let attributes = &mut save.attributes;
+    match reward {
+        QuestReward::SkillPoints(points) => attributes.newskills.value += points,
+        QuestReward::StatPoints(points) => attributes.statpts.value += points,
+        QuestReward::MaxLife(life) => attributes.maxhp.value += life * Q8_PER_POINT,
+        QuestReward::AllResistances(_) => {}
+    }
+}
+
+/// Take `reward` back, stopping at zero when its points were already spent.
+fn remove_reward(save: &mut Save, reward: QuestReward) {
+    let attributes = &mut save.attributes;
+    match reward {
+        QuestReward::SkillPoints(points) => {
+            attributes.newskills.value = attributes.newskills.value.saturating_sub(points)
+        }
+        QuestReward::StatPoints(points) => {
+            attributes.statpts.value = attributes.statpts.value.saturating_sub(points)
+        }
+        QuestReward::MaxLife(life) => {
+            attributes.maxhp.value = attributes.maxhp.value.saturating_sub(life * Q8_PER_POINT);
+            attributes.hitpoints.value = attributes.hitpoints.value.min(attributes.maxhp.value);
+        }
+        QuestReward::AllResistances(_) => {}
+    }
+}
+
+/// Mark `quest` completed with its reward collected and apply the reward once.
+pub(crate) fn grant_quest_reward(save: &mut Save, difficulty: Difficulty, quest: QuestId) {
+    if reward_granted(save.quests.get(difficulty, quest)) {
+        return;
+    }
+    save.quests.set_state(difficulty, quest, QuestState::CompletedRewardCollected);
+    if let Some(reward) = quest.reward() {
+        add_reward(save, reward);
+    }
+}
+
+/// Undo [`grant_quest_reward`], leaving the quest completed with its reward pending.
+pub(crate) fn revoke_quest_reward(
+    save: &mut Save,
+    difficulty: Difficulty,
+    quest: QuestId,
+) -> Result<(), QuestRewardError> {
+    if !reward_granted(save.quests.get(difficulty, quest)) {
+        return Ok(());
+    }
+    let points = match quest.reward() {
+        Some(QuestReward::SkillPoints(points)) => Some((points, save.attributes.newskills.value)),
+        Some(QuestReward::StatPoints(points)) => Some((points, save.attributes.statpts.value)),
+        _ => None,
+    };
+    if let Some((points, unspent)) = points.filter(|(points, unspent)| unspent < points) {
+        return Err(QuestRewardError::PointsAlreadySpent { quest, points, unspent });
+    }
+    set_quest_state(save, difficulty, quest, QuestState::CompletedRewardPending);
+    Ok(())
+}
+
+/// Set `quest` to `state`, removing its reward if one had been granted.
+pub(crate) fn set_quest_state(
+    save: &mut Save,
+    difficulty: Difficulty,
+    quest: QuestId,
+    state: QuestState,
+) {
+    let granted = reward_granted(save.quests.get(difficulty, quest));
+    save.quests.set_state(difficulty, quest, state);
+    if let Some(reward) = quest.reward().filter(|_| granted) {
+        remove_reward(save, reward);
+    }
+}
+
+fn levels_gained(save: &Save) -> u32 {
+    u32::from(save.attributes.level().saturating_sub(1))
+}
+
+/// Resolve the class row from `game_data`, or the built-in D2R table.
+pub(crate) fn class_data(
+    game_data: Option<&GameData>,
+    class: Class,
+) -> Result<ClassData, RespecError> {
+    match game_data {
+        Some(game_data) => game_data.class_data(class).cloned(),
+        None => ClassData::d2r(class),
+    }
+    .ok_or(RespecError::NoClassData(class))
+}
+
+/// Clear every skill slot and make all earned skill points unspent.
+pub(crate) fn respec_skills(save: &mut Save, class_data: &ClassData) {
+    save.skills.set_all(0);
+    save.attributes.newskills.value =
+        levels_gained(save) * class_data.skill_points_per_level + quest_skill_points(&save.quests);
+}
+
+/// Reset the four base stats to the class values, make all earned stat points unspent,
+/// and refill life, mana and stamina at their new maximums.
+pub(crate) fn respec_stats(save: &mut Save, class_data: &ClassData) {
+    let stat_points =
+        levels_gained(save) * class_data.stat_points_per_level + quest_stat_points(&save.quests);
+    let attributes = &mut save.attributes;
+    attributes.strength.value = class_data.strength;
+    attributes.dexterity.value = class_data.dexterity;
+    attributes.vitality.value = class_data.vitality;
+    attributes.energy.value = class_data.energy;
+    attributes.statpts.value = stat_points;
+
+    set_max_vitals(save, class_data);
+    let attributes = &mut save.attributes;
+    attributes.hitpoints.value = attributes.maxhp.value;
+    attributes.mana.value = attributes.maxmana.value;
+    attributes.stamina.value = attributes.maxstamina.value;
+}
+
+/// Maximum life, mana and stamina in Q8 for the class row, level and base stats.
+///
+/// Item bonuses are not included; the save stores these values without them.
+pub(crate) fn max_vitals(save: &Save, class_data: &ClassData) -> [u32; 3] {
+    let levels_gained = levels_gained(save);
+    let life_bonus = quest_life_bonus(&save.quests);
+    let attributes = &save.attributes;
+    let added_vitality = attributes.vitality.value.saturating_sub(class_data.vitality);
+    let added_energy = attributes.energy.value.saturating_sub(class_data.energy);
+
+    let max_life = (class_data.vitality + class_data.hp_add + life_bonus) * Q8_PER_POINT
+        + (levels_gained * class_data.life_per_level
+            + added_vitality * class_data.life_per_vitality)
+            * Q8_PER_QUARTER_POINT;
+    let max_mana = class_data.energy * Q8_PER_POINT
+        + (levels_gained * class_data.mana_per_level + added_energy * class_data.mana_per_energy)
+            * Q8_PER_QUARTER_POINT;
+    let max_stamina = class_data.stamina * Q8_PER_POINT
+        + (levels_gained * class_data.stamina_per_level
+            + added_vitality * class_data.stamina_per_vitality)
+            * Q8_PER_QUARTER_POINT;
+    [max_life, max_mana, max_stamina]
+}
+
+/// Set `maxhp`, `maxmana` and `maxstamina` from [`max_vitals`], lowering current values
+/// that would exceed them.
+pub(crate) fn set_max_vitals(save: &mut Save, class_data: &ClassData) {
+    let [max_life, max_mana, max_stamina] = max_vitals(save, class_data);
+    let attributes = &mut save.attributes;
+    attributes.maxhp.value = max_life;
+    attributes.maxmana.value = max_mana;
+    attributes.maxstamina.value = max_stamina;
+    attributes.hitpoints.value = attributes.hitpoints.value.min(max_life);
+    attributes.mana.value = attributes.mana.value.min(max_mana);
+    attributes.stamina.value = attributes.stamina.value.min(max_stamina);
+}
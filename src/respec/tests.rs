@@ -0,0 +1,141 @@
+use super::*;
+use crate::format::FormatId;
+use crate::quests::QuestFlag;
+
+fn grant(quest: &mut Quest) {
+    quest.state.insert(QuestFlag::RewardGranted);
+}
+
+fn barbarian_at_level_10() -> Save {
+    let mut save = Save::new(FormatId::V99, Class::Barbarian);
+    save.set_level(10);
+    grant(&mut save.quests.normal.act1.q1);
+    grant(&mut save.quests.nightmare.act1.q1);
+    grant(&mut save.quests.normal.act4.q1);
+    grant(&mut save.quests.normal.act3.q1);
+    grant(&mut save.quests.normal.act3.q4);
+    // Radament's book not read yet: no point.
+    save.quests.normal.act2.q1.state.insert(QuestFlag::RewardPending);
+    save
+}
+
+#[test]
+fn respec_skills_refunds_level_and_quest_points() {
+    let mut save = barbarian_at_level_10();
+    save.skills.set(3, 5);
+    save.skills.set(7, 2);
+
+    save.respec_skills().unwrap();
+
+    assert_eq!(save.skills.points, [0; crate::skills::SKILL_POINTS_COUNT]);
+    // 9 levels, Den of Evil twice and Izual once.
+    assert_eq!(save.attributes.newskills.value, 9 + 2 + 2);
+}
+
+#[test]
+fn respec_stats_restores_class_base_and_recomputes_vitals() {
+    let mut save = barbarian_at_level_10();
+    save.attributes.strength.value = 60;
+    save.attributes.vitality.value = 40;
+
+    save.respec_stats().unwrap();
+
+    let attributes = &save.attributes;
+    assert_eq!(
+        [attributes.strength.value, attributes.dexterity.value, attributes.vitality.value],
+        [30, 20, 25]
+    );
+    assert_eq!(attributes.energy.value, 10);
+    assert_eq!(attributes.statpts.value, 9 * 5 + 5);
+    // 25 vitality + 30 base + 20 from The Golden Bird, then 2 life per level.
+    assert_eq!(attributes.get_max_hp(), 75 + 18);
+    assert_eq!(attributes.get_max_mana(), 10 + 9);
+    assert_eq!(attributes.get_max_stamina(), 92 + 9);
+    assert_eq!(attributes.hitpoints.value, attributes.maxhp.value);
+    assert_eq!(attributes.stamina.value, attributes.maxstamina.value);
+}
+
+#[test]
+fn set_max_vitals_counts_points_above_class_base() {
+    let mut save = Save::new(FormatId::V99, Class::Sorceress);
+    let class_data = ClassData::d2r(Class::Sorceress).unwrap();
+    save.attributes.vitality.value = class_data.vitality + 4;
+    save.attributes.energy.value = class_data.energy + 10;
+
+    set_max_vitals(&mut save, &class_data);
+
+    assert_eq!(save.attributes.get_max_hp(), 10 + 30 + 4 * 2);
+    assert_eq!(save.attributes.get_max_mana(), 35 + 10 * 2);
+    assert_eq!(save.attributes.get_max_stamina(), 74 + 4);
+}
+
+#[test]
+fn respec_requires_class_data() {
+    let mut save = Save::new(FormatId::V105, Class::Warlock);
+    assert_eq!(save.respec_stats(), Err(RespecError::NoClassData(Class::Warlock)));
+    assert_eq!(save.respec_skills(), Err(RespecError::NoClassData(Class::Warlock)));
+
+    let game_data =
+        GameData::load(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/test/data")).unwrap();
+    let mut save = barbarian_at_level_10();
+    save.respec_stats_with(&game_data).unwrap();
+    assert_eq!(save.attributes.strength.value, 30);
+}
+
+#[test]
+fn new_with_class_stats_starts_at_class_base_stats() {
+    // `Save::new` keeps its generic starting values.
+    assert_eq!(Save::new(FormatId::V105, Class::Sorceress).attributes.energy.value, 10);
+
+    let save = Save::new_with_class_stats(FormatId::V105, Class::Sorceress);
+    let attributes = &save.attributes;
+    assert_eq!([attributes.strength.value, attributes.energy.value], [10, 35]);
+    assert_eq!(
+        [attributes.get_hp(), attributes.get_max_mana(), attributes.get_max_stamina()],
+        [40, 35, 74]
+    );
+    assert_eq!(attributes.statpts.value, 0);
+}
+
+#[test]
+fn quest_rewards_adjust_attributes_once() {
+    use crate::quests::{QuestId, QuestState};
+
+    let mut save = Save::new(FormatId::V99, Class::Sorceress);
+    let max_hp = save.attributes.get_max_hp();
+    for quest in [QuestId::DenOfEvil, QuestId::TheFallenAngel, QuestId::LamEsensTome] {
+        save.grant_quest_reward(Difficulty::Nightmare, quest);
+        save.grant_quest_reward(Difficulty::Nightmare, quest);
+    }
+    save.grant_quest_reward(Difficulty::Normal, QuestId::TheGoldenBird);
+    save.grant_quest_reward(Difficulty::Normal, QuestId::PrisonOfIce);
+
+    assert_eq!((save.attributes.newskills.value, save.attributes.statpts.value), (3, 5));
+    assert_eq!(save.attributes.get_max_hp(), max_hp + 20);
+    assert_eq!(
+        save.quests.state(Difficulty::Normal, QuestId::PrisonOfIce),
+        QuestState::CompletedRewardCollected
+    );
+    assert!(save.validate().issues.is_empty(), "{:?}", save.validate().issues);
+
+    save.skills.set(0, 2);
+    save.attributes.newskills.value = 1;
+    assert_eq!(
+        save.revoke_quest_reward(Difficulty::Nightmare, QuestId::TheFallenAngel),
+        Err(QuestRewardError::PointsAlreadySpent {
+            quest: QuestId::TheFallenAngel,
+            points: 2,
+            unspent: 1
+        })
+    );
+    save.revoke_quest_reward(Difficulty::Nightmare, QuestId::DenOfEvil).unwrap();
+    save.revoke_quest_reward(Difficulty::Normal, QuestId::TheGoldenBird).unwrap();
+
+    assert_eq!(save.attributes.newskills.value, 0);
+    assert_eq!(save.attributes.get_max_hp(), max_hp);
+    assert_eq!(
+        save.quests.state(Difficulty::Nightmare, QuestId::DenOfEvil),
+        QuestState::CompletedRewardPending
+    );
+    assert!(save.validate().issues.is_empty(), "{:?}", save.validate().issues);
+}
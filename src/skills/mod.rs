@@ -2,24 +2,31 @@
 //!
 //! Core API is raw index-based (`set`/`get`) so modded skill trees remain supported.
 //! D2R name mapping is available via [`SkillPoints::set_by_name_d2r`]
-//! and [`SkillPoints::get_by_name_d2r`].
+//! and [`SkillPoints::get_by_name_d2r`]; modded trees can use [`SkillPoints::set_by_name`]
+//! and [`SkillPoints::get_by_name`] with a loaded [`GameData`].
 
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
+use crate::data::GameData;
 use crate::Class;
 use crate::ParseHardError;
 
 mod named_d2r;
 #[cfg(test)]
 mod tests;
+mod tree_d2r;
 
+pub use named_d2r::d2r_skill_id;
 pub use named_d2r::d2r_skill_index;
 pub use named_d2r::d2r_skill_name;
+pub use named_d2r::d2r_skill_slot;
 pub use named_d2r::NamedSkillError;
+pub(crate) use named_d2r::{eq_normalized, has_no_ascii_alnum};
+pub use tree_d2r::{d2r_skill_tabs, d2r_skill_tree, SkillTreeEntry};
 
-const SECTION_HEADER: [u8; 2] = [0x69, 0x66];
+pub(crate) const SECTION_HEADER: [u8; 2] = [0x69, 0x66];
 /// Number of skill slots stored in this section.
 pub const SKILL_POINTS_COUNT: usize = 30;
 /// Total encoded section length including 2-byte section header.
@@ -114,4 +121,92 @@ impl SkillPoints {
         let index = d2r_skill_index(class, skill_name)?;
         Ok(self.get(index))
     }
+
+    /// Resolve a skill name from `game_data` to a class-local slot and set its value.
+    pub fn set_by_name(
+        &mut self,
+        game_data: &GameData,
+        class: Class,
+        skill_name: &str,
+        value: u8,
+    ) -> Result<(), NamedSkillError> {
+        let index = slot_index(game_data, class, skill_name)?;
+        self.set(index, value);
+        Ok(())
+    }
+
+    /// Resolve a skill name from `game_data` to a class-local slot and return its value.
+    pub fn get_by_name(
+        &self,
+        game_data: &GameData,
+        class: Class,
+        skill_name: &str,
+    ) -> Result<u8, NamedSkillError> {
+        let index = slot_index(game_data, class, skill_name)?;
+        Ok(self.get(index))
+    }
+}
+
+/// Resolve `skill_name` through `game_data`, rejecting slots the save section cannot hold.
+fn slot_index(
+    game_data: &GameData,
+    class: Class,
+    skill_name: &str,
+) -> Result<usize, NamedSkillError> {
+    let skill_index = game_data.skill_index(class, skill_name)?;
+    if skill_index >= SKILL_POINTS_COUNT {
+        return Err(NamedSkillError::InvalidSkillIndex { class, skill_index });
+    }
+    Ok(skill_index)
+}
+
+/// Level and prerequisites for one class-local skill slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SkillRequirements {
+    pub(crate) name: String,
+    pub(crate) required_level: u8,
+    /// Class-local slots of the prerequisite skills.
+    pub(crate) prerequisites: Vec<usize>,
+}
+
+/// Requirements of every skill slot of `class`, from `game_data` when given and otherwise
+/// from the default D2R tree.
+///
+/// Prerequisites that do not resolve to a slot of the same class are left out.
+pub(crate) fn skill_requirements(
+    game_data: Option<&GameData>,
+    class: Class,
+) -> Result<Vec<SkillRequirements>, NamedSkillError> {
+    let Some(game_data) = game_data else {
+        return Ok(d2r_skill_tree(class)?
+            .iter()
+            .map(|entry| SkillRequirements {
+                name: entry.name.to_string(),
+                required_level: entry.required_level,
+                prerequisites: entry
+                    .prerequisites
+                    .iter()
+                    .filter_map(|name| d2r_skill_index(class, name).ok())
+                    .collect(),
+            })
+            .collect());
+    };
+
+    let class_skills = game_data.skills(class);
+    if class_skills.is_empty() {
+        return Err(NamedSkillError::UnsupportedClass(class));
+    }
+    Ok(class_skills
+        .iter()
+        .take(SKILL_POINTS_COUNT)
+        .map(|skill| SkillRequirements {
+            name: skill.name.clone(),
+            required_level: skill.required_level,
+            prerequisites: skill
+                .prerequisites
+                .iter()
+                .filter_map(|name| slot_index(game_data, class, name).ok())
+                .collect(),
+        })
+        .collect())
 }
@@ -272,7 +272,7 @@ const WARLOCK_SKILLS: [&str; 30] = [
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NamedSkillError {
-    /// Class has no built-in D2R name table in this mapping.
+    /// Class has no skill name table in this mapping.
     UnsupportedClass(Class),
     /// Name was not found in the class table after normalization.
     UnknownSkillName { class: Class, skill_name: String },
@@ -284,10 +284,10 @@ impl fmt::Display for NamedSkillError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::UnsupportedClass(class) => {
-                write!(f, "No default D2R named-skill mapping for class {class}")
+                write!(f, "No named-skill mapping for class {class}")
             }
             Self::UnknownSkillName { class, skill_name } => {
-                write!(f, "Unknown D2R skill '{skill_name}' for class {class}")
+                write!(f, "Unknown skill '{skill_name}' for class {class}")
             }
             Self::InvalidSkillIndex { class, skill_index } => {
                 write!(f, "Invalid skill index {skill_index} for class {class}")
@@ -325,6 +325,41 @@ pub fn d2r_skill_index(class: Class, skill_name: &str) -> Result<usize, NamedSki
         .ok_or(NamedSkillError::UnknownSkillName { class, skill_name: skill_name.to_string() })
 }
 
+/// Resolve a class-local skill index to its default D2R global skill id.
+///
+/// Global ids are the `*Id` column of `skills.txt`, as stored in hotkey and mouse bindings.
+pub fn d2r_skill_id(class: Class, skill_index: usize) -> Result<u16, NamedSkillError> {
+    d2r_skill_name(class, skill_index)?;
+    Ok(d2r_first_skill_id(class)? + skill_index as u16)
+}
+
+/// Resolve a default D2R global skill id to a class-local skill index.
+///
+/// Returns `None` for skills outside the class tree, such as Attack or skills granted by
+/// items.
+pub fn d2r_skill_slot(class: Class, skill_id: u16) -> Result<Option<usize>, NamedSkillError> {
+    let first_skill_id = d2r_first_skill_id(class)?;
+    let skill_index = usize::from(skill_id.wrapping_sub(first_skill_id));
+    Ok((skill_id >= first_skill_id && skill_index < d2r_skills_for_class(class)?.len())
+        .then_some(skill_index))
+}
+
+/// Global id of the first skill in each class tree. Class skills are numbered in slot order.
+fn d2r_first_skill_id(class: Class) -> Result<u16, NamedSkillError> {
+    match class {
+        Class::Amazon => Ok(6),
+        Class::Sorceress => Ok(36),
+        Class::Necromancer => Ok(66),
+        Class::Paladin => Ok(96),
+        Class::Barbarian => Ok(126),
+        Class::Druid => Ok(221),
+        Class::Assassin => Ok(251),
+        // Warlock ids are not checked against the RotW `skills.txt` yet; load them through
+        // `GameData`.
+        Class::Warlock | Class::Unknown(_) => Err(NamedSkillError::UnsupportedClass(class)),
+    }
+}
+
 fn d2r_skills_for_class(class: Class) -> Result<&'static [&'static str; 30], NamedSkillError> {
     match class {
         Class::Amazon => Ok(&AMAZON_SKILLS),
@@ -343,11 +378,11 @@ fn normalized_ascii_alnum_byte(byte: u8) -> Option<u8> {
     byte.is_ascii_alphanumeric().then_some(byte.to_ascii_lowercase())
 }
 
-fn has_no_ascii_alnum(skill_name: &str) -> bool {
+pub(crate) fn has_no_ascii_alnum(skill_name: &str) -> bool {
     !skill_name.bytes().any(|byte| byte.is_ascii_alphanumeric())
 }
 
-fn eq_normalized(left: &str, right: &str) -> bool {
+pub(crate) fn eq_normalized(left: &str, right: &str) -> bool {
     let mut left_iter = left.bytes().filter_map(normalized_ascii_alnum_byte);
     let mut right_iter = right.bytes().filter_map(normalized_ascii_alnum_byte);
 
@@ -1,5 +1,6 @@
 use crate::skills::{
-    d2r_skill_index, d2r_skill_name, NamedSkillError, SkillPoints, SKILLS_SECTION_LENGTH,
+    d2r_skill_id, d2r_skill_index, d2r_skill_name, d2r_skill_slot, d2r_skill_tabs, d2r_skill_tree,
+    NamedSkillError, SkillPoints, SKILLS_SECTION_LENGTH,
 };
 use crate::Class;
 
@@ -63,3 +64,70 @@ fn test_named_lookup_errors_for_unknown_name_and_class() {
         Err(NamedSkillError::UnsupportedClass(Class::Unknown(0xFF)))
     ));
 }
+
+#[test]
+fn test_d2r_skill_tree_matches_named_slots() {
+    let classes = [
+        Class::Amazon,
+        Class::Sorceress,
+        Class::Necromancer,
+        Class::Paladin,
+        Class::Barbarian,
+        Class::Druid,
+        Class::Assassin,
+    ];
+    for class in classes {
+        let tree = d2r_skill_tree(class).expect("vanilla classes have a tree");
+        let mut cells = std::collections::HashSet::new();
+        for (index, entry) in tree.iter().enumerate() {
+            assert_eq!(d2r_skill_name(class, index).unwrap(), entry.name);
+            assert!((1..=3).contains(&entry.tab) && (1..=3).contains(&entry.column));
+            assert!(cells.insert((entry.tab, entry.row, entry.column)), "{} overlaps", entry.name);
+            for prerequisite in entry.prerequisites {
+                let required = &tree[d2r_skill_index(class, prerequisite).unwrap()];
+                assert_eq!(required.tab, entry.tab, "{} -> {prerequisite}", entry.name);
+                // Holy Shock to Sanctuary and Hurricane to Armageddon point sideways.
+                assert!(
+                    required.row <= entry.row && required != entry,
+                    "{} -> {prerequisite}",
+                    entry.name
+                );
+            }
+        }
+    }
+
+    let frozen_orb = &d2r_skill_tree(Class::Sorceress).unwrap()[28];
+    assert_eq!((frozen_orb.name, frozen_orb.required_level), ("Frozen Orb", 30));
+    assert_eq!(frozen_orb.prerequisites, ["Blizzard"]);
+    assert_eq!(
+        d2r_skill_tabs(Class::Sorceress).unwrap()[usize::from(frozen_orb.tab) - 1],
+        "Cold Spells"
+    );
+    // The Warlock tree has not been checked against the RotW `skills.txt` yet.
+    assert_eq!(
+        d2r_skill_tree(Class::Warlock).unwrap_err(),
+        NamedSkillError::UnsupportedClass(Class::Warlock)
+    );
+    assert!(d2r_skill_tabs(Class::Warlock).is_err());
+}
+
+#[test]
+fn test_d2r_skill_ids_map_to_slots() {
+    assert_eq!(d2r_skill_id(Class::Amazon, 0).unwrap(), 6);
+    assert_eq!(d2r_skill_id(Class::Druid, 29).unwrap(), 250);
+    assert_eq!(d2r_skill_slot(Class::Assassin, 251).unwrap(), Some(0));
+    assert_eq!(d2r_skill_slot(Class::Barbarian, 155).unwrap(), Some(29));
+    assert_eq!(d2r_skill_slot(Class::Barbarian, 156).unwrap(), None);
+    assert_eq!(d2r_skill_slot(Class::Sorceress, 0).unwrap(), None);
+    assert_eq!(
+        d2r_skill_id(Class::Amazon, 30),
+        Err(NamedSkillError::InvalidSkillIndex { class: Class::Amazon, skill_index: 30 })
+    );
+    // Warlock ids are not checked against the RotW `skills.txt` yet.
+    assert_eq!(
+        d2r_skill_slot(Class::Warlock, 395),
+        Err(NamedSkillError::UnsupportedClass(Class::Warlock))
+    );
+    assert!(d2r_skill_id(Class::Warlock, 0).is_err());
+    assert!(d2r_skill_slot(Class::Unknown(0xFF), 400).is_err());
+}
@@ -0,0 +1,310 @@
+//! Default D2R skill tree layout and requirements.
+//!
+//! Entries follow the class-local slot order of [`super::d2r_skill_name`]. The Warlock tree
+//! is not included yet; load it from `skills.txt` through [`crate::data::GameData`].
+
+use super::NamedSkillError;
+use crate::Class;
+
+/// Character level required by each skill tree row.
+const ROW_LEVELS: [u8; 6] = [1, 6, 12, 18, 24, 30];
+
+/// Position and requirements of one skill in a class skill tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkillTreeEntry {
+    pub name: &'static str,
+    /// Skill tab, `1..=3`. Tabs are numbered in the order their first skill appears in the
+    /// class slot order; see [`d2r_skill_tabs`].
+    pub tab: u8,
+    /// Row from the top of the tab, `1..=6`.
+    pub row: u8,
+    /// Column from the left of the tab, `1..=3`.
+    pub column: u8,
+    pub required_level: u8,
+    /// Skills that need at least one hard point first, by name.
+    pub prerequisites: &'static [&'static str],
+}
+
+const fn skill(
+    name: &'static str,
+    tab: u8,
+    row: u8,
+    column: u8,
+    prerequisites: &'static [&'static str],
+) -> SkillTreeEntry {
+    SkillTreeEntry {
+        name,
+        tab,
+        row,
+        column,
+        required_level: ROW_LEVELS[row as usize - 1],
+        prerequisites,
+    }
+}
+
+const AMAZON_TABS: [&str; 3] =
+    ["Bow and Crossbow Skills", "Passive and Magic Skills", "Javelin and Spear Skills"];
+const AMAZON_TREE: [SkillTreeEntry; 30] = [
+    skill("Magic Arrow", 1, 1, 2, &[]),
+    skill("Fire Arrow", 1, 1, 3, &[]),
+    skill("Inner Sight", 2, 1, 1, &[]),
+    skill("Critical Strike", 2, 1, 3, &[]),
+    skill("Jab", 3, 1, 1, &[]),
+    skill("Cold Arrow", 1, 2, 1, &["Magic Arrow"]),
+    skill("Multiple Shot", 1, 2, 2, &["Magic Arrow"]),
+    skill("Dodge", 2, 2, 2, &[]),
+    skill("Power Strike", 3, 2, 2, &["Jab"]),
+    skill("Poison Javelin", 3, 2, 3, &[]),
+    skill("Exploding Arrow", 1, 3, 3, &["Multiple Shot", "Fire Arrow"]),
+    skill("Slow Missiles", 2, 3, 1, &["Inner Sight"]),
+    skill("Avoid", 2, 3, 2, &["Dodge"]),
+    skill("Impale", 3, 3, 1, &["Jab"]),
+    skill("Lightning Bolt", 3, 3, 3, &["Power Strike", "Poison Javelin"]),
+    skill("Ice Arrow", 1, 4, 1, &["Cold Arrow"]),
+    skill("Guided Arrow", 1, 4, 2, &["Cold Arrow", "Multiple Shot"]),
+    skill("Penetrate", 2, 4, 3, &["Critical Strike"]),
+    skill("Charged Strike", 3, 4, 2, &["Power Strike", "Lightning Bolt"]),
+    skill("Plague Javelin", 3, 4, 3, &["Lightning Bolt"]),
+    skill("Strafe", 1, 5, 2, &["Guided Arrow"]),
+    skill("Immolation Arrow", 1, 5, 3, &["Exploding Arrow"]),
+    skill("Decoy", 2, 5, 1, &["Slow Missiles"]),
+    skill("Evade", 2, 5, 2, &["Avoid"]),
+    skill("Fend", 3, 5, 1, &["Impale"]),
+    skill("Freezing Arrow", 1, 6, 1, &["Ice Arrow"]),
+    skill("Valkyrie", 2, 6, 1, &["Decoy", "Evade"]),
+    skill("Pierce", 2, 6, 3, &["Penetrate"]),
+    skill("Lightning Strike", 3, 6, 2, &["Charged Strike"]),
+    skill("Lightning Fury", 3, 6, 3, &["Plague Javelin"]),
+];
+
+const SORCERESS_TABS: [&str; 3] = ["Fire Spells", "Lightning Spells", "Cold Spells"];
+const SORCERESS_TREE: [SkillTreeEntry; 30] = [
+    skill("Fire Bolt", 1, 1, 2, &[]),
+    skill("Warmth", 1, 1, 3, &[]),
+    skill("Charged Bolt", 2, 1, 2, &[]),
+    skill("Ice Bolt", 3, 1, 2, &[]),
+    skill("Frozen Armor", 3, 1, 3, &[]),
+    skill("Inferno", 1, 2, 1, &[]),
+    skill("Static Field", 2, 2, 1, &[]),
+    skill("Telekinesis", 2, 2, 3, &[]),
+    skill("Frost Nova", 3, 2, 1, &[]),
+    skill("Ice Blast", 3, 2, 2, &["Ice Bolt"]),
+    skill("Blaze", 1, 3, 1, &["Inferno"]),
+    skill("Fire Ball", 1, 3, 2, &["Fire Bolt"]),
+    skill("Nova", 2, 3, 1, &["Static Field"]),
+    skill("Lightning", 2, 3, 2, &["Charged Bolt"]),
+    skill("Shiver Armor", 3, 3, 3, &["Ice Blast", "Frozen Armor"]),
+    skill("Fire Wall", 1, 4, 1, &["Blaze"]),
+    skill("Enchant", 1, 4, 3, &["Fire Ball", "Warmth"]),
+    skill("Chain Lightning", 2, 4, 2, &["Lightning"]),
+    skill("Teleport", 2, 4, 3, &["Telekinesis"]),
+    skill("Glacial Spike", 3, 4, 2, &["Ice Blast"]),
+    skill("Meteor", 1, 5, 2, &["Fire Wall", "Fire Ball"]),
+    skill("Thunder Storm", 2, 5, 1, &["Nova", "Chain Lightning"]),
+    skill("Energy Shield", 2, 5, 3, &["Chain Lightning", "Teleport"]),
+    skill("Blizzard", 3, 5, 1, &["Frost Nova", "Glacial Spike"]),
+    skill("Chilling Armor", 3, 5, 3, &["Shiver Armor"]),
+    skill("Fire Mastery", 1, 6, 2, &[]),
+    skill("Hydra", 1, 6, 3, &["Enchant"]),
+    skill("Lightning Mastery", 2, 6, 2, &[]),
+    skill("Frozen Orb", 3, 6, 1, &["Blizzard"]),
+    skill("Cold Mastery", 3, 6, 2, &[]),
+];
+
+const NECROMANCER_TABS: [&str; 3] = ["Curses", "Poison and Bone Spells", "Summoning Spells"];
+const NECROMANCER_TREE: [SkillTreeEntry; 30] = [
+    skill("Amplify Damage", 1, 1, 2, &[]),
+    skill("Teeth", 2, 1, 2, &[]),
+    skill("Bone Armor", 2, 1, 3, &[]),
+    skill("Skeleton Mastery", 3, 1, 1, &[]),
+    skill("Raise Skeleton", 3, 1, 2, &[]),
+    skill("Dim Vision", 1, 2, 1, &["Amplify Damage"]),
+    skill("Weaken", 1, 2, 3, &["Amplify Damage"]),
+    skill("Poison Dagger", 2, 2, 1, &[]),
+    skill("Corpse Explosion", 2, 2, 2, &["Teeth"]),
+    skill("Clay Golem", 3, 2, 3, &[]),
+    skill("Iron Maiden", 1, 3, 2, &["Amplify Damage"]),
+    skill("Terror", 1, 3, 3, &["Weaken"]),
+    skill("Bone Wall", 2, 3, 3, &["Bone Armor"]),
+    skill("Golem Mastery", 3, 3, 1, &["Clay Golem"]),
+    skill("Raise Skeletal Mage", 3, 3, 2, &["Raise Skeleton"]),
+    skill("Confuse", 1, 4, 1, &["Dim Vision"]),
+    skill("Life Tap", 1, 4, 2, &["Iron Maiden"]),
+    skill("Poison Explosion", 2, 4, 1, &["Poison Dagger", "Corpse Explosion"]),
+    skill("Bone Spear", 2, 4, 2, &["Corpse Explosion"]),
+    skill("Blood Golem", 3, 4, 3, &["Clay Golem"]),
+    skill("Attract", 1, 5, 1, &["Confuse"]),
+    skill("Decrepify", 1, 5, 3, &["Terror"]),
+    skill("Bone Prison", 2, 5, 3, &["Bone Spear", "Bone Wall"]),
+    skill("Summon Resist", 3, 5, 1, &["Golem Mastery"]),
+    skill("Iron Golem", 3, 5, 3, &["Blood Golem"]),
+    skill("Lower Resist", 1, 6, 2, &["Life Tap", "Decrepify"]),
+    skill("Poison Nova", 2, 6, 1, &["Poison Explosion"]),
+    skill("Bone Spirit", 2, 6, 2, &["Bone Spear"]),
+    skill("Fire Golem", 3, 6, 3, &["Iron Golem"]),
+    skill("Revive", 3, 6, 2, &["Raise Skeletal Mage", "Iron Golem"]),
+];
+
+const PALADIN_TABS: [&str; 3] = ["Combat Skills", "Offensive Auras", "Defensive Auras"];
+const PALADIN_TREE: [SkillTreeEntry; 30] = [
+    skill("Sacrifice", 1, 1, 1, &[]),
+    skill("Smite", 1, 1, 3, &[]),
+    skill("Might", 2, 1, 1, &[]),
+    skill("Prayer", 3, 1, 1, &[]),
+    skill("Resist Fire", 3, 1, 3, &[]),
+    skill("Holy Bolt", 1, 2, 2, &[]),
+    skill("Holy Fire", 2, 2, 2, &["Might"]),
+    skill("Thorns", 2, 2, 3, &[]),
+    skill("Defiance", 3, 2, 2, &[]),
+    skill("Resist Cold", 3, 2, 3, &[]),
+    skill("Zeal", 1, 3, 1, &["Sacrifice"]),
+    skill("Charge", 1, 3, 3, &["Smite"]),
+    skill("Blessed Aim", 2, 3, 1, &["Might"]),
+    skill("Cleansing", 3, 3, 1, &["Prayer"]),
+    skill("Resist Lightning", 3, 3, 3, &[]),
+    skill("Vengeance", 1, 4, 1, &["Zeal"]),
+    skill("Blessed Hammer", 1, 4, 2, &["Holy Bolt"]),
+    skill("Concentration", 2, 4, 1, &["Blessed Aim"]),
+    skill("Holy Freeze", 2, 4, 2, &["Holy Fire"]),
+    skill("Vigor", 3, 4, 2, &["Cleansing", "Defiance"]),
+    skill("Conversion", 1, 5, 1, &["Vengeance"]),
+    skill("Holy Shield", 1, 5, 3, &["Charge", "Blessed Hammer"]),
+    skill("Holy Shock", 2, 5, 2, &["Holy Freeze"]),
+    skill("Sanctuary", 2, 5, 3, &["Thorns", "Holy Shock"]),
+    skill("Meditation", 3, 5, 1, &["Cleansing"]),
+    skill("Fist of the Heavens", 1, 6, 2, &["Blessed Hammer", "Conversion"]),
+    skill("Fanaticism", 2, 6, 1, &["Concentration"]),
+    skill("Conviction", 2, 6, 3, &["Sanctuary"]),
+    skill("Redemption", 3, 6, 2, &["Vigor"]),
+    skill("Salvation", 3, 6, 3, &["Resist Fire", "Resist Cold", "Resist Lightning"]),
+];
+
+const BARBARIAN_TABS: [&str; 3] = ["Combat Skills", "Combat Masteries", "Warcries"];
+const BARBARIAN_TREE: [SkillTreeEntry; 30] = [
+    skill("Bash", 1, 1, 2, &[]),
+    skill("Sword Mastery", 2, 1, 1, &[]),
+    skill("Axe Mastery", 2, 1, 2, &[]),
+    skill("Mace Mastery", 2, 1, 3, &[]),
+    skill("Howl", 3, 1, 1, &[]),
+    skill("Find Potion", 3, 1, 3, &[]),
+    skill("Leap", 1, 2, 1, &[]),
+    skill("Double Swing", 1, 2, 3, &["Bash"]),
+    skill("Polearm Mastery", 2, 2, 1, &[]),
+    skill("Throwing Mastery", 2, 2, 2, &[]),
+    skill("Spear Mastery", 2, 2, 3, &[]),
+    skill("Taunt", 3, 2, 1, &["Howl"]),
+    skill("Shout", 3, 2, 2, &["Howl"]),
+    skill("Stun", 1, 3, 2, &["Bash"]),
+    skill("Double Throw", 1, 3, 3, &["Double Swing"]),
+    skill("Increased Stamina", 2, 3, 3, &[]),
+    skill("Find Item", 3, 3, 3, &["Find Potion"]),
+    skill("Leap Attack", 1, 4, 1, &["Leap"]),
+    skill("Concentrate", 1, 4, 2, &["Stun"]),
+    skill("Iron Skin", 2, 4, 2, &[]),
+    skill("Battle Cry", 3, 4, 1, &["Taunt"]),
+    skill("Frenzy", 1, 5, 3, &["Double Throw"]),
+    skill("Increased Speed", 2, 5, 3, &["Increased Stamina"]),
+    skill("Battle Orders", 3, 5, 2, &["Shout"]),
+    skill("Grim Ward", 3, 5, 3, &["Find Item"]),
+    skill("Whirlwind", 1, 6, 2, &["Leap Attack", "Concentrate"]),
+    skill("Berserk", 1, 6, 3, &["Concentrate"]),
+    skill("Natural Resistance", 2, 6, 2, &["Iron Skin"]),
+    skill("War Cry", 3, 6, 1, &["Battle Cry", "Battle Orders"]),
+    skill("Battle Command", 3, 6, 2, &["Battle Orders"]),
+];
+
+const DRUID_TABS: [&str; 3] = ["Summoning", "Shape Shifting", "Elemental"];
+const DRUID_TREE: [SkillTreeEntry; 30] = [
+    skill("Raven", 1, 1, 1, &[]),
+    skill("Poison Creeper", 1, 1, 3, &[]),
+    skill("Werewolf", 2, 1, 1, &[]),
+    skill("Lycanthropy", 2, 1, 2, &[]),
+    skill("Firestorm", 3, 1, 1, &[]),
+    skill("Oak Sage", 1, 2, 2, &[]),
+    skill("Summon Spirit Wolf", 1, 2, 1, &["Raven"]),
+    skill("Werebear", 2, 2, 3, &[]),
+    skill("Molten Boulder", 3, 2, 1, &["Firestorm"]),
+    skill("Arctic Blast", 3, 2, 3, &[]),
+    skill("Carrion Vine", 1, 3, 3, &["Poison Creeper"]),
+    skill("Feral Rage", 2, 3, 1, &["Werewolf"]),
+    skill("Maul", 2, 3, 3, &["Werebear"]),
+    skill("Fissure", 3, 3, 1, &["Molten Boulder"]),
+    skill("Cyclone Armor", 3, 3, 3, &["Arctic Blast"]),
+    skill("Heart of Wolverine", 1, 4, 2, &["Oak Sage"]),
+    skill("Summon Dire Wolf", 1, 4, 1, &["Summon Spirit Wolf"]),
+    skill("Rabies", 2, 4, 1, &["Feral Rage"]),
+    skill("Fire Claws", 2, 4, 2, &["Feral Rage", "Maul"]),
+    skill("Twister", 3, 4, 3, &["Cyclone Armor"]),
+    skill("Solar Creeper", 1, 5, 3, &["Carrion Vine"]),
+    skill("Hunger", 2, 5, 2, &["Fire Claws"]),
+    skill("Shock Wave", 2, 5, 3, &["Maul"]),
+    skill("Volcano", 3, 5, 1, &["Fissure"]),
+    skill("Tornado", 3, 5, 3, &["Twister"]),
+    skill("Spirit of Barbs", 1, 6, 2, &["Heart of Wolverine"]),
+    skill("Summon Grizzly", 1, 6, 1, &["Summon Dire Wolf"]),
+    skill("Fury", 2, 6, 1, &["Rabies"]),
+    skill("Armageddon", 3, 6, 2, &["Volcano", "Hurricane"]),
+    skill("Hurricane", 3, 6, 3, &["Tornado"]),
+];
+
+const ASSASSIN_TABS: [&str; 3] = ["Traps", "Shadow Disciplines", "Martial Arts"];
+const ASSASSIN_TREE: [SkillTreeEntry; 30] = [
+    skill("Fire Blast", 1, 1, 2, &[]),
+    skill("Claw Mastery", 2, 1, 1, &[]),
+    skill("Psychic Hammer", 2, 1, 2, &[]),
+    skill("Tiger Strike", 3, 1, 2, &[]),
+    skill("Dragon Talon", 3, 1, 3, &[]),
+    skill("Shock Web", 1, 2, 3, &["Fire Blast"]),
+    skill("Blade Sentinel", 1, 2, 1, &[]),
+    skill("Burst of Speed", 2, 2, 3, &["Claw Mastery"]),
+    skill("Fists of Fire", 3, 2, 1, &[]),
+    skill("Dragon Claw", 3, 2, 3, &["Dragon Talon"]),
+    skill("Charged Bolt Sentry", 1, 3, 3, &["Shock Web"]),
+    skill("Wake of Fire", 1, 3, 2, &["Fire Blast"]),
+    skill("Weapon Block", 2, 3, 1, &["Claw Mastery"]),
+    skill("Cloak of Shadows", 2, 3, 2, &["Psychic Hammer"]),
+    skill("Cobra Strike", 3, 3, 2, &["Tiger Strike"]),
+    skill("Blade Fury", 1, 4, 1, &["Blade Sentinel"]),
+    skill("Fade", 2, 4, 3, &["Burst of Speed"]),
+    skill("Shadow Warrior", 2, 4, 1, &["Weapon Block", "Cloak of Shadows"]),
+    skill("Claws of Thunder", 3, 4, 1, &["Fists of Fire"]),
+    skill("Dragon Tail", 3, 4, 3, &["Dragon Claw"]),
+    skill("Lightning Sentry", 1, 5, 3, &["Charged Bolt Sentry"]),
+    skill("Wake of Inferno", 1, 5, 2, &["Wake of Fire"]),
+    skill("Mind Blast", 2, 5, 2, &["Cloak of Shadows"]),
+    skill("Blades of Ice", 3, 5, 1, &["Claws of Thunder"]),
+    skill("Dragon Flight", 3, 5, 3, &["Dragon Tail"]),
+    skill("Death Sentry", 1, 6, 3, &["Lightning Sentry"]),
+    skill("Blade Shield", 1, 6, 1, &["Blade Fury"]),
+    skill("Venom", 2, 6, 3, &["Fade"]),
+    skill("Shadow Master", 2, 6, 1, &["Shadow Warrior"]),
+    skill("Phoenix Strike", 3, 6, 2, &["Blades of Ice", "Cobra Strike"]),
+];
+
+/// Skill tree entries for a class, in class-local slot order.
+pub fn d2r_skill_tree(class: Class) -> Result<&'static [SkillTreeEntry; 30], NamedSkillError> {
+    match class {
+        Class::Amazon => Ok(&AMAZON_TREE),
+        Class::Sorceress => Ok(&SORCERESS_TREE),
+        Class::Necromancer => Ok(&NECROMANCER_TREE),
+        Class::Paladin => Ok(&PALADIN_TREE),
+        Class::Barbarian => Ok(&BARBARIAN_TREE),
+        Class::Druid => Ok(&DRUID_TREE),
+        Class::Assassin => Ok(&ASSASSIN_TREE),
+        Class::Warlock | Class::Unknown(_) => Err(NamedSkillError::UnsupportedClass(class)),
+    }
+}
+
+/// Skill tab names for a class, indexed by [`SkillTreeEntry::tab`] minus one.
+pub fn d2r_skill_tabs(class: Class) -> Result<[&'static str; 3], NamedSkillError> {
+    match class {
+        Class::Amazon => Ok(AMAZON_TABS),
+        Class::Sorceress => Ok(SORCERESS_TABS),
+        Class::Necromancer => Ok(NECROMANCER_TABS),
+        Class::Paladin => Ok(PALADIN_TABS),
+        Class::Barbarian => Ok(BARBARIAN_TABS),
+        Class::Druid => Ok(DRUID_TABS),
+        Class::Assassin => Ok(ASSASSIN_TABS),
+        Class::Warlock | Class::Unknown(_) => Err(NamedSkillError::UnsupportedClass(class)),
+    }
+}
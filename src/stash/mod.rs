@@ -0,0 +1,324 @@
+//! Shared stash (`.d2i`) model.
+//!
+//! A shared stash is a sequence of pages. Each page has a 64-byte header
+//! (`0x55AA55AA` signature, unknown field, version, gold, page length, reserved bytes)
+//! followed by a `JM` item list using the item layout of the page version. Shared stashes
+//! only exist in D2R, so only the `v99` and `v105` page versions are accepted.
+//!
+//! This module is experimental: the layout has not been checked against real `.d2i` files,
+//! and the unknown and reserved header fields are written back as read.
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::format::FormatId;
+use crate::items::{self, ItemList};
+use crate::utils::{u32_from, HexBytes};
+use crate::{EncodeError, IssueKind, IssueSeverity, ParseHardError, ParseIssue, Strictness};
+
+#[cfg(test)]
+mod tests;
+
+/// Page header signature (`0x55AA55AA` little-endian).
+pub const PAGE_SIGNATURE: [u8; 4] = [0x55, 0xAA, 0x55, 0xAA];
+/// Length of a page header, including reserved bytes.
+pub const PAGE_HEADER_LENGTH: usize = 64;
+const RESERVED_RANGE: std::ops::Range<usize> = 20..PAGE_HEADER_LENGTH;
+
+/// One stash page.
+#[serde_as]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct StashPage {
+    /// Header field at offset 4, written as `1`.
+    pub unknown: u32,
+    pub gold: u32,
+    pub items: ItemList,
+    /// Header bytes 20..64, written as zeros.
+    #[serde_as(as = "HexBytes")]
+    reserved: Vec<u8>,
+    /// Bytes inside the page after the item list.
+    #[serde_as(as = "HexBytes")]
+    #[serde(default)]
+    extra: Vec<u8>,
+    /// Page body kept when its item list could not be decoded.
+    #[serde_as(as = "HexBytes")]
+    #[serde(default)]
+    raw_body: Vec<u8>,
+    /// Item layout of `raw_body`, taken from the page's own version field.
+    #[serde(default)]
+    raw_format: Option<FormatId>,
+}
+
+impl Default for StashPage {
+    fn default() -> Self {
+        StashPage {
+            unknown: 1,
+            gold: 0,
+            items: ItemList::default(),
+            reserved: vec![0; RESERVED_RANGE.len()],
+            extra: Vec::new(),
+            raw_body: Vec::new(),
+            raw_format: None,
+        }
+    }
+}
+
+impl StashPage {
+    /// Whether this page body was preserved as raw bytes because it could not be decoded.
+    pub fn is_raw(&self) -> bool {
+        !self.raw_body.is_empty()
+    }
+
+    /// Format of the page's raw body, which can only be written back in that format.
+    pub fn raw_format(&self) -> Option<FormatId> {
+        self.raw_format
+    }
+}
+
+/// Full shared stash model.
+#[serde_as]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct Stash {
+    pub pages: Vec<StashPage>,
+    /// Bytes after the last readable page (lax mode only).
+    #[serde_as(as = "HexBytes")]
+    #[serde(default)]
+    trailing: Vec<u8>,
+    format: FormatId,
+}
+
+/// Parse output for a shared stash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedStash {
+    pub stash: Stash,
+    /// Non-fatal issues collected during parsing in lax mode.
+    pub issues: Vec<ParseIssue>,
+}
+
+fn stash_issue(kind: IssueKind, message: String, offset: usize) -> ParseIssue {
+    ParseIssue {
+        severity: IssueSeverity::Error,
+        kind,
+        section: Some("stash".to_string()),
+        message,
+        offset: Some(offset),
+        expected: None,
+        found: None,
+    }
+}
+
+/// Shared stashes only exist in D2R, so pages use the `v99` or `v105` item layout.
+fn is_stash_format(format: FormatId) -> bool {
+    matches!(format, FormatId::V99 | FormatId::V105)
+}
+
+/// Push `issue` in lax mode, or turn it into a hard error in strict mode.
+fn report(
+    issues: &mut Vec<ParseIssue>,
+    strictness: Strictness,
+    issue: ParseIssue,
+) -> Result<(), ParseHardError> {
+    if strictness == Strictness::Strict {
+        return Err(ParseHardError { message: format!("Cannot parse stash: {}", issue.message) });
+    }
+    issues.push(issue);
+    Ok(())
+}
+
+impl Stash {
+    /// Build an empty stash with `page_count` pages.
+    pub fn new(format: FormatId, page_count: usize) -> Stash {
+        Stash { pages: vec![StashPage::default(); page_count], trailing: Vec::new(), format }
+    }
+
+    /// Format of the first page (or the one set by [`Stash::new`]).
+    pub fn format(&self) -> FormatId {
+        self.format
+    }
+
+    /// Total gold across all pages.
+    pub fn total_gold(&self) -> u64 {
+        self.pages.iter().map(|page| u64::from(page.gold)).sum()
+    }
+
+    /// Parse shared stash bytes.
+    pub fn parse(byte_slice: &[u8], strictness: Strictness) -> Result<ParsedStash, ParseHardError> {
+        let mut issues = Vec::new();
+        let mut pages = Vec::new();
+        let mut stash_format = None;
+        let mut offset = 0;
+
+        while offset < byte_slice.len() {
+            let Some(header) = byte_slice.get(offset..offset + PAGE_HEADER_LENGTH) else {
+                report(
+                    &mut issues,
+                    strictness,
+                    stash_issue(
+                        IssueKind::TruncatedSection,
+                        format!(
+                            "Page header at byte {offset} is truncated: {} bytes left.",
+                            byte_slice.len() - offset
+                        ),
+                        offset,
+                    ),
+                )?;
+                break;
+            };
+            if header[0..4] != PAGE_SIGNATURE {
+                report(
+                    &mut issues,
+                    strictness,
+                    stash_issue(
+                        IssueKind::InvalidSignature,
+                        format!("Page at byte {offset} does not start with 55 AA 55 AA."),
+                        offset,
+                    ),
+                )?;
+                break;
+            }
+
+            let version = u32_from(&header[8..12], "stash page version")?;
+            let page_length = u32_from(&header[16..20], "stash page length")? as usize;
+            let format = match FormatId::from_version(version) {
+                Some(format) if is_stash_format(format) => format,
+                Some(format) => {
+                    return Err(ParseHardError {
+                        message: format!(
+                            "Cannot parse stash: page at byte {offset} has version {version} ({format:?}), but shared stashes only exist in D2R (v99 and v105)."
+                        ),
+                    });
+                }
+                None => {
+                    let fallback = match FormatId::fallback_for_unknown_version(version, None) {
+                        fallback if is_stash_format(fallback) => fallback,
+                        _ => FormatId::V99,
+                    };
+                    report(
+                        &mut issues,
+                        strictness,
+                        stash_issue(
+                            IssueKind::UnsupportedVersion,
+                            format!(
+                                "Unsupported stash page version {version}. Falling back to {fallback:?}."
+                            ),
+                            offset + 8,
+                        ),
+                    )?;
+                    fallback
+                }
+            };
+            stash_format.get_or_insert(format);
+
+            if page_length < PAGE_HEADER_LENGTH || offset + page_length > byte_slice.len() {
+                report(
+                    &mut issues,
+                    strictness,
+                    stash_issue(
+                        IssueKind::InconsistentLayout,
+                        format!(
+                            "Page at byte {offset} declares length {page_length}, but {} bytes are available.",
+                            byte_slice.len() - offset
+                        ),
+                        offset + 16,
+                    ),
+                )?;
+                break;
+            }
+
+            let page_bytes = &byte_slice[offset..offset + page_length];
+            let mut page = StashPage {
+                unknown: u32_from(&header[4..8], "stash page unknown")?,
+                gold: u32_from(&header[12..16], "stash page gold")?,
+                reserved: header[RESERVED_RANGE].to_vec(),
+                ..StashPage::default()
+            };
+            let stat_costs = items::default_stat_costs(format);
+            match items::parse_verified_list(
+                page_bytes,
+                PAGE_HEADER_LENGTH,
+                format,
+                stat_costs,
+                "Stash page",
+            ) {
+                Ok((item_list, list_end)) => {
+                    page.items = item_list;
+                    page.extra = page_bytes[list_end..].to_vec();
+                }
+                Err(error) => {
+                    report(
+                        &mut issues,
+                        strictness,
+                        stash_issue(
+                            IssueKind::InvalidValue,
+                            format!("Page {} items kept as raw bytes: {error}", pages.len()),
+                            offset + PAGE_HEADER_LENGTH,
+                        ),
+                    )?;
+                    page.raw_body = page_bytes[PAGE_HEADER_LENGTH..].to_vec();
+                    page.raw_format = Some(format);
+                }
+            }
+            pages.push(page);
+            offset += page_length;
+        }
+
+        let stash = Stash {
+            pages,
+            trailing: byte_slice[offset..].to_vec(),
+            format: stash_format.unwrap_or_default(),
+        };
+        Ok(ParsedStash { stash, issues })
+    }
+
+    /// Encode to a specific output format, recomputing every page length.
+    pub fn encode_for(&self, format: FormatId) -> Result<Vec<u8>, EncodeError> {
+        if let FormatId::Unknown(version) = format {
+            return Err(EncodeError::new(format!(
+                "Cannot encode stash to unknown format version {version}. Choose a known target format."
+            )));
+        }
+        if !is_stash_format(format) {
+            return Err(EncodeError::new(format!(
+                "Cannot encode stash to {format:?}: shared stashes only exist in D2R (v99 and v105)."
+            )));
+        }
+
+        let mut byte_vector = Vec::new();
+        for (page_index, page) in self.pages.iter().enumerate() {
+            let mut body = if page.is_raw() {
+                // Pages of one stash can carry different versions; check this page's own.
+                if page.raw_format != Some(format) {
+                    return Err(EncodeError::new(format!(
+                        "Stash page {page_index} could not be decoded and cannot be converted to {format:?}."
+                    )));
+                }
+                page.raw_body.clone()
+            } else {
+                let mut body = page.items.to_bytes(format).map_err(|error| {
+                    EncodeError::new(format!("Stash page {page_index}: {error}"))
+                })?;
+                body.extend_from_slice(&page.extra);
+                body
+            };
+            if page.reserved.len() != RESERVED_RANGE.len() {
+                return Err(EncodeError::new(format!(
+                    "Stash page {page_index} has {} reserved header bytes, expected {}.",
+                    page.reserved.len(),
+                    RESERVED_RANGE.len()
+                )));
+            }
+            let page_length = u32::try_from(PAGE_HEADER_LENGTH + body.len())
+                .map_err(|_| EncodeError::new(format!("Stash page {page_index} is too large.")))?;
+
+            byte_vector.extend_from_slice(&PAGE_SIGNATURE);
+            byte_vector.extend_from_slice(&page.unknown.to_le_bytes());
+            byte_vector.extend_from_slice(&format.version().to_le_bytes());
+            byte_vector.extend_from_slice(&page.gold.to_le_bytes());
+            byte_vector.extend_from_slice(&page_length.to_le_bytes());
+            byte_vector.extend_from_slice(&page.reserved);
+            byte_vector.append(&mut body);
+        }
+        byte_vector.extend_from_slice(&self.trailing);
+        Ok(byte_vector)
+    }
+}
@@ -0,0 +1,131 @@
+use super::*;
+use crate::items::Item;
+use crate::{Save, Strictness};
+
+fn fixture_items(bytes: &[u8], count: usize) -> Vec<Item> {
+    let save = Save::parse(bytes, Strictness::Strict).unwrap().save;
+    save.items.player.items.into_iter().take(count).collect()
+}
+
+fn sample_stash(format: FormatId) -> Stash {
+    let mut stash = Stash::new(format, 3);
+    stash.pages[0].gold = 2_500_000;
+    stash.pages[0].items.items = fixture_items(include_bytes!("../../assets/test/Ayame.d2s"), 12);
+    stash.pages[2].items.items = fixture_items(include_bytes!("../../assets/test/Joe.d2s"), 3);
+    stash
+}
+
+#[test]
+fn empty_stash_page_layout() {
+    let bytes = Stash::new(FormatId::V99, 1).encode_for(FormatId::V99).unwrap();
+
+    assert_eq!(bytes.len(), PAGE_HEADER_LENGTH + 4);
+    assert_eq!(&bytes[0..4], &PAGE_SIGNATURE);
+    assert_eq!(&bytes[4..8], &1u32.to_le_bytes());
+    assert_eq!(&bytes[8..12], &99u32.to_le_bytes());
+    assert_eq!(&bytes[16..20], &68u32.to_le_bytes());
+    assert!(bytes[20..64].iter().all(|byte| *byte == 0));
+    assert_eq!(&bytes[64..], &[0x4A, 0x4D, 0x00, 0x00]);
+}
+
+#[test]
+fn stash_roundtrip_is_byte_identical() {
+    for format in [FormatId::V99, FormatId::V105] {
+        let stash = sample_stash(format);
+        let bytes = stash.encode_for(format).unwrap();
+        let parsed = Stash::parse(&bytes, Strictness::Strict).unwrap();
+
+        assert!(parsed.issues.is_empty());
+        assert_eq!(parsed.stash, stash);
+        assert_eq!(parsed.stash.format(), format);
+        assert_eq!(parsed.stash.total_gold(), 2_500_000);
+        assert_eq!(parsed.stash.encode_for(format).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn stash_converts_between_formats() {
+    let stash = sample_stash(FormatId::V99);
+    let bytes = stash.encode_for(FormatId::V105).unwrap();
+    let parsed = Stash::parse(&bytes, Strictness::Strict).unwrap().stash;
+
+    assert_eq!(parsed.format(), FormatId::V105);
+    assert_eq!(parsed.pages, stash.pages);
+}
+
+#[test]
+fn page_lengths_follow_edits() {
+    let mut stash = sample_stash(FormatId::V99);
+    let moved_item = stash.pages[0].items.items.pop().unwrap();
+    stash.pages[1].items.items.push(moved_item);
+
+    let bytes = stash.encode_for(FormatId::V99).unwrap();
+    let parsed = Stash::parse(&bytes, Strictness::Strict).unwrap().stash;
+    assert_eq!(parsed.pages[0].items.items.len(), 11);
+    assert_eq!(parsed.pages[1].items.items.len(), 1);
+}
+
+#[test]
+fn strict_rejects_bad_signature_and_lax_keeps_bytes() {
+    let mut bytes = sample_stash(FormatId::V99).encode_for(FormatId::V99).unwrap();
+    let second_page = u32_from(&bytes[16..20], "page length").unwrap() as usize;
+    bytes[second_page] = 0x00;
+
+    assert!(Stash::parse(&bytes, Strictness::Strict).is_err());
+
+    let parsed = Stash::parse(&bytes, Strictness::Lax).unwrap();
+    assert_eq!(parsed.stash.pages.len(), 1);
+    assert!(matches!(parsed.issues[0].kind, IssueKind::InvalidSignature));
+    assert_eq!(parsed.stash.encode_for(FormatId::V99).unwrap(), bytes);
+}
+
+#[test]
+fn lax_keeps_undecodable_page_raw() {
+    let mut bytes = Stash::new(FormatId::V105, 1).encode_for(FormatId::V105).unwrap();
+    bytes[66] = 0x01;
+    bytes.extend_from_slice(&[0xFF; 4]);
+    bytes[16..20].copy_from_slice(&72u32.to_le_bytes());
+
+    assert!(Stash::parse(&bytes, Strictness::Strict).is_err());
+
+    let parsed = Stash::parse(&bytes, Strictness::Lax).unwrap();
+    assert!(parsed.stash.pages[0].is_raw());
+    assert_eq!(parsed.stash.encode_for(FormatId::V105).unwrap(), bytes);
+    assert!(parsed.stash.encode_for(FormatId::V99).is_err());
+}
+
+#[test]
+fn raw_page_is_only_written_in_its_own_format() {
+    // A decodable v99 page followed by an undecodable v105 page.
+    let mut bytes = Stash::new(FormatId::V99, 1).encode_for(FormatId::V99).unwrap();
+    let mut raw_page = Stash::new(FormatId::V105, 1).encode_for(FormatId::V105).unwrap();
+    raw_page[66] = 0x01;
+    raw_page.extend_from_slice(&[0xFF; 4]);
+    raw_page[16..20].copy_from_slice(&72u32.to_le_bytes());
+    bytes.extend_from_slice(&raw_page);
+
+    let stash = Stash::parse(&bytes, Strictness::Lax).unwrap().stash;
+    assert_eq!(stash.format(), FormatId::V99);
+    assert_eq!(stash.pages[1].raw_format(), Some(FormatId::V105));
+    assert!(stash.encode_for(FormatId::V99).is_err());
+    assert!(stash.encode_for(FormatId::V105).is_ok());
+}
+
+#[test]
+fn stash_rejects_classic_formats() {
+    for format in [FormatId::V71, FormatId::V87, FormatId::V89, FormatId::V92, FormatId::V96] {
+        assert!(Stash::new(FormatId::V99, 1).encode_for(format).is_err(), "{format:?}");
+
+        let mut bytes = Stash::new(FormatId::V99, 1).encode_for(FormatId::V99).unwrap();
+        bytes[8..12].copy_from_slice(&format.version().to_le_bytes());
+        for strictness in [Strictness::Strict, Strictness::Lax] {
+            let error = Stash::parse(&bytes, strictness).unwrap_err();
+            assert!(error.message.contains("only exist in D2R"), "{error}");
+        }
+    }
+
+    // Unknown versions never fall back to a classic layout.
+    let mut bytes = Stash::new(FormatId::V99, 1).encode_for(FormatId::V99).unwrap();
+    bytes[8..12].copy_from_slice(&97u32.to_le_bytes());
+    assert_eq!(Stash::parse(&bytes, Strictness::Lax).unwrap().stash.format(), FormatId::V99);
+}
@@ -1,8 +1,12 @@
 use std::cmp;
+use std::fmt;
 use std::time::SystemTime;
 
 use crate::ParseHardError;
 use bit::BitIndex;
+use serde::de::{self, Error as _, SeqAccess, Visitor};
+use serde::{Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
 
 const BITS_PER_BYTE: usize = 8;
 const MAX_U32_BIT_WIDTH: usize = 32;
@@ -136,7 +140,7 @@ pub fn u8_from(slice: &[u8], name: &'static str) -> Result<u8, ParseHardError> {
     })
 }
 
-#[derive(Default, PartialEq, Eq, Debug)]
+#[derive(Default, PartialEq, Eq, Debug, Clone, Copy)]
 pub struct BytePosition {
     pub current_byte: usize,
     pub current_bit: usize,
@@ -277,6 +281,128 @@ pub fn read_bits(
     }
 }
 
+/// `serde_with` adapter for raw byte regions.
+///
+/// Human-readable formats (JSON) get a lowercase hex string, binary formats get raw bytes.
+/// Deserialization also accepts a sequence of byte values.
+pub struct HexBytes;
+
+impl<T: AsRef<[u8]>> SerializeAs<T> for HexBytes {
+    fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = source.as_ref();
+        if serializer.is_human_readable() {
+            let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+            serializer.serialize_str(&hex)
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+}
+
+impl<'de, T: TryFrom<Vec<u8>>> DeserializeAs<'de, T> for HexBytes {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        let bytes = if deserializer.is_human_readable() {
+            deserializer.deserialize_any(HexBytesVisitor)?
+        } else {
+            deserializer.deserialize_byte_buf(HexBytesVisitor)?
+        };
+        let length = bytes.len();
+        T::try_from(bytes)
+            .map_err(|_| D::Error::custom(format!("Unexpected byte region length {length}.")))
+    }
+}
+
+struct HexBytesVisitor;
+
+impl<'de> Visitor<'de> for HexBytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex string or a sequence of bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Vec<u8>, E> {
+        if !value.len().is_multiple_of(2) {
+            return Err(E::custom(format!("Hex string has odd length {}.", value.len())));
+        }
+        (0..value.len())
+            .step_by(2)
+            .map(|index| {
+                value
+                    .get(index..index + 2)
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(|| E::custom(format!("Invalid hex byte at offset {index}.")))
+            })
+            .collect()
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+/// `serde_with` adapter for 64-bit bitfields.
+///
+/// Human-readable formats get a 16-digit lowercase hex string, since JSON numbers above
+/// 2^53 lose precision in most readers. Binary formats get the integer. Deserialization
+/// also accepts a plain number.
+pub struct HexU64;
+
+impl SerializeAs<u64> for HexU64 {
+    fn serialize_as<S: Serializer>(source: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("{source:016x}"))
+        } else {
+            serializer.serialize_u64(*source)
+        }
+    }
+}
+
+impl<'de> DeserializeAs<'de, u64> for HexU64 {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(HexU64Visitor)
+        } else {
+            deserializer.deserialize_u64(HexU64Visitor)
+        }
+    }
+}
+
+struct HexU64Visitor;
+
+impl Visitor<'_> for HexU64Visitor {
+    type Value = u64;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex string or an unsigned integer")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+        u64::from_str_radix(value, 16)
+            .map_err(|_| E::custom(format!("Invalid 64-bit hex value \"{value}\".")))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+        u64::try_from(value).map_err(|_| E::custom(format!("Negative bitfield value {value}.")))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::BytePosition;
@@ -1,8 +1,12 @@
+use crate::character::experience::experience_range_for_level;
 use crate::character::mercenary::{
     level_from_experience as mercenary_level_from_experience, mercenary_name_count_for_variant_id,
     xp_rate_for_variant_id,
 };
+use crate::data::GameData;
 use crate::quests::{Quest, QuestFlag};
+use crate::respec;
+use crate::skills;
 use crate::{Act, Difficulty, ExpansionType, Save};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -20,11 +24,17 @@ pub enum ValidationCode {
     ImpossibleDifficultySelection,
     ImpossibleActSelection,
     MercenaryDataWithoutHire,
-    MercenaryHireStateToggleUnsupported,
     MercenaryVariantUnknown,
     MercenaryNameIdOutOfRange,
     MercenaryLevelImpossible,
     QuestStateImpossible,
+    MaxVitalsMismatch,
+    ExperienceOutOfLevelRange,
+    QuestRewardPointsMismatch,
+    SkillPointsExceedBudget,
+    StatPointsExceedBudget,
+    SkillPrerequisiteMissing,
+    SkillLevelRequirementUnmet,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -159,6 +169,26 @@ fn validate_level_sync(save: &Save, issues: &mut Vec<ValidationIssue>) {
     }
 }
 
+fn validate_experience(save: &Save, issues: &mut Vec<ValidationIssue>) {
+    let level = save.attributes.level();
+    // Out-of-range levels are reported by `validate_level_sync`.
+    let Some(range) = experience_range_for_level(level) else {
+        return;
+    };
+
+    let experience = save.attributes.experience.value;
+    if !range.contains(&experience) {
+        issues.push(warning(
+            ValidationCode::ExperienceOutOfLevelRange,
+            format!(
+                "Experience ({experience}) is outside the range for level {level} ({} to {}).",
+                range.start(),
+                range.end()
+            ),
+        ));
+    }
+}
+
 fn minimum_progression_for(save: &Save) -> u8 {
     let completed_act_count = if save.expansion_type() == ExpansionType::Classic { 4 } else { 5 };
 
@@ -289,7 +319,25 @@ fn validate_quest_state(save: &Save, issues: &mut Vec<ValidationIssue>) {
     }
 }
 
-fn validate_mercenary_level(save: &Save, issues: &mut Vec<ValidationIssue>) {
+/// XP rate and name count for a mercenary variant, from `game_data` or the built-in D2R table.
+fn mercenary_variant(
+    game_data: Option<&GameData>,
+    variant_id: u16,
+) -> Option<(u32, Option<usize>)> {
+    match game_data {
+        Some(game_data) => {
+            game_data.hireling(variant_id).map(|hireling| (hireling.xp_rate, hireling.name_count))
+        }
+        None => xp_rate_for_variant_id(variant_id)
+            .map(|xp_rate| (xp_rate, mercenary_name_count_for_variant_id(variant_id))),
+    }
+}
+
+fn validate_mercenary_level(
+    save: &Save,
+    game_data: Option<&GameData>,
+    issues: &mut Vec<ValidationIssue>,
+) {
     let mercenary = save.character.mercenary;
     if !mercenary.is_hired() {
         if mercenary.has_data_without_hire() {
@@ -301,7 +349,7 @@ fn validate_mercenary_level(save: &Save, issues: &mut Vec<ValidationIssue>) {
         return;
     }
 
-    let Some(xp_rate) = xp_rate_for_variant_id(mercenary.variant_id) else {
+    let Some((xp_rate, name_count)) = mercenary_variant(game_data, mercenary.variant_id) else {
         issues.push(issue(
             ValidationCode::MercenaryVariantUnknown,
             format!("Mercenary variant id {} is not recognized.", mercenary.variant_id),
@@ -312,7 +360,7 @@ fn validate_mercenary_level(save: &Save, issues: &mut Vec<ValidationIssue>) {
     let mercenary_level = mercenary_level_from_experience(mercenary.experience, xp_rate);
     let player_level = save.attributes.level();
 
-    if let Some(name_count) = mercenary_name_count_for_variant_id(mercenary.variant_id) {
+    if let Some(name_count) = name_count {
         if mercenary.name_id as usize >= name_count {
             issues.push(issue(
                 ValidationCode::MercenaryNameIdOutOfRange,
@@ -343,26 +391,181 @@ fn validate_mercenary_level(save: &Save, issues: &mut Vec<ValidationIssue>) {
     }
 }
 
-fn validate_mercenary_hire_state(save: &Save, issues: &mut Vec<ValidationIssue>) {
-    if save.items.mercenary_hire_state_changed(save.character.mercenary.is_hired()) {
-        issues.push(issue(
-            ValidationCode::MercenaryHireStateToggleUnsupported,
-            "Changing mercenary.id between 0 (no mercenary hired) and nonzero (mercenary hired) is not supported by this version of halbu, because the mercenary item subsection in the raw item tail is not rewritten yet.",
-        ));
+fn validate_max_vitals(
+    save: &Save,
+    game_data: Option<&GameData>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    // Saves built in code start at generic placeholder values rather than the class table,
+    // so only attributes read from a save file are checked.
+    if save.attributes.present_stats().is_none() {
+        return;
+    }
+    // Classes without a table row cannot be checked.
+    let Ok(class_data) = respec::class_data(game_data, save.character.class) else {
+        return;
+    };
+
+    let attributes = &save.attributes;
+    let stored = [&attributes.maxhp, &attributes.maxmana, &attributes.maxstamina];
+    let derived = respec::max_vitals(save, &class_data);
+    for ((stat, derived), label) in stored.into_iter().zip(derived).zip(["life", "mana", "stamina"])
+    {
+        if stat.value != derived {
+            issues.push(warning(
+                ValidationCode::MaxVitalsMismatch,
+                format!(
+                    "Stored maximum {label} ({}) differs from the {} derived from class, level and stats.",
+                    f64::from(stat.value) / 256.0,
+                    f64::from(derived) / 256.0
+                ),
+            ));
+        }
+    }
+}
+
+/// Spent plus unspent skill and stat points against those earned from level and rewards.
+///
+/// More points than the budget cannot be earned in game. Fewer means a recorded reward or
+/// level was never paid out. Rewards still marked pending count toward the budget but not
+/// toward the points required.
+fn validate_point_budgets(
+    save: &Save,
+    game_data: Option<&GameData>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let Ok(class_data) = respec::class_data(game_data, save.character.class) else {
+        return;
+    };
+
+    let attributes = &save.attributes;
+    let levels_gained = u32::from(save.attributes.level().saturating_sub(1));
+    let [pending_skill_points, pending_stat_points] = respec::pending_reward_points(&save.quests);
+    let spent_skill_points: u32 = save.skills.points.iter().map(|points| u32::from(*points)).sum();
+    // A stat below its class base does not give back points to spend elsewhere.
+    let spent_stat_points: u32 = [
+        (attributes.strength.value, class_data.strength),
+        (attributes.dexterity.value, class_data.dexterity),
+        (attributes.vitality.value, class_data.vitality),
+        (attributes.energy.value, class_data.energy),
+    ]
+    .into_iter()
+    .map(|(value, base)| value.saturating_sub(base))
+    .sum();
+
+    let checks = [
+        (
+            "Skill",
+            ValidationCode::SkillPointsExceedBudget,
+            spent_skill_points + attributes.newskills.value,
+            levels_gained * class_data.skill_points_per_level
+                + respec::quest_skill_points(&save.quests),
+            pending_skill_points,
+        ),
+        (
+            "Stat",
+            ValidationCode::StatPointsExceedBudget,
+            spent_stat_points + attributes.statpts.value,
+            levels_gained * class_data.stat_points_per_level
+                + respec::quest_stat_points(&save.quests),
+            pending_stat_points,
+        ),
+    ];
+    for (label, exceeded_code, total, earned, pending) in checks {
+        if total > earned + pending {
+            issues.push(issue(
+                exceeded_code,
+                format!(
+                    "{label} points spent and unspent ({total}) exceed the {} available from level and quest rewards.",
+                    earned + pending
+                ),
+            ));
+        } else if total < earned {
+            issues.push(warning(
+                ValidationCode::QuestRewardPointsMismatch,
+                format!(
+                    "{label} points spent and unspent ({total}) are fewer than the {earned} earned from level and quest rewards."
+                ),
+            ));
+        }
+    }
+}
+
+/// Skills with points against their tree prerequisites and required character level.
+///
+/// A skill reports every prerequisite without points that lies between it and the nearest
+/// prerequisites that have points, so one gap in a chain is reported once.
+fn validate_skill_requirements(
+    save: &Save,
+    game_data: Option<&GameData>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let Ok(requirements) = skills::skill_requirements(game_data, save.character.class) else {
+        return;
+    };
+
+    let level = save.attributes.level();
+    let points = &save.skills.points;
+    for (index, skill) in requirements.iter().enumerate() {
+        if points[index] == 0 {
+            continue;
+        }
+        if skill.required_level > level {
+            issues.push(issue(
+                ValidationCode::SkillLevelRequirementUnmet,
+                format!(
+                    "{} requires character level {} but the character is level {level}.",
+                    skill.name, skill.required_level
+                ),
+            ));
+        }
+
+        let mut missing: Vec<usize> = Vec::new();
+        let mut pending = skill.prerequisites.clone();
+        while let Some(prerequisite) = pending.pop() {
+            if points[prerequisite] == 0 && !missing.contains(&prerequisite) {
+                missing.push(prerequisite);
+                pending.extend(&requirements[prerequisite].prerequisites);
+            }
+        }
+        if !missing.is_empty() {
+            missing.sort_unstable();
+            let names: Vec<&str> =
+                missing.iter().map(|index| requirements[*index].name.as_str()).collect();
+            issues.push(issue(
+                ValidationCode::SkillPrerequisiteMissing,
+                format!(
+                    "{} has points but its required skills have none: {}.",
+                    skill.name,
+                    names.join(", ")
+                ),
+            ));
+        }
     }
 }
 
 /// Build a validation report for a save model.
 pub(crate) fn build_validation_report(save: &Save) -> ValidationReport {
+    build_validation_report_with(save, None)
+}
+
+/// Build a validation report, taking mod tables from `game_data` when given.
+pub(crate) fn build_validation_report_with(
+    save: &Save,
+    game_data: Option<&GameData>,
+) -> ValidationReport {
     let mut report = ValidationReport::default();
 
     validate_class(save, &mut report.issues);
     validate_character_name(save, &mut report.issues);
     validate_level_sync(save, &mut report.issues);
+    validate_experience(save, &mut report.issues);
     validate_progression(save, &mut report.issues);
     validate_quest_state(save, &mut report.issues);
-    validate_mercenary_level(save, &mut report.issues);
-    validate_mercenary_hire_state(save, &mut report.issues);
+    validate_mercenary_level(save, game_data, &mut report.issues);
+    validate_max_vitals(save, game_data, &mut report.issues);
+    validate_point_budgets(save, game_data, &mut report.issues);
+    validate_skill_requirements(save, game_data, &mut report.issues);
 
     report
 }
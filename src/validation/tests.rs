@@ -285,13 +285,211 @@ fn validate_reports_mercenary_data_without_hire() {
 }
 
 #[test]
-fn validate_reports_mercenary_hire_state_toggle() {
+fn validate_warns_when_max_vitals_diverge_from_class_table() {
+    let mut save = Save::parse(include_bytes!("../../assets/test/Ayame.d2s"), Strictness::Strict)
+        .unwrap()
+        .save;
+    save.attributes.set_max_hp(2000);
+    save.attributes.strength.value -= 10;
+    save.attributes.vitality.value += 10;
+
+    let report = save.validate();
+    let mismatches: Vec<_> = report
+        .issues
+        .iter()
+        .filter(|issue| issue.code == ValidationCode::MaxVitalsMismatch)
+        .collect();
+    assert_eq!(mismatches.len(), 2, "{mismatches:?}");
+    assert!(mismatches.iter().all(|issue| !issue.blocking));
+    assert!(mismatches[0].message.contains("maximum life (2000)"), "{}", mismatches[0].message);
+
+    save.recompute_vitals().unwrap();
+    assert!(save.validate().issues.is_empty());
+    // 10 vitality at 3 life and 1 stamina each, for an Amazon.
+    assert_eq!(save.attributes.get_max_hp(), 1111 + 30);
+    assert_eq!(save.attributes.get_max_stamina(), 447 + 10);
+}
+
+#[test]
+fn validate_warns_when_experience_does_not_match_level() {
     let mut save = Save::default();
-    save.character.mercenary.id = 1;
+    save.set_level(90);
+
+    let experience_issue = |save: &Save| {
+        save.validate()
+            .issues
+            .iter()
+            .any(|issue| issue.code == ValidationCode::ExperienceOutOfLevelRange && !issue.blocking)
+    };
+    assert!(experience_issue(&save));
+    assert_eq!(save.level_from_experience(), 1);
+
+    save.set_level_with_experience(90);
+    assert_eq!(save.attributes.experience.value, 1_618_470_619);
+    assert_eq!(save.level_from_experience(), 90);
+    assert!(!experience_issue(&save));
+
+    save.attributes.experience.value = 1_764_543_065;
+    assert!(experience_issue(&save));
+}
 
-    let report = build_validation_report(&save);
-    assert!(report
+fn point_issues(save: &Save) -> Vec<(ValidationCode, bool, String)> {
+    let codes = [
+        ValidationCode::QuestRewardPointsMismatch,
+        ValidationCode::SkillPointsExceedBudget,
+        ValidationCode::StatPointsExceedBudget,
+    ];
+    save.validate()
         .issues
-        .iter()
-        .any(|issue| issue.code == ValidationCode::MercenaryHireStateToggleUnsupported));
+        .into_iter()
+        .filter(|issue| codes.contains(&issue.code))
+        .map(|issue| (issue.code, issue.blocking, issue.message))
+        .collect()
+}
+
+#[test]
+fn validate_warns_when_points_do_not_match_quest_rewards() {
+    use crate::quests::{QuestId, QuestState};
+
+    // Reward recorded without its points.
+    let mut save = Save::default();
+    save.quests.set_state(
+        Difficulty::Normal,
+        QuestId::LamEsensTome,
+        QuestState::CompletedRewardCollected,
+    );
+    assert_eq!(
+        point_issues(&save),
+        [(
+            ValidationCode::QuestRewardPointsMismatch,
+            false,
+            "Stat points spent and unspent (0) are fewer than the 5 earned from level and quest rewards."
+                .to_string()
+        )]
+    );
+
+    // A pending reward may or may not have been paid out.
+    save.quests.set_state(
+        Difficulty::Normal,
+        QuestId::LamEsensTome,
+        QuestState::CompletedRewardPending,
+    );
+    assert!(point_issues(&save).is_empty());
+    save.attributes.statpts.value = 5;
+    assert!(point_issues(&save).is_empty());
+}
+
+#[test]
+fn validate_blocks_points_beyond_level_and_quest_budget() {
+    use crate::quests::QuestId;
+
+    let mut save = Save::new_with_class_stats(crate::format::FormatId::V99, Class::Paladin);
+    save.set_level_with_experience(30);
+    save.grant_quest_reward(Difficulty::Normal, QuestId::DenOfEvil);
+    // 29 levels at one skill point and five stat points each, plus Den of Evil.
+    save.skills.set(10, 20);
+    save.attributes.newskills.value = 10;
+    save.attributes.strength.value += 100;
+    save.attributes.statpts.value = 45;
+    assert!(point_issues(&save).is_empty(), "{:?}", point_issues(&save));
+
+    save.skills.set(11, 1);
+    // Points taken from a stat below its class base cannot be spent elsewhere.
+    save.attributes.energy.value -= 5;
+    save.attributes.strength.value += 5;
+    assert_eq!(
+        point_issues(&save),
+        [
+            (
+                ValidationCode::SkillPointsExceedBudget,
+                true,
+                "Skill points spent and unspent (31) exceed the 30 available from level and quest rewards."
+                    .to_string()
+            ),
+            (
+                ValidationCode::StatPointsExceedBudget,
+                true,
+                "Stat points spent and unspent (150) exceed the 145 available from level and quest rewards."
+                    .to_string()
+            ),
+        ]
+    );
+    assert!(!save.validate().is_valid());
+}
+
+fn skill_requirement_issues(save: &Save) -> Vec<(ValidationCode, String)> {
+    let codes =
+        [ValidationCode::SkillPrerequisiteMissing, ValidationCode::SkillLevelRequirementUnmet];
+    save.validate()
+        .issues
+        .into_iter()
+        .filter(|issue| codes.contains(&issue.code))
+        .inspect(|issue| assert!(issue.blocking))
+        .map(|issue| (issue.code, issue.message))
+        .collect()
+}
+
+#[test]
+fn validate_blocks_skills_without_prerequisites_or_level() {
+    let mut save = Save::new(crate::format::FormatId::V99, Class::Sorceress);
+    save.set_level_with_experience(30);
+    for (skill, points) in [("Ice Bolt", 1), ("Ice Blast", 1), ("Glacial Spike", 1)] {
+        save.skills.set_by_name_d2r(Class::Sorceress, skill, points).unwrap();
+    }
+    save.skills.set_by_name_d2r(Class::Sorceress, "Frost Nova", 1).unwrap();
+    save.skills.set_by_name_d2r(Class::Sorceress, "Blizzard", 1).unwrap();
+    save.skills.set_by_name_d2r(Class::Sorceress, "Frozen Orb", 20).unwrap();
+    assert!(skill_requirement_issues(&save).is_empty());
+
+    save.skills.set_by_name_d2r(Class::Sorceress, "Ice Blast", 0).unwrap();
+    save.skills.set_by_name_d2r(Class::Sorceress, "Glacial Spike", 0).unwrap();
+    assert_eq!(
+        skill_requirement_issues(&save),
+        [(
+            ValidationCode::SkillPrerequisiteMissing,
+            "Blizzard has points but its required skills have none: Ice Blast, Glacial Spike."
+                .to_string()
+        )]
+    );
+
+    save.skills.set_all(0);
+    save.skills.set_by_name_d2r(Class::Sorceress, "Frozen Orb", 1).unwrap();
+    save.set_level_with_experience(10);
+    let issues = skill_requirement_issues(&save);
+    assert_eq!(
+        issues[0],
+        (
+            ValidationCode::SkillLevelRequirementUnmet,
+            "Frozen Orb requires character level 30 but the character is level 10.".to_string()
+        )
+    );
+    assert_eq!(
+        issues[1].1,
+        "Frozen Orb has points but its required skills have none: Ice Bolt, Frost Nova, Ice Blast, Glacial Spike, Blizzard."
+    );
+
+    // The Warlock has no built-in tree, so its skills are not checked without game data.
+    let mut warlock = Save::new(crate::format::FormatId::V105, Class::Warlock);
+    warlock.skills.set(29, 1);
+    assert!(skill_requirement_issues(&warlock).is_empty());
+}
+
+#[test]
+fn validate_skill_requirements_use_game_data_tables() {
+    let game_data =
+        GameData::load(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/test/data")).unwrap();
+    let mut save = Save::new(crate::format::FormatId::V99, Class::Barbarian);
+    // Cleaving Strike needs Bash and level 6 in the test tables.
+    save.skills.set(2, 1);
+    let codes: Vec<ValidationCode> =
+        save.validate_with(&game_data).issues.into_iter().map(|issue| issue.code).collect();
+    assert!(codes.contains(&ValidationCode::SkillPrerequisiteMissing));
+    assert!(codes.contains(&ValidationCode::SkillLevelRequirementUnmet));
+
+    save.skills.set(0, 1);
+    save.set_level_with_experience(6);
+    let codes: Vec<ValidationCode> =
+        save.validate_with(&game_data).issues.into_iter().map(|issue| issue.code).collect();
+    assert!(!codes.contains(&ValidationCode::SkillPrerequisiteMissing));
+    assert!(!codes.contains(&ValidationCode::SkillLevelRequirementUnmet));
 }
@@ -34,6 +34,26 @@ impl Section {
     }
 }
 
+/// Named byte spans of the waypoints section, relative to its start.
+pub(crate) fn field_spans() -> Vec<(Range<usize>, String)> {
+    let mut spans = vec![(Section::Header.range(), "header".to_string())];
+    let difficulties =
+        [("normal", Section::Normal), ("nightmare", Section::Nightmare), ("hell", Section::Hell)];
+    for (difficulty_name, difficulty_section) in difficulties {
+        let start = difficulty_section.range().start;
+        for (field_section, field_name) in
+            [(Section::DifficultyHeader, "header"), (Section::DifficultyWaypointsValue, "flags")]
+        {
+            let field_range = field_section.range();
+            spans.push((
+                (start + field_range.start)..(start + field_range.end),
+                format!("{difficulty_name}.{field_name}"),
+            ));
+        }
+    }
+    spans
+}
+
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum WaypointError {
     WrongAct { waypoint: Waypoint, expected: Act, actual: Act },
@@ -0,0 +1,57 @@
+#![cfg(feature = "cli")]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use halbu::format::FormatId;
+use halbu::{Save, Strictness};
+
+fn halbu(args: &[&str]) -> i32 {
+    let status = Command::new(env!("CARGO_BIN_EXE_halbu"))
+        .args(args)
+        .output()
+        .expect("halbu binary should run")
+        .status;
+    status.code().expect("halbu should exit with a code")
+}
+
+fn output_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name)
+}
+
+#[test]
+fn validate_reports_success_for_clean_save() {
+    assert_eq!(halbu(&["validate", "assets/test/Joe.d2s"]), 0);
+    assert_eq!(halbu(&["info", "assets/test/Warlock_v105.d2s"]), 0);
+}
+
+#[test]
+fn convert_blocked_by_compatibility_exits_with_blocking_code() {
+    let output = output_path("cli_warlock_v99.d2s");
+    let output = output.to_str().unwrap();
+    assert_eq!(halbu(&["convert", "assets/test/Warlock_v105.d2s", "--to", "v99", "-o", output]), 3);
+    assert_eq!(halbu(&["convert", "assets/test/Joe.d2s", "--to", "v12", "-o", output]), 2);
+}
+
+#[test]
+fn convert_then_set_writes_edited_save() {
+    let output = output_path("cli_joe_v105.d2s");
+    let output = output.to_str().unwrap();
+    assert_eq!(halbu(&["convert", "assets/test/Joe.d2s", "--to", "v105", "-o", output]), 0);
+    assert_eq!(halbu(&["set", output, "level=75", "strength=220"]), 0);
+    assert_eq!(halbu(&["set", output, "level=0"]), 3);
+    assert_eq!(halbu(&["set", output, "unknown=1"]), 2);
+    assert_eq!(halbu(&["set", output, "strength"]), 2);
+    assert_eq!(halbu(&["set", output, "strength=abc"]), 2);
+
+    let save = Save::parse(&std::fs::read(output).unwrap(), Strictness::Strict).unwrap().save;
+    assert_eq!(save.format(), FormatId::V105);
+    assert_eq!(save.character.level(), 75);
+    assert_eq!(save.attributes.strength.value, 220);
+    assert_eq!(save.level_from_experience(), 75);
+
+    assert_eq!(halbu(&["set", output, "level=80", "experience=1000000000"]), 0);
+    let save = Save::parse(&std::fs::read(output).unwrap(), Strictness::Strict).unwrap().save;
+    assert_eq!(save.character.level(), 80);
+    assert_eq!(save.attributes.experience.value, 1_000_000_000);
+}
@@ -31,7 +31,6 @@ fn assert_same_model(left: Save, right: Save, label: &str) {
         // Ignore format bookkeeping and raw/opaque payload preservation.
         save.set_format(FormatId::Unknown(0));
         save.character.raw_section.clear();
-        save.items = halbu::items::Placeholder::default();
     }
 
     assert_eq!(left, right, "{label}: semantic model mismatch");
@@ -52,6 +51,7 @@ fn same_format_roundtrip_keeps_model() {
         let encoded = original
             .encode_for(format_id, CompatibilityChecks::Enforce)
             .unwrap_or_else(|e| panic!("encode {name}: {e}"));
+        assert!(encoded == bytes, "{name}: unmodified save did not re-encode byte-identically");
         let reparsed = parse_strict_clean(name, &encoded);
         assert_eq!(reparsed.format(), format_id, "{name}: format drift");
         assert_same_model(original, reparsed, name);
@@ -65,6 +65,7 @@ fn v105_mode_goldens_roundtrip_semantic() {
         let encoded = original
             .encode_for(FormatId::V105, CompatibilityChecks::Enforce)
             .unwrap_or_else(|e| panic!("encode {name}: {e}"));
+        assert!(encoded == bytes, "{name}: unmodified save did not re-encode byte-identically");
         let reparsed = parse_strict_clean(name, &encoded);
         assert_eq!(reparsed.format(), FormatId::V105, "{name}: format drift");
         assert_same_model(original, reparsed, name);
@@ -237,3 +238,89 @@ fn encode_for_force_bypasses_compatibility_blockers() {
         .expect("forced encode should bypass compatibility blockers");
     assert!(!forced.is_empty(), "forced encode should still produce bytes");
 }
+
+#[test]
+fn v99_to_v96_back_to_v99_keeps_model() {
+    let source = &include_bytes!("../assets/test/Joe.d2s")[..];
+    let mut start = parse_strict_clean("Joe_v99", source);
+    // Classic saves have no D2R menu appearance block.
+    start.character.resurrected_menu_appearance = [0x00; 48];
+
+    let encoded_v96 = start
+        .encode_for(FormatId::V96, CompatibilityChecks::Enforce)
+        .unwrap_or_else(|e| panic!("encode Joe_v99 -> v96: {e}"));
+    assert_eq!(u32::from_le_bytes(encoded_v96[4..8].try_into().unwrap()), 96);
+    assert_eq!(&encoded_v96[20..24], b"Joe\0");
+
+    let as_v96 = parse_strict_clean("Joe_as_v96", &encoded_v96);
+    assert_eq!(as_v96.format(), FormatId::V96, "Joe_as_v96: wrong format");
+    assert!(!as_v96.items.is_raw(), "Joe_as_v96: items fell back to raw bytes");
+    assert_eq!(as_v96.character.name, start.character.name, "Joe v99 -> v96: name mismatch");
+    assert_eq!(as_v96.character.class, start.character.class, "Joe v99 -> v96: class mismatch");
+    assert_eq!(as_v96.character.level(), start.character.level(), "Joe v99 -> v96: level mismatch");
+    assert_eq!(as_v96.attributes, start.attributes, "Joe v99 -> v96: attributes mismatch");
+    assert_eq!(as_v96.quests, start.quests, "Joe v99 -> v96: quests mismatch");
+    // Item versions keep their on-disk spelling (`101` in v96, `5` in v99).
+    let codes = |save: &Save| -> Vec<String> {
+        save.items.player.items.iter().map(|item| item.code.clone()).collect()
+    };
+    assert_eq!(codes(&as_v96), codes(&start), "Joe v99 -> v96: item mismatch");
+
+    let encoded_v99 = as_v96
+        .encode_for(FormatId::V99, CompatibilityChecks::Enforce)
+        .unwrap_or_else(|e| panic!("encode Joe_v96 -> v99: {e}"));
+    let back_to_v99 = parse_strict_clean("Joe_back_to_v99", &encoded_v99);
+    assert_eq!(back_to_v99.format(), FormatId::V99, "Joe_back_to_v99: wrong format");
+    assert_same_model(start, back_to_v99, "Joe v99 -> v96 -> v99");
+}
+
+#[test]
+fn pre_110_versions_are_rejected() {
+    let joe = parse_strict_clean("Joe_v99", &include_bytes!("../assets/test/Joe.d2s")[..]);
+    let mut bytes = joe.encode_for(FormatId::V96, CompatibilityChecks::Enforce).unwrap();
+
+    for version in [71u32, 87, 89, 92] {
+        bytes[4..8].copy_from_slice(&version.to_le_bytes());
+        for strictness in [Strictness::Strict, Strictness::Lax] {
+            let error = Save::parse(&bytes, strictness)
+                .expect_err("saves older than 1.10 should not decode");
+            assert!(error.message.contains("predates Diablo II 1.10"), "{error}");
+        }
+    }
+    joe.encode_for(FormatId::V92, CompatibilityChecks::Enforce)
+        .expect_err("version 92 is not an encode target");
+}
+
+#[test]
+fn check_compatibility_reports_blocking_long_name_to_v96() {
+    let mut save = Save::new(FormatId::V99, Class::Sorceress);
+    for name in ["Sixteen_Letters_", "Björn"] {
+        save.character.name = name.to_string();
+        let issues = save.check_compatibility(FormatId::V96);
+        assert!(
+            issues.iter().any(|issue| issue.blocking
+                && issue.code == CompatibilityCode::ClassicNameRequiresShortAscii),
+            "expected blocking ClassicNameRequiresShortAscii issue for {name}, got: {:?}",
+            issues
+        );
+        assert!(save.check_compatibility(FormatId::V99).is_empty(), "{name}: v99 should accept");
+    }
+}
+
+#[test]
+fn all_fixtures_roundtrip_byte_exact() {
+    let fixture_directory = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/test");
+    let mut fixture_count = 0;
+    for entry in std::fs::read_dir(fixture_directory).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|extension| extension != "d2s") {
+            continue;
+        }
+        let bytes = std::fs::read(&path).unwrap();
+        let report = Save::verify_roundtrip(&bytes)
+            .unwrap_or_else(|e| panic!("verify {}: {e}", path.display()));
+        assert!(report.is_exact(), "{}: {:?}", path.display(), report.mismatches);
+        fixture_count += 1;
+    }
+    assert!(fixture_count >= 7, "expected every .d2s fixture, found {fixture_count}");
+}